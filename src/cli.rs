@@ -0,0 +1,601 @@
+use std::error::Error;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use image::{ImageFormat, RgbaImage, Rgba};
+use minecraft_titles::{Font, FontLoadOptions, LayoutProfile, GuideTemplate, Outline, CharSet, DisallowedPolicy, MAX_OUTLINE_THICKNESS, MAX_MOCKUP_GUI_SCALE, measure_text, title_screen_mockup, sanitize_text, font_summary, DebugOverlayColors, RenderTimings};
+use crate::clipboard::read_clipboard_text;
+use crate::self_test::run_self_test;
+use crate::texture::{FontFiles, load_embedded_font, render_texture, render_frames, render_into_atlas, write_atomically, save_numbered, encode_png, parse_hex_color, AtlasRegion, Alignment, LineFit, TextureOptions, DEFAULT_MAX_LINE_WIDTH, MAX_OUTPUT_SCALE};
+
+const USAGE: &str = "\
+Usage: rust_bitmap_renderer --list-fonts
+       rust_bitmap_renderer --self-test [--self-test-out <FILE>]
+       rust_bitmap_renderer (--text <TEXT> | --from-clipboard | --frame <TEXT>...) --out <FILE> [--numbered] [--font <FNT>] [--kerning] [--letter-spacing <PIXELS>] [--scale <FACTOR> [--legacy-guide-scale]] [--scale-output <N>] [--expand-glyph-crop <PIXELS>]
+                            [--advance-adjust <PIXELS>] [--lenient] [--no-sanitize] [--frame-time <TICKS>]
+                            [--layout <PROFILE>] [--bold] [--shadow] [--color <RRGGBB>] [--outline <PIXELS> [--outline-color <RRGGBB>]]
+                            [--gradient <RRGGBB>,<RRGGBB>]
+                            [--align <ALIGNMENT>] [--guides <TEMPLATE>] [--guide-color <RRGGBBAA>] [--baseline-guide]
+                            [--allowed-chars <SET> [--on-disallowed <POLICY>]] [--dry-run]
+                            [--into <PNG> --at <X>,<Y> [--region <W>x<H>] [--clip]]
+                            [--max-width <PIXELS>] [--auto-split | --wrap] [--strict-clipping] [--debug-overlay <FILE>] [--mockup <SCALE>] [--verbose]
+
+Renders a title texture without opening the window.
+
+  --list-fonts       List the available fonts with their metadata and coverage
+  --self-test        Check the installation: render a fixed title with the embedded font and compare
+                     it with the image this version is known to make; prints PASS or FAIL per step
+  --self-test-out <FILE>
+                     Also save the self test's texture here; nothing is written otherwise
+  --text <TEXT>      Text to render; each newline starts another line
+  --from-clipboard   Render the text on the clipboard instead, as copied from a spreadsheet or document
+  --no-sanitize      Keep control, zero-width and bidi format characters and look them up in the font;
+                     by default they are stripped from the text with a warning
+  --frame <TEXT>     Render an animation instead: one frame per --frame, in order, stacked into a strip
+                     of equally sized frames, with the .mcmeta saved next to it as <FILE>.mcmeta
+  --frame-time <TICKS>
+                     How long each frame shows, in game ticks (default 1)
+  --out <FILE>       Where to save the PNG; with --into it defaults to the --into file itself
+  --numbered         Save as the first free <NAME>_1.png, <NAME>_2.png and so on next to --out
+                     instead, and print the name; copies of the tool saving at once get one each
+  --font <FNT>       Render with this BMFont file and the atlases next to it instead of the embedded font
+  --kerning          Apply the font's kerning pairs
+  --letter-spacing <PIXELS>
+                     Pixels added between characters; negative values tighten the text
+  --scale <FACTOR>   Scale of the texture, text and guides together, nearest neighbor (default 1.5)
+  --legacy-guide-scale
+                     Scale only the UV guides, at most 32 px tall, and leave the text at the font's
+                     size, as older versions did; the bands then don't line up with the glyphs
+  --scale-output <N> Save the finished texture, background included, at 2, 3 or 4 times the pixel
+                     density for high resolution packs; every pixel becomes an exact N x N block
+  --guides <TEMPLATE>
+                     UV helper behind the text: 'bands' (default) for the highlighted columns and
+                     colored bands, 'boxes' to outline every glyph, 'frame' for a 1px border or 'none'
+  --guide-color <RRGGBBAA>
+                     Color of the guides' highlight, boxes or frame, in hex with optional alpha
+  --baseline-guide   Draw the red debug line along the baseline; left out by default
+  --expand-glyph-crop <PIXELS>
+                     Grow every glyph's atlas rect by this many pixels per side
+  --advance-adjust <PIXELS>
+                     Subtract this from every glyph's advance (default 3, which suits the embedded font)
+  --lenient          Skip unreadable lines of a damaged --font file instead of failing
+  --layout <PROFILE> 'legacy' (default) for the tool's trimmed spacing, 'vanilla' to space glyphs
+                     exactly as the game does, by their full advances, or 'exported' for the full
+                     advances plus the spacing the --font file declares
+  --bold             Draw the text bold, the way the game does
+  --align <ALIGNMENT>
+                     Place split lines and animation frames 'left', 'center' (default) or 'right'
+  --shadow           Draw the game's drop shadow under the text
+  --color <RRGGBB>   Tint the text with this hex color, such as #FFAA00; an optional fourth byte
+                     scales its alpha. The default, white, keeps the font's colors
+  --outline <PIXELS> Draw a 1 to 3 pixel outline around the text, growing the texture by as much on
+                     every side
+  --outline-color <RRGGBB>
+                     Color of the outline (default black)
+  --gradient <RRGGBB>,<RRGGBB>
+                     Color the text with a left to right blend of the two hex colors instead, keeping
+                     the font's alpha; the shadow darkens the blend
+  --max-width <PIXELS>
+                     Warn with a suggested two-line split when the text is wider than this
+  --auto-split       Render text wider than the max width (default 256) as two centered lines
+  --wrap             Wrap text wider than the max width (default 256) onto as many lines as it needs
+  --strict-clipping  Fail instead of warning when a glyph, or its outline or shadow, would be cut off at
+                     the texture edge
+  --debug-overlay <FILE>
+                     Also save the per-glyph debug overlay as its own PNG
+  --mockup <SCALE>   Also save a preview of the text on a stand-in title screen at GUI scale 1 to 4,
+                     next to the output as <FILE>.mockup.png; it is a preview, not a texture
+  --allowed-chars <SET>
+                     Only let the font draw these characters and ranges, such as 'A-Z0-9!?'; spaces are
+                     always allowed
+  --on-disallowed <POLICY>
+                     What to do with other characters: 'fail' (default), 'uppercase' to use their
+                     uppercase letter when it is allowed, or 'replace=<C>' to use it and C otherwise
+  --dry-run          Lay the text out and print its size without rendering or saving anything;
+                     --out is not needed
+  --into <PNG>       Paste the texture into a copy of this existing texture atlas instead of saving it alone
+  --at <X>,<Y>       Top left corner of the texture in the --into atlas
+  --region <W>x<H>   Largest space the texture may cover in the atlas (default: up to the atlas edges)
+  --clip             Clip a texture that does not fit, with a warning, instead of failing
+  -v, --verbose      Print how long each render step took
+  --help             Show this message";
+
+// Exit code for bad arguments, as opposed to a failed render
+const USAGE_ERROR: u8 = 2;
+
+enum Command {
+    Help,
+    ListFonts,
+    SelfTest(Option<PathBuf>),
+    Render(Box<CliArgs>),
+}
+
+// What to render: one texture, or the frames of an animation
+enum Input {
+    Text(String),
+    // Read when the render starts, so a bad clipboard fails the render rather than the arguments
+    Clipboard,
+    Frames { texts: Vec<String>, frame_time: u32 },
+}
+
+// An existing atlas the texture is pasted into
+struct IntoAtlas {
+    atlas: PathBuf,
+    region: AtlasRegion,
+    clip: bool,
+}
+
+struct CliArgs {
+    input: Input,
+    // Empty for a dry run, which saves nothing
+    out: PathBuf,
+    // Save as the next free numbered name next to `out`, see save_numbered
+    numbered: bool,
+    dry_run: bool,
+    into: Option<IntoAtlas>,
+    font: Option<PathBuf>,
+    debug_overlay: Option<PathBuf>,
+    // GUI scale of the title screen preview saved next to the texture
+    mockup: Option<u32>,
+    verbose: bool,
+    options: TextureOptions,
+    load_options: FontLoadOptions,
+}
+
+// Headless mode: render the texture described by the arguments and save it, reporting on stderr
+pub fn run(args: &[String]) -> ExitCode {
+    #[cfg(windows)]
+    attach_parent_console();
+
+    let cli_args = match parse_args(args) {
+        Ok(Command::Render(cli_args)) => cli_args,
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::ListFonts) => return list_fonts(),
+        Ok(Command::SelfTest(out)) => return self_test(out.as_deref()),
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+
+    match render_to_file(&cli_args) {
+        Ok(warnings) => {
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut text = None;
+    let mut from_clipboard = false;
+    let mut frames = Vec::new();
+    let mut frame_time = 1;
+    let mut into = None;
+    let mut at = None;
+    let mut region = None;
+    let mut clip = false;
+    let mut dry_run = false;
+    let mut numbered = false;
+    let mut out = None;
+    let mut font = None;
+    let mut debug_overlay = None;
+    let mut mockup = None;
+    let mut verbose = false;
+    let mut guide_color = None;
+    let mut outline_thickness = None;
+    let mut outline_color = Rgba([0, 0, 0, 255]);
+    let mut self_test = false;
+    let mut self_test_out = None;
+    let mut load_options = FontLoadOptions::default();
+    let mut options = TextureOptions {
+        sanitize: true,
+        use_kerning: false,
+        kern_across_spaces: None,
+        scale_factor: 1.5,
+        legacy_guide_scale: false,
+        output_scale: 1,
+        background_choice: 0,
+        guides_mirrored: false,
+        guides: GuideTemplate::current_bands(),
+        draw_baseline_guide: false,
+        letter_spacing: None,
+        alignment: Alignment::Center,
+        layout_profile: LayoutProfile::Legacy,
+        bold: false,
+        shadow: false,
+        tint: Rgba([255, 255, 255, 255]),
+        outline: None,
+        gradient: None,
+        allowed_chars: None,
+        disallowed_policy: DisallowedPolicy::Fail,
+        max_line_width: None,
+        line_fit: LineFit::Warn,
+        strict_clipping: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--text" => text = Some(value()?.clone()),
+            "--from-clipboard" => from_clipboard = true,
+            "--frame" => frames.push(value()?.clone()),
+            "--frame-time" => {
+                let ticks = value()?;
+                frame_time = ticks.parse().ok()
+                    .filter(|ticks| *ticks > 0)
+                    .ok_or(format!("--frame-time must be a positive whole number of ticks, got '{}'", ticks))?;
+            }
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--font" => font = Some(PathBuf::from(value()?)),
+            "--debug-overlay" => debug_overlay = Some(PathBuf::from(value()?)),
+            "--mockup" => {
+                let scale = value()?;
+                mockup = Some(scale.parse().ok()
+                    .filter(|scale| (1..=MAX_MOCKUP_GUI_SCALE).contains(scale))
+                    .ok_or(format!("--mockup must be a GUI scale from 1 to {}, got '{}'", MAX_MOCKUP_GUI_SCALE, scale))?);
+            }
+            "--expand-glyph-crop" => {
+                let pixels = value()?;
+                load_options.expand_glyph_crop = pixels.parse()
+                    .map_err(|_| format!("--expand-glyph-crop must be a whole number of pixels, got '{}'", pixels))?;
+            }
+            "--max-width" => {
+                let pixels = value()?;
+                options.max_line_width = Some(pixels.parse()
+                    .map_err(|_| format!("--max-width must be a whole number of pixels, got '{}'", pixels))?);
+            }
+            "--auto-split" => options.line_fit = LineFit::Split,
+            "--wrap" => options.line_fit = LineFit::Wrap,
+            "--strict-clipping" => options.strict_clipping = true,
+            "--advance-adjust" => {
+                let pixels = value()?;
+                load_options.glyph_trim.advance_adjust = pixels.parse()
+                    .map_err(|_| format!("--advance-adjust must be a whole number of pixels, got '{}'", pixels))?;
+            }
+            "--lenient" => load_options.lenient = true,
+            "--no-sanitize" => options.sanitize = false,
+            "--layout" => {
+                options.layout_profile = match value()?.as_str() {
+                    "legacy" => LayoutProfile::Legacy,
+                    "vanilla" => LayoutProfile::Vanilla,
+                    "exported" => LayoutProfile::Exported,
+                    other => return Err(format!("--layout must be 'legacy', 'vanilla' or 'exported', got '{}'", other)),
+                };
+            }
+            "--bold" => options.bold = true,
+            "--guides" => {
+                options.guides = match value()?.as_str() {
+                    "bands" => GuideTemplate::current_bands(),
+                    "boxes" => GuideTemplate::per_glyph_boxes(),
+                    "frame" => GuideTemplate::border_frame(),
+                    "none" => GuideTemplate::None,
+                    other => return Err(format!("--guides must be 'bands', 'boxes', 'frame' or 'none', got '{}'", other)),
+                };
+            }
+            "--baseline-guide" => options.draw_baseline_guide = true,
+            "--guide-color" => guide_color = Some(parse_color(value()?, "--guide-color")?),
+            "--color" => options.tint = parse_color(value()?, "--color")?,
+            "--outline" => {
+                let pixels = value()?;
+                outline_thickness = Some(pixels.parse().ok()
+                    .filter(|pixels| (1..=MAX_OUTLINE_THICKNESS).contains(pixels))
+                    .ok_or(format!("--outline must be a thickness from 1 to {} pixels, got '{}'", MAX_OUTLINE_THICKNESS, pixels))?);
+            }
+            "--outline-color" => outline_color = parse_color(value()?, "--outline-color")?,
+            "--gradient" => {
+                let colors = value()?;
+                let (from, to) = colors.split_once(',').ok_or(format!("--gradient must be two hex colors joined by a comma, got '{}'", colors))?;
+                options.gradient = Some((parse_color(from.trim(), "--gradient")?, parse_color(to.trim(), "--gradient")?));
+            }
+            "--allowed-chars" => options.allowed_chars = Some(CharSet::parse(value()?).map_err(|e| e.to_string())?),
+            "--on-disallowed" => {
+                let policy = value()?;
+                options.disallowed_policy = match (policy.as_str(), policy.strip_prefix("replace=")) {
+                    ("fail", _) => DisallowedPolicy::Fail,
+                    ("uppercase", _) => DisallowedPolicy::Uppercase,
+                    (_, Some(replacement)) if replacement.chars().count() == 1 => DisallowedPolicy::Replace(replacement.chars().next().unwrap_or(' ')),
+                    _ => return Err(format!("--on-disallowed must be 'fail', 'uppercase' or 'replace=<C>', got '{}'", policy)),
+                };
+            }
+            "--dry-run" => dry_run = true,
+            "--numbered" => numbered = true,
+            "--align" => {
+                options.alignment = match value()?.as_str() {
+                    "left" => Alignment::Left,
+                    "center" => Alignment::Center,
+                    "right" => Alignment::Right,
+                    other => return Err(format!("--align must be 'left', 'center' or 'right', got '{}'", other)),
+                };
+            }
+            "--into" => into = Some(PathBuf::from(value()?)),
+            "--at" => at = Some(parse_pair(value()?, ',', "--at", "<X>,<Y>")?),
+            "--region" => region = Some(parse_pair(value()?, 'x', "--region", "<W>x<H>")?),
+            "--clip" => clip = true,
+            "--shadow" => options.shadow = true,
+            "--verbose" | "-v" => verbose = true,
+            "--kerning" => options.use_kerning = true,
+            "--letter-spacing" => {
+                let pixels = value()?;
+                options.letter_spacing = pixels.parse().map(Some)
+                    .map_err(|_| format!("--letter-spacing must be a whole number of pixels, got '{}'", pixels))?;
+            }
+            "--legacy-guide-scale" => options.legacy_guide_scale = true,
+            "--scale-output" => {
+                let factor = value()?;
+                options.output_scale = factor.parse().ok()
+                    .filter(|factor| (1..=MAX_OUTPUT_SCALE).contains(factor))
+                    .ok_or(format!("--scale-output must be a whole number from 1 to {}, got '{}'", MAX_OUTPUT_SCALE, factor))?;
+            }
+            "--scale" => {
+                let scale = value()?;
+                options.scale_factor = scale.parse::<f32>().ok()
+                    .filter(|scale| scale.is_finite() && *scale > 0.0)
+                    .ok_or(format!("--scale must be a positive number, got '{}'", scale))?;
+            }
+            "--help" | "-h" => return Ok(Command::Help),
+            "--list-fonts" => return Ok(Command::ListFonts),
+            "--self-test" => self_test = true,
+            "--self-test-out" => self_test_out = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    match (self_test, self_test_out) {
+        (true, out) => return Ok(Command::SelfTest(out)),
+        (false, Some(_)) => return Err("--self-test-out needs --self-test".to_string()),
+        (false, None) => {}
+    }
+
+    options.outline = outline_thickness.map(|thickness| Outline::new(outline_color, thickness));
+
+    if let Some(color) = guide_color {
+        options.guides = match options.guides {
+            GuideTemplate::CurrentBands { upper_band, lower_band, .. } => GuideTemplate::CurrentBands { highlight: color, upper_band, lower_band },
+            GuideTemplate::PerGlyphBoxes { .. } => GuideTemplate::PerGlyphBoxes { color },
+            GuideTemplate::BorderFrame { .. } => GuideTemplate::BorderFrame { color },
+            GuideTemplate::None => return Err("--guide-color cannot be used with --guides none".to_string()),
+        };
+    }
+
+    if options.line_fit != LineFit::Warn {
+        options.max_line_width.get_or_insert(DEFAULT_MAX_LINE_WIDTH);
+    }
+
+    let input = match (text, from_clipboard, frames.is_empty()) {
+        (Some(_), true, _) => return Err("--text and --from-clipboard cannot be used together".to_string()),
+        (Some(_), false, false) | (None, true, false) => return Err("--frame cannot be used with --text or --from-clipboard".to_string()),
+        (Some(text), false, true) => Input::Text(text),
+        (None, true, true) => Input::Clipboard,
+        (None, false, false) => Input::Frames { texts: frames, frame_time },
+        (None, false, true) => return Err("--text is required".to_string()),
+    };
+    if matches!(input, Input::Frames { .. }) && debug_overlay.is_some() {
+        return Err("--debug-overlay cannot be used with --frame".to_string());
+    }
+    if mockup.is_some() && (matches!(input, Input::Frames { .. }) || into.is_some()) {
+        return Err("--mockup cannot be used with --frame or --into".to_string());
+    }
+
+    let into = match (into, at) {
+        (Some(_), _) if matches!(input, Input::Frames { .. }) => return Err("--into cannot be used with --frame".to_string()),
+        (Some(atlas), Some((x, y))) => {
+            let (max_width, max_height) = region.map_or((None, None), |(width, height)| (Some(width), Some(height)));
+            Some(IntoAtlas { atlas, region: AtlasRegion { x, y, max_width, max_height }, clip })
+        }
+        (Some(_), None) => return Err("--into needs --at".to_string()),
+        (None, Some(_)) => return Err("--at needs --into".to_string()),
+        (None, None) if region.is_some() || clip => return Err("--region and --clip need --into".to_string()),
+        (None, None) => None,
+    };
+    let out = match (out, &into) {
+        (Some(out), _) => out,
+        (None, Some(into)) => into.atlas.clone(),
+        (None, None) if dry_run => PathBuf::new(),
+        (None, None) => return Err("--out is required".to_string()),
+    };
+
+    Ok(Command::Render(Box::new(CliArgs {
+        input,
+        out,
+        numbered,
+        dry_run,
+        into,
+        font,
+        debug_overlay,
+        mockup,
+        verbose,
+        options,
+        load_options,
+    })))
+}
+
+// title.png gets its preview in title.mockup.png
+fn mockup_path(out: &Path) -> PathBuf {
+    out.with_extension("mockup.png")
+}
+
+fn parse_color(value: &str, flag: &str) -> Result<Rgba<u8>, String> {
+    parse_hex_color(value).ok_or(format!("{} must be a hex color such as #FFAA00 or #00FF0080, got '{}'", flag, value))
+}
+
+// Two whole numbers joined by `separator`, as in 16,208 or 64x32
+fn parse_pair(value: &str, separator: char, flag: &str, form: &str) -> Result<(u32, u32), String> {
+    value.split_once(separator)
+        .and_then(|(first, second)| Some((first.trim().parse().ok()?, second.trim().parse().ok()?)))
+        .ok_or(format!("{} must be {} in whole pixels, got '{}'", flag, form, value))
+}
+
+// Print a table of every font the tool can render with; for now that is the embedded one
+fn list_fonts() -> ExitCode {
+    let font = match load_embedded_font(&FontLoadOptions::default()) {
+        Ok(font) => font,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let summary = font_summary(&font);
+    let atlas = format!("{}x{}", summary.atlas_size.0, summary.atlas_size.1);
+    let line_height = summary.metrics.map_or("-".to_string(), |metrics| metrics.line_height.to_string());
+    println!("{:<10} {:<28} {:>6} {:>8} {:>5} {:<9} COVERAGE", "NAME", "FACE", "GLYPHS", "KERNING", "LINE", "ATLAS");
+    println!(
+        "{:<10} {:<28} {:>6} {:>8} {:>5} {:<9} {}",
+        "embedded", summary.face, summary.glyph_count, summary.kerning_pair_count, line_height, atlas, summary.block_counts()
+    );
+
+    ExitCode::SUCCESS
+}
+
+fn self_test(out: Option<&Path>) -> ExitCode {
+    let report = run_self_test();
+    println!("{}", report);
+
+    if let (Some(out), Some(png)) = (out, &report.png) {
+        if let Err(e) = write_atomically(out, png) {
+            eprintln!("Error: could not save the self test texture: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    if report.passed() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+fn render_to_file(cli_args: &CliArgs) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut font_load = Duration::ZERO;
+    let font = RenderTimings::time(&mut font_load, || match &cli_args.font {
+        Some(path) => FontFiles::beside(path)?.load(&cli_args.load_options),
+        None => load_embedded_font(&cli_args.load_options),
+    })?;
+    if cli_args.dry_run {
+        return measure(cli_args, &font);
+    }
+    let text = match &cli_args.input {
+        Input::Text(text) => text.clone(),
+        Input::Clipboard => read_clipboard_text()?,
+        Input::Frames { texts, frame_time } => return render_frames_to_file(cli_args, &font, texts, *frame_time, font_load),
+    };
+    let mut texture = match &cli_args.into {
+        Some(into) => {
+            let atlas = image::open(&into.atlas).map_err(|e| format!("Could not open {}: {}", into.atlas.display(), e))?.to_rgba8();
+            render_into_atlas(&font, &text, &cli_args.options, &atlas, &into.region, into.clip)?
+        }
+        None => render_texture(&font, &text, &cli_args.options)?,
+    };
+    texture.timings.font_load = font_load;
+
+    let mockup = cli_args.mockup.map(|gui_scale| title_screen_mockup(&font, &texture.text_layer(&font), gui_scale)).transpose()?;
+    save_output(cli_args, |out| {
+        save_image(&texture.image, out, &font, &mut texture.timings)?;
+        match &mockup {
+            Some(mockup) => write_atomically(&mockup_path(out), &encode_png(mockup, &font)?),
+            None => Ok(()),
+        }
+    })?;
+
+    if cli_args.verbose {
+        eprint!("{}", texture.timings);
+    }
+
+    if let Some(path) = &cli_args.debug_overlay {
+        texture.debug_overlay(&font, &DebugOverlayColors::default()).save(path)?;
+    }
+
+    Ok(texture.warnings)
+}
+
+// Print the size each text would render at, one line each, checking it the way a render would
+fn measure(cli_args: &CliArgs, font: &Font) -> Result<Vec<String>, Box<dyn Error>> {
+    let texts = match &cli_args.input {
+        Input::Text(text) => vec![text.clone()],
+        Input::Clipboard => vec![read_clipboard_text()?],
+        Input::Frames { texts, .. } => texts.clone(),
+    };
+
+    let mut warnings = Vec::new();
+    for text in &texts {
+        let text = if cli_args.options.sanitize { sanitize_text(text).0 } else { text.clone() };
+        let metrics = measure_text(font, &text, &cli_args.options.render_options())?;
+        let output_scale = cli_args.options.output_scale.max(1);
+        println!("{}x{} {}", metrics.width * output_scale, metrics.height * output_scale, text);
+
+        let replaced: Vec<String> = metrics.replaced.iter().map(|replaced| replaced.to_string()).collect();
+        if !replaced.is_empty() {
+            warnings.push(format!("Outside the allowed characters: {}", replaced.join(", ")));
+        }
+    }
+
+    Ok(warnings)
+}
+
+fn render_frames_to_file(cli_args: &CliArgs, font: &Font, texts: &[String], frame_time: u32, font_load: Duration) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut strip = render_frames(font, texts, &cli_args.options)?;
+    strip.timings.font_load = font_load;
+    let mcmeta = strip.animation_mcmeta(frame_time)?;
+
+    save_output(cli_args, |out| {
+        save_image(&strip.image, out, font, &mut strip.timings)?;
+        let mut mcmeta_path = out.as_os_str().to_os_string();
+        mcmeta_path.push(".mcmeta");
+        write_atomically(Path::new(&mcmeta_path), mcmeta.as_bytes())
+    })?;
+
+    if cli_args.verbose {
+        eprint!("{}", strip.timings);
+    }
+
+    Ok(strip.warnings)
+}
+
+// Write the texture and the files saved next to it with `save`, at --out or, with --numbered, at the
+// numbered name save_numbered picks, which is printed for scripts to pick up
+fn save_output(cli_args: &CliArgs, save: impl FnOnce(&Path) -> Result<(), Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+    if !cli_args.numbered {
+        return save(&cli_args.out);
+    }
+    let out = save_numbered(&cli_args.out, save)?;
+    println!("{}", out.display());
+    Ok(())
+}
+
+// PNGs credit the font in a text chunk; other formats are written as they come
+fn save_image(image: &RgbaImage, out: &Path, font: &Font, timings: &mut RenderTimings) -> Result<(), Box<dyn Error>> {
+    let format = ImageFormat::from_path(out)?;
+    let encoded = RenderTimings::time(&mut timings.encode, || match format {
+        ImageFormat::Png => encode_png(image, font),
+        _ => {
+            let mut encoded = Cursor::new(Vec::new());
+            image.write_to(&mut encoded, format)?;
+            Ok(encoded.into_inner())
+        }
+    })?;
+    RenderTimings::time(&mut timings.write, || write_atomically(out, &encoded))?;
+    Ok(())
+}
+
+// The windows subsystem starts without a console, so borrow the one of the shell that launched us
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Failing just means there is no parent console; output is then dropped as before
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
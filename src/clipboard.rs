@@ -0,0 +1,47 @@
+// Reading the text to render off the clipboard, for --from-clipboard and the window's 'Paste &
+// render'. Only text is read; nothing here writes to the clipboard
+use std::error::Error;
+#[cfg(windows)]
+use native_windows_gui as nwg;
+
+// Clipboard text as the renderer takes it: Windows line endings become the newlines that split it
+// into lines, and the line break a copied spreadsheet cell ends in is dropped
+#[cfg(windows)]
+fn clipboard_input(text: &str) -> Result<String, Box<dyn Error>> {
+    let text = text.replace("\r\n", "\n");
+    let text = text.trim_end_matches('\n');
+    if text.trim().is_empty() {
+        return Err("The clipboard text is blank".into());
+    }
+    Ok(text.to_string())
+}
+
+// For the command line, which has no window to open the clipboard with, so a hidden message
+// window stands in
+#[cfg(windows)]
+pub fn read_clipboard_text() -> Result<String, Box<dyn Error>> {
+    nwg::init()?;
+    let mut message_window = nwg::MessageWindow::default();
+    nwg::MessageWindow::builder().build(&mut message_window)?;
+    read_clipboard_text_for(message_window.handle)
+}
+
+// The clipboard is opened on behalf of `owner`, the window asking for it
+#[cfg(windows)]
+pub fn read_clipboard_text_for(owner: nwg::ControlHandle) -> Result<String, Box<dyn Error>> {
+    use nwg::{Clipboard, ClipboardFormat};
+
+    if !Clipboard::has_format(ClipboardFormat::UnicodeText) && !Clipboard::has_format(ClipboardFormat::Text) {
+        return Err(match Clipboard::count_clipboard_formats() {
+            0 => "The clipboard is empty".into(),
+            _ => "The clipboard holds something other than text, such as an image; copy the text to render".into(),
+        });
+    }
+    let text = Clipboard::data_text(owner).ok_or("The clipboard text could not be read")?;
+    clipboard_input(&text)
+}
+
+#[cfg(not(windows))]
+pub fn read_clipboard_text() -> Result<String, Box<dyn Error>> {
+    Err("Reading the clipboard is only supported on Windows".into())
+}
@@ -0,0 +1,781 @@
+use std::cell::RefCell;
+use std::error::{Error};
+use std::{fs};
+use std::path::{Path, PathBuf};
+use image::imageops;
+use native_windows_derive::{NwgUi};
+use native_windows_gui::{NativeUi};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, font_summary, DebugOverlayColors, render_layout_layers, measure_text, compose, diagnostic_image, generate_background, extract_text_layer, trim_font, coverage_report, glyph_sheet, title_screen_mockup, sanitize_text, layout_with, glyph_source_summary, BackgroundOptions, LayoutProfile, GuideTemplate, DisallowedPolicy, BackgroundSource};
+use crate::clipboard::read_clipboard_text_for;
+use crate::self_test::run_self_test;
+use crate::texture::{FontFiles, load_embedded_font, background_source, texture_background, render_texture, render_into_atlas, write_atomically, encode_png, parse_hex_color, AtlasRegion, Alignment, LineFit, TextureOptions, BACKGROUND_IMAGE, DEFAULT_MAX_LINE_WIDTH};
+
+extern crate native_windows_gui as nwg;
+
+// Structure to define the UI elements for the input dialog
+#[derive(Default, NwgUi)]
+pub struct InputDialog {
+    #[nwg_resource(source_bin: Some(ICON_DATA))]
+    window_icon: nwg::Icon,
+
+    // Main window configuration
+    #[nwg_control(size: (300, 705), center: true, title: "Minecraft Titles [Texture Generator]", flags: "WINDOW|VISIBLE")]
+    #[nwg_events(OnWindowClose: [InputDialog::exit])]
+    window: nwg::Window,
+
+    // Menu bar; the window is 20 px taller to make room for it
+    #[nwg_control(parent: window, text: "Help")]
+    help_menu: nwg::Menu,
+
+    #[nwg_control(parent: help_menu, text: "Run self test")]
+    #[nwg_events(OnMenuItemSelected: [InputDialog::self_test])]
+    self_test_item: nwg::MenuItem,
+
+    // Label for the input field
+    #[nwg_control(size: (280, 25), position: (10, 10), text: "Please enter the text to render:")]
+    label: nwg::Label,
+
+    // Text input field for entering text to render
+    #[nwg_control(size: (280, 25), position: (10, 40))]
+    #[nwg_events(OnTextInput: [InputDialog::update_width])]
+    input: nwg::TextInput,
+
+    // Checkbox to enable or disable kerning
+    #[nwg_control(size: (100, 25), position: (10, 70), text: "Use kerning")]
+    use_kerning_checkbox: nwg::CheckBox,
+
+    // Carry a fraction of a kerning pair over a single space between its characters
+    #[nwg_control(size: (130, 25), position: (115, 70), text: "Kern across spaces")]
+    kern_across_spaces_checkbox: nwg::CheckBox,
+
+    #[nwg_control(size: (40, 25), position: (250, 70), text: "0.5")]
+    kern_fraction_input: nwg::TextInput,
+
+    // Background the text is placed on
+    #[nwg_control(size: (80, 25), position: (10, 100), text: "Background:")]
+    background_label: nwg::Label,
+
+    #[nwg_control(size: (195, 25), position: (95, 100), collection: vec!["UV checker", "Checker", "Solid color", "Gradient"], selected_index: Some(0))]
+    background_combo: nwg::ComboBox<&'static str>,
+
+    // Flip the highlight and guide bands horizontally for models that map the texture mirrored
+    #[nwg_control(size: (135, 25), position: (10, 130), text: "Mirror UV guides")]
+    guides_mirrored_checkbox: nwg::CheckBox,
+
+    // Strip control, zero-width and bidi characters that sneak in with pasted text
+    #[nwg_control(size: (140, 25), position: (150, 130), text: "Strip hidden characters", check_state: nwg::CheckBoxState::Checked)]
+    sanitize_checkbox: nwg::CheckBox,
+
+    // Also save a per-glyph debug overlay next to the texture, as its own layer
+    #[nwg_control(size: (135, 25), position: (10, 160), text: "Save debug overlay")]
+    debug_overlay_checkbox: nwg::CheckBox,
+
+    // Pixels to grow every glyph's atlas rect by, for fonts whose ink bleeds into the glyph borders
+    #[nwg_control(size: (100, 25), position: (150, 160), text: "Expand glyph crop:")]
+    expand_crop_label: nwg::Label,
+
+    #[nwg_control(size: (40, 25), position: (250, 160), value_int: 0, min_int: 0, max_int: 4)]
+    expand_crop_select: nwg::NumberSelect,
+
+    // Button to trigger text rendering
+    #[nwg_control(size: (135, 25), position: (10, 190), text: "Ok")]
+    #[nwg_events(OnButtonClick: [InputDialog::exit])]
+    button: nwg::Button,
+
+    // Render the clipboard's text straight away, lines and all, instead of the typed text
+    #[nwg_control(size: (135, 25), position: (155, 190), text: "Paste && render")]
+    #[nwg_events(OnButtonClick: [InputDialog::paste_and_render])]
+    paste_button: nwg::Button,
+
+    #[nwg_control(size: (100, 25), position: (10, 220), text: "About")]
+    #[nwg_events(OnButtonClick: [InputDialog::about])]
+    about_button: nwg::Button,
+
+    // Recover the text layer of a texture generated earlier with the selected background
+    #[nwg_control(size: (175, 25), position: (115, 220), text: "Recover text layer…")]
+    #[nwg_events(OnButtonClick: [InputDialog::recover_text_layer])]
+    recover_button: nwg::Button,
+
+    #[nwg_resource(title: "Open generated texture", action: nwg::FileDialogAction::Open, filters: "PNG(*.png)")]
+    recover_dialog: nwg::FileDialog,
+
+    // Export a font containing only the glyphs the entered text needs
+    #[nwg_control(size: (135, 25), position: (10, 250), text: "Export trimmed font…")]
+    #[nwg_events(OnButtonClick: [InputDialog::export_trimmed_font])]
+    trim_font_button: nwg::Button,
+
+    #[nwg_resource(title: "Save trimmed font", action: nwg::FileDialogAction::Save, filters: "BMFont(*.fnt)")]
+    trim_font_dialog: nwg::FileDialog,
+
+    // Show the font's metadata, which characters it covers and which of the entered ones it lacks
+    #[nwg_control(size: (140, 25), position: (150, 250), text: "Font info…")]
+    #[nwg_events(OnButtonClick: [InputDialog::font_coverage])]
+    coverage_button: nwg::Button,
+
+    // Export every glyph of the font rendered on its own, labeled with its code point
+    #[nwg_control(size: (135, 25), position: (10, 280), text: "Export glyph sheet…")]
+    #[nwg_events(OnButtonClick: [InputDialog::export_glyph_sheet])]
+    glyph_sheet_button: nwg::Button,
+
+    #[nwg_resource(title: "Save glyph sheet", action: nwg::FileDialogAction::Save, filters: "PNG(*.png)")]
+    glyph_sheet_dialog: nwg::FileDialog,
+
+    // Save every stage of the current render in one labeled image for bug reports
+    #[nwg_control(size: (140, 25), position: (150, 280), text: "Save diagnostic image…")]
+    #[nwg_events(OnButtonClick: [InputDialog::save_diagnostic_image])]
+    diagnostic_button: nwg::Button,
+
+    #[nwg_resource(title: "Save diagnostic image", action: nwg::FileDialogAction::Save, filters: "PNG(*.png)")]
+    diagnostic_dialog: nwg::FileDialog,
+
+    // Render with a .fnt and atlas from disk instead of the embedded font
+    #[nwg_control(size: (135, 25), position: (10, 310), text: "Choose font…")]
+    #[nwg_events(OnButtonClick: [InputDialog::choose_font])]
+    choose_font_button: nwg::Button,
+
+    #[nwg_control(size: (140, 25), position: (150, 310), text: "Font: embedded")]
+    font_label: nwg::Label,
+
+    #[nwg_resource(title: "Open font", action: nwg::FileDialogAction::Open, filters: "BMFont(*.fnt)")]
+    font_dialog: nwg::FileDialog,
+
+    #[nwg_resource(title: "Open font atlas", action: nwg::FileDialogAction::Open, filters: "PNG(*.png)")]
+    atlas_dialog: nwg::FileDialog,
+
+    // Put titles too wide for a GUI slot on two lines, or wrap them, instead of only warning about them
+    #[nwg_control(size: (110, 25), position: (10, 340), text: "Wider than 256 px:")]
+    line_fit_label: nwg::Label,
+
+    #[nwg_control(size: (165, 25), position: (125, 340), collection: vec!["Warn", "Split onto two lines", "Wrap onto lines"], selected_index: Some(0))]
+    line_fit_combo: nwg::ComboBox<&'static str>,
+
+    // Pixels taken off every glyph's advance, for fonts exported with different spacing than the embedded one
+    #[nwg_control(size: (100, 25), position: (10, 370), text: "Advance adjust:")]
+    advance_adjust_label: nwg::Label,
+
+    #[nwg_control(size: (40, 25), position: (115, 370), value_int: 3, min_int: -8, max_int: 8)]
+    advance_adjust_select: nwg::NumberSelect,
+
+    // Width of the entered text as it will render, updated while typing
+    #[nwg_control(size: (125, 25), position: (165, 370), text: "Width: 0 px")]
+    width_label: nwg::Label,
+
+    // Paste the texture into an existing texture atlas, with its top left corner at these coordinates
+    #[nwg_control(size: (70, 25), position: (10, 400), text: "Atlas x, y:")]
+    into_label: nwg::Label,
+
+    #[nwg_control(size: (40, 25), position: (85, 400), text: "0")]
+    into_x_input: nwg::TextInput,
+
+    #[nwg_control(size: (40, 25), position: (130, 400), text: "0")]
+    into_y_input: nwg::TextInput,
+
+    #[nwg_control(size: (115, 25), position: (175, 400), text: "Paste into atlas…")]
+    #[nwg_events(OnButtonClick: [InputDialog::paste_into_atlas])]
+    into_button: nwg::Button,
+
+    #[nwg_resource(title: "Open texture atlas", action: nwg::FileDialogAction::Open, filters: "PNG(*.png)")]
+    into_atlas_dialog: nwg::FileDialog,
+
+    // Choosing the atlas itself overwrites it
+    #[nwg_resource(title: "Save atlas with the title", action: nwg::FileDialogAction::Save, filters: "PNG(*.png)")]
+    into_save_dialog: nwg::FileDialog,
+
+    // How split lines narrower than the widest one are placed
+    #[nwg_control(size: (100, 25), position: (10, 430), text: "Line alignment:")]
+    alignment_label: nwg::Label,
+
+    #[nwg_control(size: (175, 25), position: (115, 430), collection: vec!["Left", "Center", "Right"], selected_index: Some(1))]
+    alignment_combo: nwg::ComboBox<&'static str>,
+
+    // Pixels added between characters, for text looser or tighter than the font's advances. The kerning
+    // row is full, so it gets a row of its own
+    #[nwg_control(size: (100, 25), position: (10, 460), text: "Letter spacing:")]
+    letter_spacing_label: nwg::Label,
+
+    #[nwg_control(size: (40, 25), position: (115, 460), value_int: 0, min_int: -8, max_int: 16)]
+    letter_spacing_select: nwg::NumberSelect,
+
+    // Hex color multiplied into the white font, instead of recoloring the texture afterwards
+    #[nwg_control(size: (60, 25), position: (165, 460), text: "Text color:")]
+    tint_label: nwg::Label,
+
+    #[nwg_control(size: (65, 25), position: (225, 460), text: "#FFFFFF")]
+    tint_input: nwg::TextInput,
+
+    // UV helper drawn behind the text, for models that unwrap the title differently
+    #[nwg_control(size: (100, 25), position: (10, 490), text: "UV guides:")]
+    guides_label: nwg::Label,
+
+    #[nwg_control(size: (175, 25), position: (115, 490), collection: vec!["Bands", "Glyph boxes", "Border frame", "None"], selected_index: Some(0))]
+    guides_combo: nwg::ComboBox<&'static str>,
+
+    // The game's drop shadow: the text again a pixel down and right, a quarter as bright, beneath it
+    #[nwg_control(size: (135, 25), position: (10, 520), text: "Drop shadow")]
+    shadow_checkbox: nwg::CheckBox,
+
+    // The red debug line along the baseline, for lining up a font; left out of textures that ship
+    #[nwg_control(size: (140, 25), position: (150, 520), text: "Show baseline guide")]
+    baseline_guide_checkbox: nwg::CheckBox,
+
+    // Two hex colors the text blends between from left to right, overriding the text color; left
+    // empty for none
+    #[nwg_control(size: (100, 25), position: (10, 550), text: "Gradient:")]
+    gradient_label: nwg::Label,
+
+    #[nwg_control(size: (85, 25), position: (115, 550), text: "")]
+    gradient_from_input: nwg::TextInput,
+
+    #[nwg_control(size: (85, 25), position: (205, 550), text: "")]
+    gradient_to_input: nwg::TextInput,
+
+    // Scale of the whole texture, text and guides together, for fonts and title sizes the default
+    // doesn't suit
+    #[nwg_control(size: (100, 25), position: (10, 580), text: "Scale:")]
+    scale_label: nwg::Label,
+
+    #[nwg_control(size: (60, 25), position: (115, 580), text: "1.5")]
+    scale_input: nwg::TextInput,
+
+    // Save how the text looks in game at a GUI scale, on a stand-in title screen. Only a preview;
+    // the texture is saved by Ok as always
+    #[nwg_control(size: (120, 25), position: (10, 610), text: "Preview at GUI scale:")]
+    mockup_label: nwg::Label,
+
+    #[nwg_control(size: (45, 25), position: (135, 610), collection: vec![2, 3, 4], selected_index: Some(0))]
+    mockup_scale_combo: nwg::ComboBox<u32>,
+
+    #[nwg_control(size: (105, 25), position: (185, 610), text: "Save preview…")]
+    #[nwg_events(OnButtonClick: [InputDialog::save_mockup])]
+    mockup_button: nwg::Button,
+
+    #[nwg_resource(title: "Save title screen preview", action: nwg::FileDialogAction::Save, filters: "PNG(*.png)")]
+    mockup_dialog: nwg::FileDialog,
+
+    // Save the texture at 2x to 4x the pixel density, every pixel an exact block, for high
+    // resolution packs
+    #[nwg_control(size: (120, 25), position: (10, 640), text: "Texture pixel scale:")]
+    output_scale_label: nwg::Label,
+
+    #[nwg_control(size: (45, 25), position: (135, 640), collection: vec![1, 2, 3, 4], selected_index: Some(0))]
+    output_scale_combo: nwg::ComboBox<u32>,
+
+    // The chosen font, None while the embedded one is used
+    font_files: RefCell<Option<FontFiles>>,
+
+    // Text read by 'Paste & render', rendered in place of the input, which can't hold its newlines
+    pasted_text: RefCell<Option<String>>,
+
+    // Layout configuration for the window
+    #[nwg_layout(parent: window, spacing: 1)]
+    grid_layout: nwg::GridLayout,
+}
+
+impl InputDialog {
+    fn texture_options(&self) -> Result<TextureOptions, Box<dyn Error>> {
+        let checked = |checkbox: &nwg::CheckBox| checkbox.check_state() == nwg::CheckBoxState::Checked;
+
+        let kern_across_spaces = if checked(&self.kern_across_spaces_checkbox) {
+            match self.kern_fraction_input.text().trim().parse::<f32>() {
+                Ok(fraction) if (0.0..=1.0).contains(&fraction) => Some(fraction),
+                _ => return Err("The kerning fraction must be a number between 0 and 1.".into()),
+            }
+        } else {
+            None
+        };
+
+        let scale_factor = match self.scale_input.text().trim().parse::<f32>() {
+            Ok(scale) if scale.is_finite() && scale > 0.0 => scale,
+            _ => return Err("The scale must be a positive number such as 1.5.".into()),
+        };
+
+        let tint = parse_hex_color(&self.tint_input.text())
+            .ok_or("The text color must be a hex color such as #FFAA00.")?;
+
+        let gradient = match (self.gradient_from_input.text().trim(), self.gradient_to_input.text().trim()) {
+            ("", "") => None,
+            (from, to) => match (parse_hex_color(from), parse_hex_color(to)) {
+                (Some(from), Some(to)) => Some((from, to)),
+                _ => return Err("The gradient needs two hex colors such as #FFAA00, or neither.".into()),
+            },
+        };
+
+        Ok(TextureOptions {
+            sanitize: checked(&self.sanitize_checkbox),
+            use_kerning: checked(&self.use_kerning_checkbox),
+            kern_across_spaces,
+            scale_factor,
+            legacy_guide_scale: false,
+            output_scale: self.output_scale_combo.selection().and_then(|index| self.output_scale_combo.collection().get(index).copied()).unwrap_or(1),
+            background_choice: self.background_combo.selection().unwrap_or(0),
+            guides_mirrored: checked(&self.guides_mirrored_checkbox),
+            guides: match self.guides_combo.selection() {
+                Some(1) => GuideTemplate::per_glyph_boxes(),
+                Some(2) => GuideTemplate::border_frame(),
+                Some(3) => GuideTemplate::None,
+                _ => GuideTemplate::current_bands(),
+            },
+            draw_baseline_guide: checked(&self.baseline_guide_checkbox),
+            letter_spacing: Some(match self.letter_spacing_select.data() {
+                nwg::NumberSelectData::Int { value, .. } => value.clamp(i32::MIN.into(), i32::MAX.into()) as i32,
+                nwg::NumberSelectData::Float { value, .. } => value as i32,
+            }),
+            alignment: match self.alignment_combo.selection() {
+                Some(0) => Alignment::Left,
+                Some(2) => Alignment::Right,
+                _ => Alignment::Center,
+            },
+            layout_profile: LayoutProfile::Legacy,
+            bold: false,
+            shadow: checked(&self.shadow_checkbox),
+            tint,
+            outline: None,
+            gradient,
+            allowed_chars: None,
+            disallowed_policy: DisallowedPolicy::Fail,
+            max_line_width: Some(DEFAULT_MAX_LINE_WIDTH),
+            line_fit: match self.line_fit_combo.selection() {
+                Some(1) => LineFit::Split,
+                Some(2) => LineFit::Wrap,
+                _ => LineFit::Warn,
+            },
+            strict_clipping: false,
+        })
+    }
+
+    fn font_load_options(&self) -> FontLoadOptions {
+        let expand_glyph_crop = match self.expand_crop_select.data() {
+            nwg::NumberSelectData::Int { value, .. } => value.clamp(0, u32::MAX.into()) as u32,
+            nwg::NumberSelectData::Float { value, .. } => value.max(0.0) as u32,
+        };
+        let advance_adjust = match self.advance_adjust_select.data() {
+            nwg::NumberSelectData::Int { value, .. } => value.clamp(i32::MIN.into(), i32::MAX.into()) as i32,
+            nwg::NumberSelectData::Float { value, .. } => value as i32,
+        };
+        let glyph_trim = GlyphTrim { advance_adjust, ..GlyphTrim::default() };
+
+        FontLoadOptions { expand_glyph_crop, glyph_trim, ..FontLoadOptions::default() }
+    }
+
+    // Chosen fonts may come from an interrupted download; render what survived and warn about it.
+    // The embedded font is loaded strictly, so a damaged copy of it fails like anywhere else
+    fn chosen_font_load_options(&self) -> FontLoadOptions {
+        FontLoadOptions { lenient: true, ..self.font_load_options() }
+    }
+
+    // The chosen font if there is one, otherwise the embedded font
+    fn load_font(&self) -> Result<Font, Box<dyn Error>> {
+        match &*self.font_files.borrow() {
+            Some(files) => files.load(&self.chosen_font_load_options()),
+            None => load_embedded_font(&self.font_load_options()),
+        }
+    }
+
+    fn update_width(&self) {
+        let measured = self.texture_options().and_then(|options| {
+            let text = match options.sanitize {
+                true => sanitize_text(&self.input.text()).0,
+                false => self.input.text(),
+            };
+            Ok(measure_text(&self.load_font()?, &text, &options.render_options())?)
+        });
+
+        let text = match measured {
+            Ok(metrics) if metrics.width > DEFAULT_MAX_LINE_WIDTH => format!("Width: {} px (too wide)", metrics.width),
+            Ok(metrics) => format!("Width: {} px", metrics.width),
+            Err(_) => "Width: -".to_string(),
+        };
+        self.width_label.set_text(&text);
+    }
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+    fn paste_and_render(&self) {
+        match read_clipboard_text_for(self.window.handle) {
+            Ok(text) => {
+                self.input.set_text(&text.replace('\n', " "));
+                *self.pasted_text.borrow_mut() = Some(text);
+                nwg::stop_thread_dispatch();
+            }
+            Err(e) => {
+                nwg::simple_message("Error", &format!("Could not paste the text: {}", e));
+            }
+        }
+    }
+
+    fn about(&self) {
+        let font = match self.load_font() {
+            Ok(font) => format!("\n\nFont: {}", font.attribution()),
+            Err(_) => String::new(),
+        };
+        nwg::simple_message("ⓘAbout", &format!("Copyright 2023 Archie★\nVisit my GitHub: https://github.com/ghosthesia\nsource_code:\nhttps://github.com/ArchieC0des/minecraft_titles_texture_generator_bitmap{}", font));
+    }
+
+    // The same check as --self-test; nothing is saved
+    fn self_test(&self) {
+        let report = run_self_test();
+        let title = if report.passed() { "Self test passed" } else { "Self test failed" };
+        nwg::simple_message(title, &report.to_string());
+    }
+
+    fn recover_text_layer(&self) {
+        if !self.recover_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let background_choice = self.background_combo.selection().unwrap_or(0);
+        let result = self.recover_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| recover_text_layer_file(Path::new(&path), background_choice));
+
+        match result {
+            Ok(output_path) => nwg::simple_message("Text layer recovered", &format!("Saved to {}", output_path.display())),
+            Err(e) => nwg::simple_message("Error", &format!("Could not recover the text layer: {}", e)),
+        };
+    }
+
+    fn export_trimmed_font(&self) {
+        let text = self.input.text();
+        if text.is_empty() {
+            nwg::simple_message("Export trimmed font", "Enter the text whose characters the font should keep first.");
+            return;
+        }
+        if !self.trim_font_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let result = self.trim_font_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| export_trimmed_font_file(Path::new(&path), &text, &self.load_font()?));
+
+        match result {
+            Ok(fnt_path) => nwg::simple_message("Trimmed font exported", &format!("Saved to {}", fnt_path.display())),
+            Err(e) => nwg::simple_message("Error", &format!("Could not export the trimmed font: {}", e)),
+        };
+    }
+
+    fn font_coverage(&self) {
+        let sample = self.input.text();
+        match self.load_font() {
+            Ok(font) => {
+                let report = coverage_report(&font, Some(&sample).filter(|sample| !sample.is_empty()).map(|sample| sample.as_str()));
+                nwg::simple_message("Font info", &format!("{}\n{}", font_summary(&font), report))
+            }
+            Err(e) => nwg::simple_message("Error", &format!("Could not load the font: {}", e)),
+        };
+    }
+
+    fn export_glyph_sheet(&self) {
+        if !self.glyph_sheet_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let result = self.glyph_sheet_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| export_glyph_sheet_file(Path::new(&path), &self.load_font()?));
+
+        match result {
+            Ok(output_path) => nwg::simple_message("Glyph sheet exported", &format!("Saved to {}", output_path.display())),
+            Err(e) => nwg::simple_message("Error", &format!("Could not export the glyph sheet: {}", e)),
+        };
+    }
+
+    fn save_diagnostic_image(&self) {
+        let options = match self.texture_options() {
+            Ok(options) => options,
+            Err(e) => {
+                nwg::simple_message("Error", &e.to_string());
+                return;
+            }
+        };
+        if !self.diagnostic_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let text = self.input.text();
+        let result = self.diagnostic_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| save_diagnostic_file(Path::new(&path), &text, &options, &self.load_font()?));
+
+        match result {
+            Ok(output_path) => nwg::simple_message("Diagnostic image saved", &format!("Saved to {}", output_path.display())),
+            Err(e) => nwg::simple_message("Error", &format!("Could not save the diagnostic image: {}", e)),
+        };
+    }
+
+    fn save_mockup(&self) {
+        let options = match self.texture_options() {
+            Ok(options) => options,
+            Err(e) => {
+                nwg::simple_message("Error", &e.to_string());
+                return;
+            }
+        };
+        if !self.mockup_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let gui_scale = self.mockup_scale_combo.selection().and_then(|index| self.mockup_scale_combo.collection().get(index).copied()).unwrap_or(2);
+        let text = self.input.text();
+        let result = self.mockup_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| save_mockup_file(Path::new(&path), &text, &options, gui_scale, &self.load_font()?));
+
+        match result {
+            Ok(output_path) => nwg::simple_message("Preview saved", &format!("Saved to {}", output_path.display())),
+            Err(e) => nwg::simple_message("Error", &format!("Could not save the preview: {}", e)),
+        };
+    }
+
+    fn paste_into_atlas(&self) {
+        let options = match self.texture_options() {
+            Ok(options) => options,
+            Err(e) => {
+                nwg::simple_message("Error", &e.to_string());
+                return;
+            }
+        };
+        let position = match (self.into_x_input.text().trim().parse::<u32>(), self.into_y_input.text().trim().parse::<u32>()) {
+            (Ok(x), Ok(y)) => (x, y),
+            _ => {
+                nwg::simple_message("Error", "The atlas position must be two whole numbers of pixels.");
+                return;
+            }
+        };
+        if !self.into_atlas_dialog.run(Some(&self.window)) {
+            return;
+        }
+        let atlas_path = match self.into_atlas_dialog.get_selected_item() {
+            Ok(path) => PathBuf::from(path),
+            Err(e) => {
+                nwg::simple_message("Error", &format!("Could not open the atlas: {}", e));
+                return;
+            }
+        };
+        if !self.into_save_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let text = self.input.text();
+        let result = self.into_save_dialog.get_selected_item()
+            .map_err(|e| e.into())
+            .and_then(|path| paste_into_atlas_file(&atlas_path, Path::new(&path), position, &text, &options, &self.load_font()?));
+
+        match result {
+            Ok((output_path, warnings)) => nwg::simple_message(
+                "Pasted into atlas",
+                &format!("Saved to {}{}", output_path.display(), warnings.iter().map(|warning| format!("\n{}", warning)).collect::<String>()),
+            ),
+            Err(e) => nwg::simple_message("Error", &format!("Could not paste into the atlas: {}", e)),
+        };
+    }
+
+    fn choose_font(&self) {
+        if !self.font_dialog.run(Some(&self.window)) {
+            return;
+        }
+
+        let files = match self.font_dialog.get_selected_item().map_err(|e| e.into()).and_then(|path| FontFiles::beside(Path::new(&path))) {
+            Ok(files) => files,
+            Err(e) => {
+                nwg::simple_message("Error", &format!("Could not read the font: {}", e));
+                return;
+            }
+        };
+        let Some(files) = self.locate_missing_pages(files) else {
+            return;
+        };
+
+        // Load it once now so a broken font is reported here rather than when rendering
+        match files.load(&self.chosen_font_load_options()) {
+            Ok(font) => {
+                self.font_label.set_text(&format!("Font: {}", font.info().face));
+                *self.font_files.borrow_mut() = Some(files);
+            }
+            Err(e) => {
+                nwg::simple_message("Error", &format!("Could not load the font: {}", e));
+            }
+        }
+    }
+
+    // Ask for every atlas page that isn't where the .fnt says it is; None if a picker was cancelled
+    fn locate_missing_pages(&self, mut files: FontFiles) -> Option<FontFiles> {
+        if files.pages.is_empty() {
+            files.pages.push(files.fnt.with_extension("png"));
+        }
+
+        for page in files.pages.iter_mut().filter(|page| !page.is_file()) {
+            let file_name = page.file_name().map_or(String::new(), |name| name.to_string_lossy().into_owned());
+            nwg::simple_message("Choose font atlas", &format!("Could not find the atlas {} next to the font; please select it.", file_name));
+            if !self.atlas_dialog.run(Some(&self.window)) {
+                return None;
+            }
+            *page = PathBuf::from(self.atlas_dialog.get_selected_item().ok()?);
+        }
+
+        Some(files)
+    }
+}
+
+//load icon
+const ICON_DATA: &[u8] = include_bytes!("assets/icon.ico");
+
+const GLYPH_SHEET_COLUMNS: u32 = 16;
+
+// How far a pixel may drift from the regenerated background and still count as background
+const RECOVER_TOLERANCE: u8 = 8;
+
+// Subtract a freshly generated background from an earlier texture and save the remaining layer next to it
+fn recover_text_layer_file(path: &Path, background_choice: usize) -> Result<PathBuf, Box<dyn Error>> {
+    let composite = image::open(path)?.to_rgba8();
+    let bg_image = image::load_from_memory(BACKGROUND_IMAGE)?;
+    let source = background_source(background_choice).unwrap_or(BackgroundSource::Image(&bg_image));
+
+    let background = generate_background(&source, composite.width(), composite.height(), &BackgroundOptions::default())?;
+    let text_layer = extract_text_layer(&composite, &background, RECOVER_TOLERANCE);
+
+    let file_stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("title_texture_map");
+    let output_path = path.with_file_name(format!("{}_text_layer.png", file_stem));
+    text_layer.save(&output_path)?;
+
+    Ok(output_path)
+}
+
+// Write a .fnt and its atlas PNG (same file stem) holding only the glyphs `text` uses, and the
+// sidecar with the font's attribution when it says more than the face name the .fnt keeps
+fn export_trimmed_font_file(path: &Path, text: &str, font: &Font) -> Result<PathBuf, Box<dyn Error>> {
+    let fnt_path = path.with_extension("fnt");
+    let atlas_path = path.with_extension("png");
+    let page_file = atlas_path.file_name().and_then(|name| name.to_str()).ok_or("Invalid file name")?;
+
+    let (fnt, atlas) = trim_font(font, text, page_file);
+    fs::write(&fnt_path, fnt)?;
+    atlas.save(&atlas_path)?;
+    let attribution = font.attribution();
+    if attribution.author.is_some() || attribution.license.is_some() || attribution.url.is_some() {
+        fs::write(path.with_extension("toml"), attribution.to_toml())?;
+    }
+
+    Ok(fnt_path)
+}
+
+fn export_glyph_sheet_file(path: &Path, font: &Font) -> Result<PathBuf, Box<dyn Error>> {
+    let output_path = path.with_extension("png");
+    fs::write(&output_path, encode_png(&glyph_sheet(font, GLYPH_SHEET_COLUMNS)?, font)?)?;
+
+    Ok(output_path)
+}
+
+// Render `text` the way the Ok button would and paste it into a copy of the atlas at `atlas_path`,
+// saved to `path`. Saving over the atlas itself goes through a temporary file
+fn paste_into_atlas_file(atlas_path: &Path, path: &Path, (x, y): (u32, u32), text: &str, options: &TextureOptions, font: &Font) -> Result<(PathBuf, Vec<String>), Box<dyn Error>> {
+    let atlas = image::open(atlas_path)?.to_rgba8();
+    let region = AtlasRegion { x, y, max_width: None, max_height: None };
+    let texture = render_into_atlas(font, text, options, &atlas, &region, false)?;
+
+    let output_path = path.with_extension("png");
+    write_atomically(&output_path, &encode_png(&texture.image, font)?)?;
+
+    Ok((output_path, texture.warnings))
+}
+
+// Render `text` the way the Ok button would and save it on a stand-in title screen at `gui_scale`
+fn save_mockup_file(path: &Path, text: &str, options: &TextureOptions, gui_scale: u32, font: &Font) -> Result<PathBuf, Box<dyn Error>> {
+    let texture = render_texture(font, text, options)?;
+    let mockup = title_screen_mockup(font, &texture.text_layer(font), gui_scale)?;
+
+    let output_path = path.with_extension("png");
+    write_atomically(&output_path, &encode_png(&mockup, font)?)?;
+
+    Ok(output_path)
+}
+
+// Render `text` the way the Ok button would and save each stage, labeled, in one image
+fn save_diagnostic_file(path: &Path, text: &str, options: &TextureOptions, font: &Font) -> Result<PathBuf, Box<dyn Error>> {
+    let bg_image = image::load_from_memory(BACKGROUND_IMAGE)?;
+    let text = if options.sanitize { sanitize_text(text).0 } else { text.to_string() };
+
+    let render_options = options.render_options();
+    render_options.limits.check_text(&text)?;
+    let text_layout = layout_with(font, &text, &render_options)?;
+    let layers = render_layout_layers(font, &text_layout, &render_options)?;
+    let sources = glyph_source_summary(&text_layout.glyphs);
+    let (width, height) = layers.dimensions();
+    let rendered_image = compose(width, height, &[&layers.guides, &layers.baseline, &layers.text]);
+    let background = texture_background(&bg_image, options.background_choice, &rendered_image)?;
+    let mut composite = background.clone();
+    imageops::overlay(&mut composite, &rendered_image, -1, 0);
+
+    let footer = format!(
+        "v{} kerning={} across_spaces={:?} background={} mirrored={} glyphs={}",
+        env!("CARGO_PKG_VERSION"), options.use_kerning, options.kern_across_spaces, options.background_choice, options.guides_mirrored,
+        sources.as_deref().unwrap_or("all from the font"),
+    );
+    let panels = [("Text layer", &layers.text), ("Highlight layer", &layers.guides), ("Background", &background), ("Composite", &composite)];
+
+    let output_path = path.with_extension("png");
+    diagnostic_image(font, &panels, &footer)?.save(&output_path)?;
+
+    Ok(output_path)
+}
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+
+    // Initialize the GUI framework and set default font
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    // Build the UI from the defined structure
+    let ui = InputDialog::build_ui(Default::default()).expect("Failed to build UI");
+
+    // Set the window icon
+    ui.window.set_icon(Some(&ui.window_icon));
+
+    // Start the event dispatch loop for the GUI
+    nwg::dispatch_thread_events();
+
+    // Get the entered text and kerning preference from the UI
+    let options = match ui.texture_options() {
+        Ok(options) => options,
+        Err(e) => {
+            nwg::simple_message("Error", &e.to_string());
+            return Ok(());
+        }
+    };
+    let save_debug_overlay = ui.debug_overlay_checkbox.check_state() == nwg::CheckBoxState::Checked;
+
+    let font = match ui.load_font() {
+        Ok(font) => font,
+        Err(e) => {
+            nwg::simple_message("Error", &format!("Could not load the font: {}", e));
+            return Ok(());
+        }
+    };
+    let text = ui.pasted_text.borrow_mut().take().unwrap_or_else(|| ui.input.text());
+    let texture = match render_texture(&font, &text, &options) {
+        Ok(texture) => texture,
+        Err(e) => {
+            nwg::simple_message("Error", &format!("Could not render the text: {}", e));
+            return Ok(());
+        }
+    };
+
+    if !texture.warnings.is_empty() {
+        nwg::simple_message("Warning", &texture.warnings.join("\n"));
+    }
+
+    // Create the directory if it doesn't exist
+    fs::create_dir_all("./title_texture_map")?;
+
+    // Now save the file in the newly created (or already existing) directory. Another copy of the tool
+    // may be saving the same file, so it is replaced whole rather than written in place
+    write_atomically(Path::new("./title_texture_map/title_texture_map.png"), &encode_png(&texture.image, &font)?)?;
+
+    if save_debug_overlay {
+        texture.debug_overlay(&font, &DebugOverlayColors::default()).save("./title_texture_map/title_texture_map_debug.png")?;
+    }
+
+    Ok(())
+}
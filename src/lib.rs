@@ -0,0 +1,7 @@
+// Bitmap font title rendering: font loading, layout, rasterizing and backgrounds.
+// Has no GUI dependencies, so it builds on every target
+mod utilities;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+pub use crate::utilities::*;
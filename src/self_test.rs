@@ -0,0 +1,151 @@
+// End-to-end check of an installation: load the embedded font, lay out and render a fixed text with
+// a fixed recipe, encode it and compare the pixels with the hash baked in below. Nothing comes from
+// the user or the machine, so a working build passes everywhere.
+//
+// Updating SELF_TEST_HASH: the hash changes whenever rendering changes what the recipe draws. After
+// a change meant to alter the output, run `rust_bitmap_renderer --self-test --self-test-out check.png`,
+// make sure check.png looks right, and paste the hash the report says it got in as the new value, in
+// the same commit as the change. tests/cli.rs runs the self test, so a stale hash fails the tests
+use std::fmt;
+use image::{Rgba, RgbaImage};
+use minecraft_titles::{GuideTemplate, LayoutProfile, DisallowedPolicy, font_summary, layout_with};
+use crate::texture::{load_embedded_font, render_texture, encode_png, Alignment, LineFit, TextureOptions};
+
+const SELF_TEST_TEXT: &str = "Minecraft Titles AV 0123!";
+const SELF_TEST_HASH: u64 = 0x42e6edd2367a3365;
+
+// The settings the self test renders with, spelled out so changing a default doesn't change them
+fn self_test_options() -> TextureOptions {
+    TextureOptions {
+        sanitize: true,
+        use_kerning: true,
+        kern_across_spaces: None,
+        scale_factor: 1.5,
+        legacy_guide_scale: false,
+        output_scale: 1,
+        background_choice: 0,
+        guides_mirrored: false,
+        guides: GuideTemplate::current_bands(),
+        draw_baseline_guide: true,
+        letter_spacing: None,
+        alignment: Alignment::Center,
+        layout_profile: LayoutProfile::Legacy,
+        bold: false,
+        shadow: false,
+        tint: Rgba([255, 255, 255, 255]),
+        outline: None,
+        gradient: None,
+        allowed_chars: None,
+        disallowed_policy: DisallowedPolicy::Fail,
+        max_line_width: None,
+        line_fit: LineFit::Warn,
+        strict_clipping: false,
+    }
+}
+
+// One stage of the self test: what it found when it worked, why not when it didn't, None when an
+// earlier stage failed and it never ran
+pub struct SelfTestStep {
+    pub name: &'static str,
+    pub result: Option<Result<String, String>>,
+}
+
+pub struct SelfTestReport {
+    pub steps: Vec<SelfTestStep>,
+    // The encoded texture, when the test got as far as encoding it
+    pub png: Option<Vec<u8>>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|step| matches!(step.result, Some(Ok(_))))
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for step in &self.steps {
+            match &step.result {
+                Some(Ok(detail)) => writeln!(f, "{}: OK, {}", step.name, detail)?,
+                Some(Err(reason)) => writeln!(f, "{}: FAIL, {}", step.name, reason)?,
+                None => writeln!(f, "{}: skipped", step.name)?,
+            }
+        }
+        write!(f, "{}", if self.passed() { "PASS" } else { "FAIL" })
+    }
+}
+
+pub fn run_self_test() -> SelfTestReport {
+    let mut report = SelfTestReport {
+        steps: ["Font parse", "Layout", "Render", "Encode", "Hash match"].iter()
+            .map(|name| SelfTestStep { name, result: None })
+            .collect(),
+        png: None,
+    };
+    let options = self_test_options();
+
+    // Each stage records its outcome; the first failure leaves the rest skipped
+    let mut record = |index: usize, result: Result<String, String>| {
+        let ok = result.is_ok();
+        report.steps[index].result = Some(result);
+        ok
+    };
+
+    let font = match load_embedded_font(&Default::default()) {
+        Ok(font) => font,
+        Err(e) => {
+            record(0, Err(e.to_string()));
+            return report;
+        }
+    };
+    record(0, Ok(format!("{} glyphs, {} kerning pairs", font.chars().count(), font_summary(&font).kerning_pair_count)));
+
+    let layout_result = layout_with(&font, SELF_TEST_TEXT, &options.render_options())
+        .map(|layout| format!("{} x {} px, {} glyphs", layout.width, layout.height, layout.glyphs.len()))
+        .map_err(|e| e.to_string());
+    if !record(1, layout_result) {
+        return report;
+    }
+
+    let image = match render_texture(&font, SELF_TEST_TEXT, &options) {
+        Ok(texture) => texture.image,
+        Err(e) => {
+            record(2, Err(e.to_string()));
+            return report;
+        }
+    };
+    record(2, Ok(format!("{} x {} px", image.width(), image.height())));
+
+    let encoded = encode_png(&image, &font).map_err(|e| e.to_string()).and_then(|png| {
+        let decoded = image::load_from_memory(&png).map_err(|e| format!("the PNG does not decode: {}", e))?.to_rgba8();
+        match decoded == image {
+            true => Ok(png),
+            false => Err("the decoded PNG differs from the rendered image".to_string()),
+        }
+    });
+    let png = match encoded {
+        Ok(png) => png,
+        Err(e) => {
+            record(3, Err(e));
+            return report;
+        }
+    };
+    record(3, Ok(format!("{} bytes", png.len())));
+
+    let hash = image_hash(&image);
+    match hash == SELF_TEST_HASH {
+        true => record(4, Ok(format!("{:#018x}", hash))),
+        false => record(4, Err(format!("expected {:#018x}, got {:#018x}", SELF_TEST_HASH, hash))),
+    };
+    report.png = Some(png);
+
+    report
+}
+
+// 64-bit FNV-1a over the size and the RGBA bytes, so the hash depends on the pixels alone and not on
+// how an encoder compresses them
+fn image_hash(image: &RgbaImage) -> u64 {
+    let size = [image.width().to_le_bytes(), image.height().to_le_bytes()].concat();
+    size.iter().chain(image.as_raw().iter())
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
@@ -0,0 +1,106 @@
+// A tiny 3x5 pixel font drawn in code, for tests that need a font they can redistribute. Unlike the
+// embedded MinecraftDebugger font it carries no license question, so downstream crates can use it
+// in their own golden tests. Enabled with the `test-support` feature
+use std::error::Error;
+use image::{DynamicImage, RgbaImage, Rgba};
+use crate::{Font, FontLoadOptions, RenderOptions, render_text_with};
+
+// Each glyph is five rows of three pixels, top to bottom, '#' for ink
+const FIXTURE_GLYPHS: &[(char, &str)] = &[
+    ('A', ".#./#.#/###/#.#/#.#"), ('B', "##./#.#/##./#.#/##."), ('C', ".##/#../#../#../.##"),
+    ('D', "##./#.#/#.#/#.#/##."), ('E', "###/#../##./#../###"), ('F', "###/#../##./#../#.."),
+    ('G', ".##/#../#.#/#.#/.##"), ('H', "#.#/#.#/###/#.#/#.#"), ('I', "###/.#./.#./.#./###"),
+    ('J', "..#/..#/..#/#.#/.#."), ('K', "#.#/#.#/##./#.#/#.#"), ('L', "#../#../#../#../###"),
+    ('M', "#.#/###/###/#.#/#.#"), ('N', "##./#.#/#.#/#.#/#.#"), ('O', ".#./#.#/#.#/#.#/.#."),
+    ('P', "##./#.#/##./#../#.."), ('Q', ".#./#.#/#.#/##./.##"), ('R', "##./#.#/##./#.#/#.#"),
+    ('S', ".##/#../.#./..#/##."), ('T', "###/.#./.#./.#./.#."), ('U', "#.#/#.#/#.#/#.#/###"),
+    ('V', "#.#/#.#/#.#/#.#/.#."), ('W', "#.#/#.#/###/###/#.#"), ('X', "#.#/#.#/.#./#.#/#.#"),
+    ('Y', "#.#/#.#/.#./.#./.#."), ('Z', "###/..#/.#./#../###"),
+    ('0', "###/#.#/#.#/#.#/###"), ('1', ".#./##./.#./.#./###"), ('2', "##./..#/.#./#../###"),
+    ('3', "##./..#/.#./..#/##."), ('4', "#.#/#.#/###/..#/..#"), ('5', "###/#../##./..#/##."),
+    ('6', ".##/#../###/#.#/###"), ('7', "###/..#/.#./.#./.#."), ('8', "###/#.#/###/#.#/###"),
+    ('9', "###/#.#/###/..#/##."),
+    ('.', ".../.../.../.../.#."), (',', ".../.../.../.#./#.."), ('!', ".#./.#./.#./.../.#."),
+    ('?', "##./..#/.#./.../.#."), (':', ".../.#./.../.#./..."), (';', ".../.#./.../.#./#.."),
+    ('\'', ".#./.#./.../.../..."), ('"', "#.#/#.#/.../.../..."), ('-', ".../.../###/.../..."),
+    ('+', ".../.#./###/.#./..."), ('=', ".../###/.../###/..."), ('/', "..#/..#/.#./#../#.."),
+    ('(', "..#/.#./.#./.#./..#"), (')', "#../.#./.#./.#./#.."), ('_', ".../.../.../.../###"),
+    ('*', "#.#/.#./#.#/.../..."), ('#', "#.#/###/#.#/###/#.#"), ('%', "#.#/..#/.#./#../#.#"),
+    ('&', ".#./#.#/.#./#.#/.##"), ('<', "..#/.#./#../.#./..#"), ('>', "#../.#./..#/.#./#.."),
+    ('[', "##./#../#../#../##."), (']', ".##/..#/..#/..#/.##"),
+];
+
+const FIXTURE_KERNING: &[(char, char, i32)] = &[('A', 'V', -1), ('V', 'A', -1), ('L', 'T', -1)];
+
+// Atlas cells hold the 3 ink columns plus the 1px padding on either side the renderer trims off
+const CELL_WIDTH: u32 = 5;
+const CELL_HEIGHT: u32 = 5;
+const ATLAS_COLUMNS: u32 = 16;
+// The default GlyphTrim advances by xadvance - 3, so this leaves one empty column after each glyph
+const FIXTURE_ADVANCE: u32 = 7;
+const FIXTURE_SPACE_ADVANCE: u32 = 5;
+
+// Every character the fixture font has, in atlas order; lowercase letters reuse the capitals
+fn fixture_chars() -> impl Iterator<Item = (char, &'static str)> {
+    FIXTURE_GLYPHS.iter().copied()
+        .chain(FIXTURE_GLYPHS.iter().filter(|(ch, _)| ch.is_ascii_uppercase()).map(|(ch, rows)| (ch.to_ascii_lowercase(), *rows)))
+}
+
+fn cell_position(index: u32) -> (u32, u32) {
+    ((index % ATLAS_COLUMNS) * CELL_WIDTH, (index / ATLAS_COLUMNS) * CELL_HEIGHT)
+}
+
+// The fixture atlas: white ink on transparent cells
+pub fn fixture_atlas() -> RgbaImage {
+    let rows = (fixture_chars().count() as u32).div_ceil(ATLAS_COLUMNS);
+    let mut atlas = RgbaImage::new(ATLAS_COLUMNS * CELL_WIDTH, rows * CELL_HEIGHT);
+
+    for (index, (_, bitmap)) in fixture_chars().enumerate() {
+        let (cell_x, cell_y) = cell_position(index as u32);
+        for (y, row) in bitmap.split('/').enumerate() {
+            for (x, _) in row.chars().enumerate().filter(|(_, pixel)| *pixel == '#') {
+                atlas.put_pixel(cell_x + 1 + x as u32, cell_y + y as u32, Rgba([255, 255, 255, 255]));
+            }
+        }
+    }
+
+    atlas
+}
+
+// The .fnt text describing fixture_atlas
+pub fn fixture_fnt() -> String {
+    let atlas = fixture_atlas();
+    let mut lines = vec![
+        "info face=\"Fixture 3x5\" size=5 spacing=0,0 outline=0 padding=0,1,0,1".to_string(),
+        format!("common lineHeight=7 base=5 scaleW={} scaleH={} pages=1 packed=0", atlas.width(), atlas.height()),
+        "page id=0 file=\"fixture.png\"".to_string(),
+        format!("char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance={} page=0", FIXTURE_SPACE_ADVANCE),
+    ];
+
+    for (index, (ch, _)) in fixture_chars().enumerate() {
+        let (x, y) = cell_position(index as u32);
+        lines.push(format!(
+            "char id={} x={} y={} width={} height={} xoffset=0 yoffset=0 xadvance={} page=0",
+            ch as u32, x, y, CELL_WIDTH, CELL_HEIGHT, FIXTURE_ADVANCE
+        ));
+    }
+    for (first, second, amount) in FIXTURE_KERNING {
+        lines.push(format!("kerning first={} second={} amount={}", *first as u32, *second as u32, amount));
+    }
+
+    lines.join("\n")
+}
+
+pub fn fixture_font() -> Font {
+    fixture_font_with(&FontLoadOptions::default())
+}
+
+pub fn fixture_font_with(options: &FontLoadOptions) -> Font {
+    Font::from_fnt_with(fixture_fnt().as_bytes(), vec![DynamicImage::ImageRgba8(fixture_atlas())], options)
+        .expect("the fixture font is generated to be valid")
+}
+
+// render_text_with with the fixture font and the settings the window starts with
+pub fn render_fixture(text: &str) -> Result<RgbaImage, Box<dyn Error>> {
+    render_text_with(&fixture_font(), text, &RenderOptions::default())
+}
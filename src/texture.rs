@@ -0,0 +1,600 @@
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use image::{DynamicImage, RgbaImage, Rgba, imageops};
+use minecraft_titles::{Font, FontAttribution, FontLoadOptions, page_files, TextLayout, RenderTimings, DebugOverlayColors, render_layout_layers, compose_into, debug_overlay, rasterize_glyphs, scale_nearest, layout_with, suggest_split, wrap_lines, clipped_glyphs, tile_background, generate_background, tiled_width, sanitize_text, strip_formatting_codes, formatting_codes_at_end, glyph_source_summary, BackgroundOptions, RenderError, RenderOptions, LayoutProfile, GuideTemplate, Outline, CharSet, DisallowedPolicy, BackgroundSource};
+
+// Load font data and images
+pub const FONT_DATA: &[u8] = include_bytes!("./assets/MinecraftDebugger-bitmap.fnt");
+pub const FONT_IMAGE: &[u8] = include_bytes!("./assets/MinecraftDebugger-bitmap.png");
+pub const BACKGROUND_IMAGE: &[u8] = include_bytes!("./assets/uv_checker.png");
+
+// Keyword of the PNG text chunk that credits the font a texture was drawn with
+const SOURCE_FONT_KEYWORD: &str = "Source-Font";
+
+pub fn load_embedded_font(load_options: &FontLoadOptions) -> Result<Font, Box<dyn Error>> {
+    Font::from_fnt_with(FONT_DATA, vec![image::load_from_memory(FONT_IMAGE)?], load_options)
+}
+
+// A .fnt file on disk, its atlas images in page id order and the sidecar with its attribution
+pub struct FontFiles {
+    pub fnt: PathBuf,
+    pub pages: Vec<PathBuf>,
+    pub sidecar: Option<PathBuf>,
+}
+
+impl FontFiles {
+    // Take the atlas files from the .fnt's page lines, next to the .fnt itself. The sidecar is
+    // <name>.toml next to <name>.fnt, or a font.toml the whole folder shares
+    pub fn beside(fnt: &Path) -> Result<FontFiles, Box<dyn Error>> {
+        let directory = fnt.parent().unwrap_or(Path::new(""));
+        let font_data = fs::read(fnt).map_err(|e| format!("Could not read {}: {}", fnt.display(), e))?;
+        let pages = page_files(&font_data)?.iter().map(|file| directory.join(file)).collect();
+        let sidecar = [fnt.with_extension("toml"), directory.join("font.toml")].into_iter().find(|path| path.is_file());
+
+        Ok(FontFiles { fnt: fnt.to_path_buf(), pages, sidecar })
+    }
+
+    pub fn load(&self, load_options: &FontLoadOptions) -> Result<Font, Box<dyn Error>> {
+        let pages = self.pages.iter()
+            .map(|path| image::open(path).map_err(|e| format!("Could not open the atlas {}: {}", path.display(), e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut font = Font::from_fnt_with(&fs::read(&self.fnt)?, pages, load_options)?;
+        if let Some(sidecar) = &self.sidecar {
+            let text = fs::read_to_string(sidecar).map_err(|e| format!("Could not read {}: {}", sidecar.display(), e))?;
+            font.merge_attribution(FontAttribution::from_toml(&text).map_err(|e| format!("{} ({})", e, sidecar.display()))?);
+        }
+
+        Ok(font)
+    }
+}
+
+// Settings the texture is rendered with, from the window controls or the command line
+pub struct TextureOptions {
+    pub sanitize: bool,
+    pub use_kerning: bool,
+    pub kern_across_spaces: Option<f32>,
+    pub scale_factor: f32,
+    pub legacy_guide_scale: bool,
+    // Pixel density of the saved texture: every pixel becomes an output_scale × output_scale block,
+    // for packs at 2x to MAX_OUTPUT_SCALE. 1 saves it as drawn
+    pub output_scale: u32,
+    pub background_choice: usize,
+    pub guides_mirrored: bool,
+    pub guides: GuideTemplate,
+    // The red debug line at the baseline row, for lining up a font; off for textures that ship
+    pub draw_baseline_guide: bool,
+    // None leaves it to the layout profile
+    pub letter_spacing: Option<i32>,
+    pub alignment: Alignment,
+    pub layout_profile: LayoutProfile,
+    pub bold: bool,
+    pub shadow: bool,
+    pub tint: Rgba<u8>,
+    pub outline: Option<Outline>,
+    pub gradient: Option<(Rgba<u8>, Rgba<u8>)>,
+    pub allowed_chars: Option<CharSet>,
+    pub disallowed_policy: DisallowedPolicy,
+    // Text wider than this is handled by line_fit
+    pub max_line_width: Option<u32>,
+    pub line_fit: LineFit,
+    // Fail instead of warning when a glyph would be cut off at the texture edge
+    pub strict_clipping: bool,
+}
+
+// Largest output_scale offered; 4x is as dense as title art in high resolution packs gets
+pub const MAX_OUTPUT_SCALE: u32 = 4;
+
+// What render_texture does with text wider than max_line_width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineFit {
+    // Keep it on one line and warn, suggesting a two-line split
+    #[default]
+    Warn,
+    // Split it onto two lines at the space that balances them best
+    Split,
+    // Wrap it onto as many lines as it takes
+    Wrap,
+}
+
+impl TextureOptions {
+    // The settings render_layers_with needs for one line of the texture
+    pub fn render_options(&self) -> RenderOptions {
+        RenderOptions::default()
+            .kerning(self.use_kerning)
+            .kern_across_spaces(self.kern_across_spaces)
+            .scale(self.scale_factor)
+            .mirrored_guides(self.guides_mirrored)
+            .guides(self.guides)
+            .draw_baseline(self.draw_baseline_guide)
+            .letter_spacing(self.letter_spacing)
+            .layout_profile(self.layout_profile)
+            .bold(self.bold)
+            .shadow(self.shadow)
+            .tint(self.tint)
+            .outline(self.outline)
+            .gradient(self.gradient)
+            .allowed_chars(self.allowed_chars.clone(), self.disallowed_policy)
+            .legacy_guide_scale(self.legacy_guide_scale)
+            .strict_clipping(self.strict_clipping)
+    }
+
+    // How far a line's ink reaches in the texture, further than in its layout when the text is scaled
+    fn output_ink_width(&self, text_layout: &TextLayout) -> u32 {
+        match self.legacy_guide_scale {
+            true => text_layout.ink_width,
+            false => (text_layout.ink_width as f32 * self.scale_factor).round() as u32,
+        }
+    }
+
+    // The finished texture at output_scale times its density. Nearest neighbor with a whole factor
+    // copies every pixel, the background's included, into an exact block, so nothing blurs
+    fn scale_output(&self, image: RgbaImage) -> Result<RgbaImage, Box<dyn Error>> {
+        if self.output_scale <= 1 {
+            return Ok(image);
+        }
+        let (width, height) = (image.width().saturating_mul(self.output_scale), image.height().saturating_mul(self.output_scale));
+        self.render_options().limits.check_canvas(width, height)?;
+        Ok(imageops::resize(&image, width, height, imageops::FilterType::Nearest))
+    }
+}
+
+// Where lines narrower than the widest one go, and how frames narrower than the widest one are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl Alignment {
+    // Left edge of each line, given its width and the column its rightmost glyph ends at, and the width
+    // all of them need together. Centering rounds down so a text always lands on the same column. Right
+    // alignment lines up the glyphs' right edges rather than the pens, which may end past them
+    fn place(self, lines: &[(u32, u32)]) -> (Vec<u32>, u32) {
+        let widest = lines.iter().map(|(width, _)| *width).max().unwrap_or(0);
+        let offsets: Vec<u32> = match self {
+            Alignment::Left => lines.iter().map(|_| 0).collect(),
+            Alignment::Center => lines.iter().map(|(width, _)| (widest - width) / 2).collect(),
+            Alignment::Right => {
+                let right = lines.iter().map(|(_, ink_width)| *ink_width).max().unwrap_or(0);
+                lines.iter().map(|(_, ink_width)| right - ink_width).collect()
+            }
+        };
+        let width = lines.iter().zip(&offsets).map(|((width, _), x)| x + width).max().unwrap_or(0);
+
+        (offsets, width)
+    }
+}
+
+// Widest line the window accepts before suggesting a split, the width of a 256 px GUI slot
+pub const DEFAULT_MAX_LINE_WIDTH: u32 = 256;
+
+// One line of the texture: its layout and where its layers were placed in the rendered image
+pub struct TextureLine {
+    pub layout: TextLayout,
+    pub x: i64,
+    pub y: i64,
+}
+
+// A finished texture together with the layout of each line and anything worth warning about
+pub struct Texture {
+    pub image: RgbaImage,
+    pub lines: Vec<TextureLine>,
+    pub warnings: Vec<String>,
+    pub timings: RenderTimings,
+    // How much every line was scaled from its layout: scale_factor, or 1 with the legacy guide scale
+    pub scale: f32,
+    // How much the finished image was scaled on top of that, see TextureOptions::output_scale
+    pub output_scale: u32,
+}
+
+impl Texture {
+    // Debug overlay of every line, placed the same way as the line's layers and as dense as the image
+    pub fn debug_overlay(&self, font: &Font, colors: &DebugOverlayColors) -> RgbaImage {
+        let overlay = self.stack_lines(|layout| debug_overlay(layout, font, colors));
+        match self.output_scale > 1 {
+            true => imageops::resize(&overlay, overlay.width() * self.output_scale, overlay.height() * self.output_scale, imageops::FilterType::Nearest),
+            false => overlay,
+        }
+    }
+
+    // The text of every line alone, without the guides, the baseline or the background, as the game
+    // draws it, so at the font's density whatever output_scale is
+    pub fn text_layer(&self, font: &Font) -> RgbaImage {
+        self.stack_lines(|layout| rasterize_glyphs(layout, font))
+    }
+
+    // An image drawn from each line's layout, scaled the way the line's layers were and placed where
+    // they went
+    fn stack_lines(&self, draw: impl Fn(&TextLayout) -> RgbaImage) -> RgbaImage {
+        let scale = |length: u32| (length as f32 * self.scale).round() as i64;
+        let width = self.lines.iter().map(|line| line.x + scale(line.layout.width)).max().unwrap_or(0);
+        let height = self.lines.iter().map(|line| line.y + scale(line.layout.height)).max().unwrap_or(0);
+        let mut stacked = RgbaImage::new(width as u32, height as u32);
+
+        for line in &self.lines {
+            let mut image = draw(&line.layout);
+            if self.scale != 1.0 {
+                image = scale_nearest(&image, scale(line.layout.width) as u32, scale(line.layout.height) as u32);
+            }
+            compose_into(&mut stacked, line.x, line.y, &[&image]);
+        }
+
+        stacked
+    }
+}
+
+// Background sources offered by the combo box, None being the embedded UV checker
+pub fn background_source(background_choice: usize) -> Option<BackgroundSource<'static>> {
+    match background_choice {
+        1 => Some(BackgroundSource::Checker { cell_size: 8, colors: (Rgba([64, 64, 64, 255]), Rgba([96, 96, 96, 255])) }),
+        2 => Some(BackgroundSource::SolidColor(Rgba([0, 0, 0, 255]))),
+        3 => Some(BackgroundSource::Gradient { from: Rgba([48, 48, 96, 255]), to: Rgba([0, 0, 0, 255]), vertical: true }),
+        _ => None,
+    }
+}
+
+// Background the text layer is placed on in the saved texture
+pub fn texture_background(bg_image: &DynamicImage, background_choice: usize, text_layer: &RgbaImage) -> Result<RgbaImage, RenderError> {
+    let text_layer_width = text_layer.width();
+    let tiled_bg_height = text_layer.height().max(32); // Ensure at least 32 pixels high
+
+    match background_source(background_choice) {
+        // Keep the same output width as the UV checker so textures stay interchangeable
+        Some(source) => generate_background(&source, tiled_width(bg_image.width(), text_layer_width)?, tiled_bg_height, &BackgroundOptions::default()),
+        None => tile_background(bg_image, text_layer_width, tiled_bg_height),
+    }
+}
+
+// Run the whole pipeline, from the entered text to the texture that gets saved
+pub fn render_texture(font: &Font, text: &str, options: &TextureOptions) -> Result<Texture, Box<dyn Error>> {
+    let bg_image = image::load_from_memory(BACKGROUND_IMAGE)?;
+    let mut texture = render_lines(font, text, options)?;
+    texture.warnings.splice(0..0, font.warnings().iter().cloned());
+
+// Create the tiled background and overlay the rendered image on it
+    texture.image = RenderTimings::time(&mut texture.timings.background, || {
+        place_on_background(&bg_image, options.background_choice, &texture.image)
+    })?;
+    texture.image = options.scale_output(texture.image)?;
+    texture.output_scale = options.output_scale.max(1);
+
+    Ok(texture)
+}
+
+fn place_on_background(bg_image: &DynamicImage, background_choice: usize, rendered_image: &RgbaImage) -> Result<RgbaImage, RenderError> {
+    let mut tiled_bg = texture_background(bg_image, background_choice, rendered_image)?;
+    imageops::overlay(&mut tiled_bg, rendered_image, -1, 0);
+    Ok(tiled_bg)
+}
+
+// Every render step short of the background: the lines stacked into one transparent image
+fn render_lines(font: &Font, text: &str, options: &TextureOptions) -> Result<Texture, Box<dyn Error>> {
+    let mut warnings = Vec::new();
+    let mut text_to_render = text.to_string();
+    if options.sanitize {
+        let (sanitized, removed) = sanitize_text(&text_to_render);
+        text_to_render = sanitized;
+        warnings.extend(removed);
+    }
+
+    // A damaged font is only good enough if it still has every character this text needs
+    if font.is_partial() {
+        let mut lost: Vec<char> = Vec::new();
+        for ch in strip_formatting_codes(&text_to_render).chars().filter(|ch| !ch.is_whitespace() && !ch.is_control() && !font.has(*ch)) {
+            if !lost.contains(&ch) {
+                lost.push(ch);
+            }
+        }
+        if !lost.is_empty() {
+            let listed: Vec<String> = lost.iter().map(|ch| format!("'{}' (U+{:04X})", ch, *ch as u32)).collect();
+            return Err(format!("The font file is damaged and lacks {} needed for this text", listed.join(", ")).into());
+        }
+    }
+
+// Render every line and stack them, aligned, into the final image
+    // Each newline starts a line of its own, fitted to the line width by itself. Blank lines are
+    // skipped, and text with nothing but blank lines fails the way blank text does. The limit on the
+    // length is for the whole text, not per line
+    options.render_options().limits.check_text(&text_to_render)?;
+    // Every line is laid out on its own, so each starts in the style the one before it ended in, and
+    // is fitted in that style
+    let mut line_texts: Vec<String> = Vec::new();
+    for line in text_to_render.split('\n').filter(|line| !line.trim().is_empty()) {
+        let carried = line_texts.last().map(|previous| formatting_codes_at_end(previous)).unwrap_or_default();
+        line_texts.extend(fit_lines(font, &format!("{}{}", carried, line), options, &mut warnings)?);
+    }
+    if line_texts.is_empty() {
+        line_texts.push(text_to_render.clone());
+    }
+    let render_options = options.render_options();
+    let mut timings = RenderTimings::default();
+    let text_layouts = line_texts.iter()
+        .map(|line| RenderTimings::time(&mut timings.layout, || layout_with(font, line, &render_options)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let line_layers = text_layouts.iter()
+        .map(|text_layout| render_layout_layers(font, text_layout, &render_options))
+        .collect::<Result<Vec<_>, _>>()?;
+    let extents: Vec<(u32, u32)> = line_layers.iter().zip(&text_layouts)
+        .map(|(layers, text_layout)| (layers.dimensions().0, options.output_ink_width(text_layout)))
+        .collect();
+    let (offsets, width) = options.alignment.place(&extents);
+    let height = line_layers.iter().map(|layers| layers.dimensions().1).sum();
+    let mut rendered_image = RgbaImage::new(width, height);
+    let mut lines = Vec::new();
+
+    let line_count = text_layouts.len();
+    let mut y = 0;
+    for (index, ((layers, text_layout), x)) in line_layers.iter().zip(text_layouts).zip(offsets).enumerate() {
+        let (line_width, line_height) = layers.dimensions();
+        compose_into(&mut rendered_image, x.into(), y.into(), &[&layers.guides, &layers.baseline, &layers.text]);
+        timings.glyph_blit += layers.timings.glyph_blit;
+        timings.guides += layers.timings.guides;
+
+// Warn about glyphs that were cut off at the texture edges instead of shipping them silently
+        let clipped = clipped_glyphs(&text_layout, line_width, line_height);
+        if options.strict_clipping && !clipped.is_empty() {
+            return Err(RenderError::GlyphsClipped(clipped).into());
+        }
+        warnings.extend(clipped.iter().map(|clip| format!("Clipped at the texture edge: {}", clip)));
+        if !text_layout.replaced.is_empty() {
+            let listed: Vec<String> = text_layout.replaced.iter().map(|replaced| replaced.to_string()).collect();
+            let line = if line_count > 1 { format!(" in line {}", index + 1) } else { String::new() };
+            warnings.push(format!("Outside the allowed characters{}: {}", line, listed.join(", ")));
+        }
+
+        lines.push(TextureLine { layout: text_layout, x: x.into(), y: y.into() });
+        y += line_height;
+    }
+
+    let mut missing: Vec<char> = Vec::new();
+    for ch in lines.iter().flat_map(|line| &line.layout.missing) {
+        if !missing.contains(ch) {
+            missing.push(*ch);
+        }
+    }
+    if !missing.is_empty() {
+        let listed: Vec<String> = missing.iter().map(|ch| format!("'{}' (U+{:04X})", ch, *ch as u32)).collect();
+        warnings.push(format!("The font has no glyph for {}", listed.join(", ")));
+    }
+    if let Some(summary) = glyph_source_summary(lines.iter().flat_map(|line| &line.layout.glyphs)) {
+        warnings.push(format!("Not every glyph came from the font: {}", summary));
+    }
+
+    let scale = if options.legacy_guide_scale { 1.0 } else { options.scale_factor };
+    Ok(Texture { image: rendered_image, lines, warnings, timings, scale, output_scale: 1 })
+}
+
+// Several texts rendered as the frames of one animated texture, stacked top to bottom
+pub struct FrameStrip {
+    pub image: RgbaImage,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub warnings: Vec<String>,
+    pub timings: RenderTimings,
+}
+
+impl FrameStrip {
+    // The .mcmeta that makes Minecraft play the strip, `frame_time` ticks per frame. Frames that are not
+    // square need their size spelled out, and the strip must hold a whole number of them
+    pub fn animation_mcmeta(&self, frame_time: u32) -> Result<String, Box<dyn Error>> {
+        if self.frame_height == 0 || !self.image.height().is_multiple_of(self.frame_height) || self.image.width() != self.frame_width {
+            return Err(format!(
+                "Error: a {}x{} strip does not divide into {}x{} frames",
+                self.image.width(), self.image.height(), self.frame_width, self.frame_height
+            ).into());
+        }
+
+        Ok(format!(
+            "{{\n  \"animation\": {{\n    \"frametime\": {},\n    \"width\": {},\n    \"height\": {}\n  }}\n}}\n",
+            frame_time, self.frame_width, self.frame_height
+        ))
+    }
+}
+
+// Where render_into_atlas pastes the texture: its top left corner in the atlas and, optionally, the
+// largest region it may cover
+pub struct AtlasRegion {
+    pub x: u32,
+    pub y: u32,
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+}
+
+// Render the texture and paste it into a copy of `atlas`, replacing the pixels it covers. A texture
+// bigger than the region or running off the atlas is an error, unless `clip` is set; then the part
+// that fits is pasted and the rest is reported in the warnings
+pub fn render_into_atlas(font: &Font, text: &str, options: &TextureOptions, atlas: &RgbaImage, region: &AtlasRegion, clip: bool) -> Result<Texture, Box<dyn Error>> {
+    if region.x >= atlas.width() || region.y >= atlas.height() {
+        return Err(format!("Error: {},{} is outside the {}x{} atlas", region.x, region.y, atlas.width(), atlas.height()).into());
+    }
+
+    let mut texture = render_texture(font, text, options)?;
+    let available_width = (atlas.width() - region.x).min(region.max_width.unwrap_or(u32::MAX));
+    let available_height = (atlas.height() - region.y).min(region.max_height.unwrap_or(u32::MAX));
+    let (width, height) = texture.image.dimensions();
+    if width > available_width || height > available_height {
+        let problem = format!(
+            "the {}x{} texture does not fit the {}x{} space at {},{}",
+            width, height, available_width, available_height, region.x, region.y
+        );
+        if !clip {
+            return Err(format!("Error: {}", problem).into());
+        }
+        texture.warnings.push(format!("Clipped, {}", problem));
+    }
+
+    let pasted = imageops::crop_imm(&texture.image, 0, 0, width.min(available_width), height.min(available_height)).to_image();
+    let mut image = atlas.clone();
+    imageops::replace(&mut image, &pasted, region.x.into(), region.y.into());
+    texture.image = image;
+
+    Ok(texture)
+}
+
+// Saves this process has started, so each one gets a temporary file of its own
+static TEMPORARY_FILES: AtomicU64 = AtomicU64::new(0);
+
+// Write through a temporary file next to `path` and rename it into place, so an interrupted save
+// never leaves a half-written file, which matters most when overwriting an existing atlas. The
+// temporary file is named after this process and this call, so saves of the same file at once,
+// from several copies of the tool or several threads of one, each replace it whole and the last one
+// wins
+pub fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    let file_name = path.file_name().ok_or(format!("Error: {} is not a file path", path.display()))?;
+    let mut temporary_name = file_name.to_os_string();
+    temporary_name.push(format!(".{}.{}.tmp", std::process::id(), TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)));
+    let temporary_path = path.with_file_name(temporary_name);
+
+    // A name that is already taken belongs to someone else's save, so it is left alone. The file is
+    // closed before the rename, which Windows refuses for an open file
+    let mut file = OpenOptions::new().write(true).create_new(true).open(&temporary_path)?;
+    let written = file.write_all(bytes);
+    drop(file);
+    if let Err(e) = written.and_then(|_| fs::rename(&temporary_path, path)) {
+        let _ = fs::remove_file(&temporary_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+// Save an output under the first of <stem>_1.<ext>, <stem>_2.<ext> and so on next to `path` that
+// doesn't exist yet, and return that path. `save` writes every file of the output while an advisory
+// lock on <file>.lock next to it is held, and the names are checked once the lock is, so copies of
+// the tool numbering their outputs in one folder at once never take the same number. The lock file
+// stays behind for the next save
+pub fn save_numbered(path: &Path, save: impl FnOnce(&Path) -> Result<(), Box<dyn Error>>) -> Result<PathBuf, Box<dyn Error>> {
+    let file_name = path.file_name().ok_or(format!("Error: {} is not a file path", path.display()))?;
+    let mut lock_name = file_name.to_os_string();
+    lock_name.push(".lock");
+    let lock = OpenOptions::new().create(true).truncate(false).write(true).open(path.with_file_name(lock_name))?;
+    lock.lock()?;
+
+    let stem = path.file_stem().unwrap_or(file_name).to_string_lossy();
+    let numbered = (1..=u32::MAX)
+        .map(|number| match path.extension() {
+            Some(extension) => path.with_file_name(format!("{}_{}.{}", stem, number, extension.to_string_lossy())),
+            None => path.with_file_name(format!("{}_{}", stem, number)),
+        })
+        .find(|numbered| !numbered.exists())
+        .ok_or(format!("Error: every numbered name for {} is taken", path.display()))?;
+    save(&numbered)?;
+
+    // Dropping the file releases the lock
+    drop(lock);
+    Ok(numbered)
+}
+
+// RRGGBB or RRGGBBAA in hex, with or without a leading '#'; alpha defaults to opaque
+pub fn parse_hex_color(value: &str) -> Option<Rgba<u8>> {
+    let hex = value.trim().strip_prefix('#').unwrap_or(value.trim());
+    let channels: Option<Vec<u8>> = (0..hex.len().div_ceil(2))
+        .map(|index| hex.get(index * 2..index * 2 + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect();
+    match *channels.as_deref()? {
+        [red, green, blue] => Some(Rgba([red, green, blue, 255])),
+        [red, green, blue, alpha] => Some(Rgba([red, green, blue, alpha])),
+        _ => None,
+    }
+}
+
+// Encode an image as PNG, with a Source-Font text chunk crediting the font when it has an attribution
+pub fn encode_png(image: &RgbaImage, font: &Font) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut encoded = Vec::new();
+    let mut encoder = png::Encoder::new(&mut encoded, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    if !font.attribution().is_empty() {
+        encoder.add_text_chunk(SOURCE_FONT_KEYWORD.to_string(), font.attribution().to_string())?;
+    }
+    encoder.write_header()?.write_image_data(image.as_raw())?;
+
+    Ok(encoded)
+}
+
+// Render each text as one frame. Every frame gets the size of the largest text, so the strip can play
+// as an animation; texts are placed in it by the alignment and centered vertically
+pub fn render_frames(font: &Font, texts: &[String], options: &TextureOptions) -> Result<FrameStrip, Box<dyn Error>> {
+    if texts.is_empty() {
+        return Err("Error: an animation needs at least one frame".into());
+    }
+
+    let bg_image = image::load_from_memory(BACKGROUND_IMAGE)?;
+    let rendered = texts.iter()
+        .map(|text| render_lines(font, text, options))
+        .collect::<Result<Vec<_>, _>>()?;
+    let extents: Vec<(u32, u32)> = rendered.iter()
+        .map(|texture| {
+            let ink_width = texture.lines.iter().map(|line| line.x as u32 + options.output_ink_width(&line.layout)).max().unwrap_or(0);
+            (texture.image.width(), ink_width)
+        })
+        .collect();
+    let (offsets, width) = options.alignment.place(&extents);
+    let height = rendered.iter().map(|texture| texture.image.height()).max().unwrap_or(0);
+
+    // The font's own warnings are the same for every frame, so they are only listed once
+    let mut warnings = font.warnings().to_vec();
+    let mut timings = RenderTimings::default();
+    let mut frames = Vec::new();
+    for (index, (texture, x)) in rendered.iter().zip(offsets).enumerate() {
+        warnings.extend(texture.warnings.iter().map(|warning| format!("Frame {}: {}", index + 1, warning)));
+        timings.layout += texture.timings.layout;
+        timings.glyph_blit += texture.timings.glyph_blit;
+        timings.guides += texture.timings.guides;
+
+        let mut frame = RgbaImage::new(width, height);
+        let y = (height - texture.image.height()) / 2;
+        imageops::overlay(&mut frame, &texture.image, x.into(), y.into());
+        frames.push(RenderTimings::time(&mut timings.background, || place_on_background(&bg_image, options.background_choice, &frame))?);
+    }
+
+    let (frame_width, frame_height) = frames[0].dimensions();
+    let mut image = RgbaImage::new(frame_width, frame_height * frames.len() as u32);
+    for (index, frame) in frames.iter().enumerate() {
+        imageops::overlay(&mut image, frame, 0, (index as u32 * frame_height).into());
+    }
+    let image = options.scale_output(image)?;
+    let (frame_width, frame_height) = (frame_width * options.output_scale.max(1), frame_height * options.output_scale.max(1));
+
+    Ok(FrameStrip { image, frame_width, frame_height, warnings, timings })
+}
+
+// Keep the text on one line unless it is wider than max_line_width; then wrap it, split it at the best
+// space or warn with the split that would fit, as line_fit says. Only wrapping breaks words
+fn fit_lines(font: &Font, text: &str, options: &TextureOptions, warnings: &mut Vec<String>) -> Result<Vec<String>, Box<dyn Error>> {
+    let Some(max_line_width) = options.max_line_width else {
+        return Ok(vec![text.to_string()]);
+    };
+    let width = layout_with(font, text, &options.render_options())?.width;
+    if width <= max_line_width {
+        return Ok(vec![text.to_string()]);
+    }
+
+    if options.line_fit == LineFit::Wrap {
+        let lines = wrap_lines(font, text, max_line_width, &options.render_options())?;
+        for line in &lines {
+            let line_width = layout_with(font, line, &options.render_options())?.width;
+            if line_width > max_line_width {
+                warnings.push(format!("\"{}\" is {} px wide, more than the {} px line width, even on a line of its own", line, line_width, max_line_width));
+            }
+        }
+        return Ok(lines);
+    }
+
+    match suggest_split(font, text, &options.render_options())? {
+        Some((first, second)) if options.line_fit == LineFit::Split => Ok(vec![first.to_string(), format!("{}{}", formatting_codes_at_end(first), second)]),
+        Some((first, second)) => {
+            warnings.push(format!("The text is {} px wide, more than the {} px line width; it could be split as \"{} | {}\"", width, max_line_width, first, second));
+            Ok(vec![text.to_string()])
+        }
+        None => {
+            warnings.push(format!("The text is {} px wide, more than the {} px line width, and has no space to split it at", width, max_line_width));
+            Ok(vec![text.to_string()])
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::error::Error;
-use image::{DynamicImage, RgbaImage, imageops, Rgba};
+use std::fmt;
+use std::time::{Duration, Instant};
+use image::{DynamicImage, GenericImageView, Pixel, RgbaImage, imageops, Rgba};
 
 pub struct CharData {
     id: u32,
@@ -8,265 +13,3216 @@ pub struct CharData {
     y: u32,
     width: u32,
     height: u32,
+    xoffset: i32,
     yoffset: i32,
     xadvance: u32,
+    page: u32,
+    // Pixels added to each side of the rect (up, right, down, left) when the font was loaded
+    expansion: (u32, u32, u32, u32),
 }
 
+impl CharData {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    // Glyph rect in the atlas page
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn xoffset(&self) -> i32 {
+        self.xoffset
+    }
+
+    pub fn yoffset(&self) -> i32 {
+        self.yoffset
+    }
+
+    pub fn xadvance(&self) -> u32 {
+        self.xadvance
+    }
+
+    // Index of the atlas page the rect is on
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+}
+
+// Font-wide values from the .fnt info line. spacing and outline describe space and outline
+// pixels the exporter already baked into the atlas and the advances; padding (up, right, down,
+// left) is the empty margin around every glyph rect, None when the file doesn't declare it
+pub struct FontInfo {
+    pub face: String,
+    pub size: i32,
+    pub spacing: (i32, i32),
+    pub outline: u32,
+    pub padding: Option<(u32, u32, u32, u32)>,
+}
+
+// Who made a font and on what terms, so renders and exports can credit it. The name comes from the
+// .fnt info line's face; a sidecar TOML file next to the .fnt can fill in the rest or override it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontAttribution {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub license: Option<String>,
+    pub url: Option<String>,
+}
+
+impl FontAttribution {
+    // Read the name, author, license and url keys of a sidecar file. Only flat string keys are
+    // understood; other keys and a [font] table header are skipped, anything else is an error
+    pub fn from_toml(text: &str) -> Result<FontAttribution, Box<dyn Error>> {
+        let mut attribution = FontAttribution::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line == "[font]" {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or(format!("Error: line {} of the font sidecar is not a key = \"value\" pair", number + 1))?;
+            let value = parse_toml_string(value.trim())
+                .ok_or(format!("Error: the value on line {} of the font sidecar is not a quoted string", number + 1))?;
+            let field = match key.trim() {
+                "name" => &mut attribution.name,
+                "author" => &mut attribution.author,
+                "license" => &mut attribution.license,
+                "url" => &mut attribution.url,
+                _ => continue,
+            };
+            *field = Some(value);
+        }
+
+        Ok(attribution)
+    }
+
+    // The sidecar text from_toml reads back into the same attribution
+    pub fn to_toml(&self) -> String {
+        self.fields().iter()
+            .map(|(key, value)| format!("{} = \"{}\"\n", key, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\t', "\\t")))
+            .collect()
+    }
+
+    // Fields set here, with the missing ones taken from `fallback`
+    pub fn or(self, fallback: &FontAttribution) -> FontAttribution {
+        FontAttribution {
+            name: self.name.or_else(|| fallback.name.clone()),
+            author: self.author.or_else(|| fallback.author.clone()),
+            license: self.license.or_else(|| fallback.license.clone()),
+            url: self.url.or_else(|| fallback.url.clone()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields().is_empty()
+    }
+
+    fn fields(&self) -> Vec<(&'static str, &str)> {
+        [("name", &self.name), ("author", &self.author), ("license", &self.license), ("url", &self.url)].into_iter()
+            .filter_map(|(key, value)| Some((key, value.as_deref()?)))
+            .collect()
+    }
+}
+
+// On one line, as in "Some Font by Someone, CC BY 4.0, https://example.com"
+impl fmt::Display for FontAttribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name.as_deref().unwrap_or("Unnamed font"))?;
+        if let Some(author) = &self.author {
+            write!(f, " by {}", author)?;
+        }
+        for extra in [&self.license, &self.url].into_iter().flatten() {
+            write!(f, ", {}", extra)?;
+        }
+        Ok(())
+    }
+}
+
+// A basic "..." string with the common escapes, or a literal '...' string, optionally followed by a # comment
+fn parse_toml_string(value: &str) -> Option<String> {
+    let ends_cleanly = |rest: &str| rest.trim().is_empty() || rest.trim().starts_with('#');
+
+    if let Some(literal) = value.strip_prefix('\'') {
+        let (literal, rest) = literal.split_once('\'')?;
+        return ends_cleanly(rest).then(|| literal.to_string());
+    }
+
+    let mut chars = value.strip_prefix('"')?.chars();
+    let mut parsed = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return ends_cleanly(chars.as_str()).then_some(parsed),
+            '\\' => parsed.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            ch => parsed.push(ch),
+        }
+    }
+}
+
+// Line metrics from the .fnt common line
+#[derive(Debug, Clone, Copy)]
+pub struct FontMetrics {
+    pub line_height: u32,
+    pub base: u32,
+    pub scale_w: u32,
+    pub scale_h: u32,
+}
+
+// A loaded bitmap font: glyphs ordered by code point, kerning pairs, info and page images
+pub struct Font {
+    glyphs: BTreeMap<u32, CharData>,
+    kerning_pairs: HashMap<(u32, u32), i32>,
+    info: FontInfo,
+    metrics: Option<FontMetrics>,
+    standard_vertical_metrics: bool,
+    glyph_trim: GlyphTrim,
+    usual_xoffset: i32,
+    pages: Vec<DynamicImage>,
+    page_files: Vec<String>,
+    space_advance: Option<u32>,
+    missing_glyph: MissingGlyph,
+    partial: bool,
+    warnings: Vec<String>,
+    attribution: FontAttribution,
+}
+
+// What layout uses for a character the font has no glyph for
+enum MissingGlyph {
+    // Draw this glyph in its place
+    Glyph(u32),
+    // Leave a gap this wide so the words around it stay apart
+    Advance(u32),
+    // Skip the character entirely
+    Skip,
+}
+
+// Adjustments applied while a font is loaded
+pub struct FontLoadOptions {
+    // Share of the median letter/digit advance given to a synthesized space
+    pub space_fraction: f32,
+    // Grow every glyph's source rect by this many pixels per side, for atlases whose ink bleeds
+    // into the glyph borders
+    pub expand_glyph_crop: u32,
+    // Place glyphs the standard BMFont way: yoffset counts down from the top of the line, the
+    // baseline sits `base` rows below it and the line is `lineHeight` tall. Off by default because
+    // the embedded font measures yoffset up from the glyph bottom; layout then derives the baseline
+    // from the largest yoffset, as it does for fonts without a common line
+    pub standard_vertical_metrics: bool,
+    // Draw characters the font lacks as its glyph 0 or '?', or leave an average-width gap when it
+    // has neither, instead of dropping them and gluing the neighboring words together
+    pub missing_glyph_fallback: bool,
+    // Skip lines of a text or XML .fnt that can't be parsed, as left by an interrupted export or
+    // download, instead of failing the load. What was recovered is reported in the font's warnings
+    pub lenient: bool,
+    pub glyph_trim: GlyphTrim,
+}
+
+// Per-font tuning of how glyph rects are cut and how far the pen moves. The defaults suit exports
+// like the embedded font, whose rects carry padding and whose advances include negative spacing
+#[derive(Debug, Clone, Copy)]
+pub struct GlyphTrim {
+    // Columns cut off each side of a rect, never more than the padding the font declares
+    pub crop_left: u32,
+    pub crop_right: u32,
+    // Subtracted from every xadvance before the pen moves
+    pub advance_adjust: i32,
+}
+
+impl Default for GlyphTrim {
+    fn default() -> Self {
+        GlyphTrim { crop_left: 1, crop_right: 1, advance_adjust: 3 }
+    }
+}
+
+// How many glyphs the expansion warning names before summarizing the rest
+const EXPANSION_WARNING_GLYPHS: usize = 8;
+
+impl Default for FontLoadOptions {
+    fn default() -> Self {
+        FontLoadOptions { space_fraction: 0.5, expand_glyph_crop: 0, standard_vertical_metrics: false, missing_glyph_fallback: true, lenient: false, glyph_trim: GlyphTrim::default() }
+    }
+}
+
+impl Font {
+    // Parse a .fnt file; `pages` are the atlas images in page id order (see page_files) and every
+    // glyph's page must be among them
+    pub fn from_fnt(font_data_bytes: &[u8], pages: Vec<DynamicImage>) -> Result<Font, Box<dyn Error>> {
+        Font::from_fnt_with(font_data_bytes, pages, &FontLoadOptions::default())
+    }
+
+    // Parse a single-page font straight from the bytes of its .fnt file and its encoded atlas image
+    pub fn from_fnt_bytes(fnt: &[u8], atlas: &[u8]) -> Result<Font, Box<dyn Error>> {
+        let atlas = image::load_from_memory(atlas).map_err(|e| format!("Error: could not decode the atlas image: {}", e))?;
+        Font::from_fnt(fnt, vec![atlas])
+    }
+
+    pub fn from_fnt_with(font_data_bytes: &[u8], pages: Vec<DynamicImage>, options: &FontLoadOptions) -> Result<Font, Box<dyn Error>> {
+        if pages.is_empty() {
+            return Err("Error: a font needs at least one page image".into());
+        }
+
+        let mut recovery = Recovery { lenient: options.lenient, ..Recovery::default() };
+        let (glyphs, kerning_pairs, info, metrics, page_files) = parse_fnt(font_data_bytes, &mut recovery)?;
+        let recovery_warning = recovery.summary(glyphs.len(), kerning_pairs.len());
+        if let Some(char_data) = glyphs.values().find(|char_data| char_data.page as usize >= pages.len()) {
+            return Err(format!(
+                "Error: glyph U+{:04X} is on page {} but only {} page image{} given",
+                char_data.id, char_data.page, pages.len(), if pages.len() == 1 { " was" } else { "s were" }
+            ).into());
+        }
+
+        let mut font = Font {
+            glyphs,
+            kerning_pairs,
+            info,
+            metrics,
+            standard_vertical_metrics: options.standard_vertical_metrics,
+            glyph_trim: options.glyph_trim,
+            usual_xoffset: 0,
+            pages,
+            page_files,
+            space_advance: None,
+            missing_glyph: MissingGlyph::Skip,
+            partial: recovery_warning.is_some(),
+            warnings: recovery_warning.into_iter().collect(),
+            attribution: FontAttribution::default(),
+        };
+        font.attribution.name = Some(font.info.face.clone()).filter(|face| !face.is_empty());
+        font.usual_xoffset = font.most_common_xoffset();
+        font.synthesize_space(options.space_fraction);
+        if options.missing_glyph_fallback {
+            font.missing_glyph = font.pick_missing_glyph();
+        }
+        font.expand_glyph_rects(options.expand_glyph_crop);
+
+        Ok(font)
+    }
+
+    // Grow each glyph rect by up to `pixels` on every side. A side stops early at the atlas edge or
+    // where it would run into another glyph's rect, which tightly packed atlases leave no room for
+    fn expand_glyph_rects(&mut self, pixels: u32) {
+        if pixels == 0 {
+            return;
+        }
+
+        // (id, page, rect) for every glyph with pixels
+        let rects: Vec<_> = self.glyphs.values()
+            .filter(|char_data| char_data.width > 0 && char_data.height > 0)
+            .map(|char_data| (char_data.id, char_data.page, (char_data.x, char_data.y, char_data.width, char_data.height)))
+            .collect();
+        let overlaps_neighbor = |id: u32, page: u32, (x, y, width, height): (i64, i64, i64, i64)| {
+            rects.iter().any(|(other_id, other_page, (other_x, other_y, other_width, other_height))| {
+                *other_id != id
+                    && *other_page == page
+                    && x < (other_x + other_width) as i64 && (*other_x as i64) < x + width
+                    && y < (other_y + other_height) as i64 && (*other_y as i64) < y + height
+            })
+        };
+
+        let mut blocked = Vec::new();
+        for (id, page, (x, y, width, height)) in &rects {
+            let (page_width, page_height) = self.pages[*page as usize].dimensions();
+            let (mut up, mut right, mut down, mut left) = (0, 0, 0, 0);
+            let mut hit_neighbor = false;
+            let rect = |up: u32, right: u32, down: u32, left: u32| {
+                (*x as i64 - left as i64, *y as i64 - up as i64, (width + left + right) as i64, (height + up + down) as i64)
+            };
+
+            for _ in 0..pixels {
+                // One side at a time, so a neighbor on one side doesn't stop the others
+                for side in 0..4 {
+                    let grown = match side {
+                        0 if up < *y => (up + 1, right, down, left),
+                        1 if x + width + right < page_width => (up, right + 1, down, left),
+                        2 if y + height + down < page_height => (up, right, down + 1, left),
+                        3 if left < *x => (up, right, down, left + 1),
+                        _ => continue,
+                    };
+                    if overlaps_neighbor(*id, *page, rect(grown.0, grown.1, grown.2, grown.3)) {
+                        hit_neighbor = true;
+                    } else {
+                        (up, right, down, left) = grown;
+                    }
+                }
+            }
+
+            if hit_neighbor {
+                blocked.push(*id);
+            }
+            if let Some(char_data) = self.glyphs.get_mut(id) {
+                char_data.expansion = (up, right, down, left);
+            }
+        }
+
+        if !blocked.is_empty() {
+            let listed: Vec<String> = blocked.iter().take(EXPANSION_WARNING_GLYPHS).map(|id| format!("U+{:04X}", id)).collect();
+            let more = if blocked.len() > listed.len() { format!(" and {} more", blocked.len() - listed.len()) } else { String::new() };
+            self.warnings.push(format!("Glyph crop expansion stopped at a neighboring glyph for {}{}", listed.join(", "), more));
+        }
+    }
+
+    // The renderer's one-column trim already lines glyphs up for the font's usual xoffset (-1 for
+    // the embedded font, 0 for most others), so only a glyph's difference from it moves the glyph
+    fn most_common_xoffset(&self) -> i32 {
+        let mut counts: BTreeMap<i32, usize> = BTreeMap::new();
+        for char_data in self.glyphs.values().filter(|char_data| char_data.width > 0) {
+            *counts.entry(char_data.xoffset).or_default() += 1;
+        }
+        counts.into_iter().max_by_key(|(xoffset, count)| (*count, Reverse(*xoffset))).map_or(0, |(xoffset, _)| xoffset)
+    }
+
+    fn pick_missing_glyph(&self) -> MissingGlyph {
+        if let Some(id) = [0, '?' as u32].into_iter().find(|id| self.glyphs.get(id).is_some_and(|char_data| char_data.width > 0)) {
+            return MissingGlyph::Glyph(id);
+        }
+
+        let advances: Vec<u32> = self.glyphs.values().filter(|char_data| char_data.width > 0).map(|char_data| char_data.xadvance).collect();
+        match advances.len() {
+            0 => MissingGlyph::Skip,
+            count => MissingGlyph::Advance((advances.iter().map(|advance| *advance as u64).sum::<u64>() / count as u64) as u32),
+        }
+    }
+
+    // Whether layout has to fall back for this character. Control characters are never drawn
+    fn is_missing(&self, ch: char) -> bool {
+        self.advance(ch).is_none() && !ch.is_control()
+    }
+
+    // The glyph layout draws for `ch`, which is the fallback glyph when the font lacks it
+    fn layout_glyph(&self, ch: char) -> Option<&CharData> {
+        match &self.missing_glyph {
+            MissingGlyph::Glyph(id) if self.is_missing(ch) => self.glyphs.get(id),
+            _ => self.glyph(ch),
+        }
+    }
+
+    fn layout_advance(&self, ch: char) -> Option<u32> {
+        match &self.missing_glyph {
+            MissingGlyph::Glyph(id) if self.is_missing(ch) => self.glyphs.get(id).map(|char_data| char_data.xadvance),
+            MissingGlyph::Advance(advance) if self.is_missing(ch) => Some(*advance),
+            _ => self.advance(ch),
+        }
+    }
+
+    // Minimal fonts sometimes leave out the space glyph, which runs words together.
+    // Give the space a fraction of the median letter/digit advance instead
+    fn synthesize_space(&mut self, fraction: f32) {
+        if self.glyphs.contains_key(&(' ' as u32)) {
+            return;
+        }
+
+        let mut advances: Vec<u32> = self.glyphs.values()
+            .filter(|char_data| char::from_u32(char_data.id).is_some_and(|ch| ch.is_alphanumeric()))
+            .map(|char_data| char_data.xadvance)
+            .collect();
+        if advances.is_empty() {
+            return;
+        }
+        advances.sort_unstable();
+
+        let advance = (advances[advances.len() / 2] as f32 * fraction).round() as u32;
+        self.space_advance = Some(advance);
+        self.warnings.push(format!("The font has no space glyph; using a synthesized {} px advance", advance));
+    }
+
+    // Horizontal advance of a character, including a synthesized space
+    pub fn advance(&self, ch: char) -> Option<u32> {
+        match (self.glyph(ch), ch) {
+            (Some(char_data), _) => Some(char_data.xadvance),
+            (None, ' ') => self.space_advance,
+            (None, _) => None,
+        }
+    }
+
+    // Whether lenient loading had to skip parts of the file, so glyphs may be missing that the font
+    // was meant to have
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    // Problems found while loading that the font could work around
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn glyph(&self, ch: char) -> Option<&CharData> {
+        self.glyphs.get(&(ch as u32))
+    }
+
+    // Kerning between two characters, 0 when the font has no pair for them
+    pub fn kerning(&self, first: char, second: char) -> i32 {
+        self.kerning_pairs.get(&(first as u32, second as u32)).copied().unwrap_or(0)
+    }
+
+    pub fn has(&self, ch: char) -> bool {
+        self.glyphs.contains_key(&(ch as u32))
+    }
+
+    // Every character the font has a glyph for, in code point order
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.keys().filter_map(|id| char::from_u32(*id))
+    }
+
+    pub fn info(&self) -> &FontInfo {
+        &self.info
+    }
+
+    pub fn attribution(&self) -> &FontAttribution {
+        &self.attribution
+    }
+
+    // Take the attribution from a sidecar file; what it leaves out is kept from the .fnt
+    pub fn merge_attribution(&mut self, sidecar: FontAttribution) {
+        self.attribution = sidecar.or(&self.attribution);
+    }
+
+    // None when the file has no common line
+    pub fn metrics(&self) -> Option<&FontMetrics> {
+        self.metrics.as_ref()
+    }
+
+    // Distance between lines: the common line's lineHeight, or the tallest glyph when the file has none
+    pub fn line_height(&self) -> u32 {
+        match &self.metrics {
+            Some(metrics) => metrics.line_height,
+            None => self.glyphs.values().map(|char_data| char_data.height).max().unwrap_or(0),
+        }
+    }
+
+    pub fn pages(&self) -> &[DynamicImage] {
+        &self.pages
+    }
+
+    // Atlas file names from the page lines, in page id order
+    pub fn page_files(&self) -> &[String] {
+        &self.page_files
+    }
+}
+
+type FontTables = (BTreeMap<u32, CharData>, HashMap<(u32, u32), i32>, FontInfo, Option<FontMetrics>, Vec<String>);
+type FontMaps = (HashMap<u32, CharData>, HashMap<(u32, u32), i32>);
+
 // Function to load font data from a .fnt file
-pub fn load_font_data(font_data_bytes: &[u8]) -> Result<(HashMap<u32, CharData>, HashMap<(u32, u32), i32>), Box<dyn Error>> {
-    let font_data_str = std::str::from_utf8(font_data_bytes)?;
+#[deprecated(note = "use Font::from_fnt, which also keeps the font info and page images")]
+pub fn load_font_data(font_data_bytes: &[u8]) -> Result<FontMaps, Box<dyn Error>> {
+    let (glyphs, kerning_pairs, _, _, _) = parse_fnt(font_data_bytes, &mut Recovery::default())?;
+
+    Ok((glyphs.into_iter().collect(), kerning_pairs))
+}
+
+// Atlas file names a .fnt file refers to, in page id order, so callers know which images to load
+// before handing them to Font::from_fnt. Lines that don't parse are skipped, since only the page
+// lines matter here
+pub fn page_files(font_data_bytes: &[u8]) -> Result<Vec<String>, Box<dyn Error>> {
+    let (_, _, _, _, page_files) = parse_fnt(font_data_bytes, &mut Recovery { lenient: true, ..Recovery::default() })?;
+
+    Ok(page_files)
+}
+
+// Lines a lenient load skipped, and the char and kerning counts the file's headers promised
+#[derive(Default)]
+struct Recovery {
+    lenient: bool,
+    skipped: Vec<String>,
+    expected_chars: Option<usize>,
+    expected_kernings: Option<usize>,
+}
+
+impl Recovery {
+    // Pass a parsed line through, or note it and carry on without it when lenient
+    fn check<T>(&mut self, line_number: usize, result: Result<T, Box<dyn Error>>) -> Result<Option<T>, Box<dyn Error>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.lenient => {
+                self.skipped.push(format!("line {}: {}", line_number, e));
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Warning describing what a lenient load recovered, None when the file was complete
+    fn summary(&self, chars: usize, kernings: usize) -> Option<String> {
+        if !self.lenient {
+            return None;
+        }
+        let short_chars = self.expected_chars.filter(|expected| chars < *expected);
+        let short_kernings = self.expected_kernings.filter(|expected| kernings < *expected);
+        if self.skipped.is_empty() && short_chars.is_none() && short_kernings.is_none() {
+            return None;
+        }
+
+        let counted = |found: usize, expected: Option<usize>, name: &str| match expected {
+            Some(expected) => format!("{} of {} {}", found, expected, name),
+            None => format!("{} {}", found, name),
+        };
+        let mut summary = format!(
+            "The font file looks damaged; recovered {} and {}",
+            counted(chars, short_chars, "chars"), counted(kernings, short_kernings, "kerning pairs")
+        );
+        if let Some(first) = self.skipped.first() {
+            let plural = if self.skipped.len() == 1 { "" } else { "s" };
+            summary.push_str(&format!(", skipping {} unreadable line{} (first at {})", self.skipped.len(), plural, first));
+        }
+        Some(summary)
+    }
+}
+
+fn parse_fnt(font_data_bytes: &[u8], recovery: &mut Recovery) -> Result<FontTables, Box<dyn Error>> {
+    if font_data_bytes.starts_with(BINARY_FNT_MAGIC) {
+        return parse_binary_fnt(&font_data_bytes[BINARY_FNT_MAGIC.len()..]);
+    }
+    if font_data_bytes.starts_with(b"BMF") {
+        let version = font_data_bytes.get(3).map_or("missing".to_string(), |version| version.to_string());
+        return Err(format!("Error: unsupported binary font version ({}); only version 3 is supported", version).into());
+    }
+
+    // A download cut off inside a multi-byte character leaves invalid UTF-8 at the very end
+    let font_data_str = match std::str::from_utf8(font_data_bytes) {
+        Ok(text) => text,
+        Err(e) if recovery.lenient => {
+            let valid = &font_data_bytes[..e.valid_up_to()];
+            recovery.skipped.push(format!("line {}: invalid UTF-8", valid.iter().filter(|byte| **byte == b'\n').count() + 1));
+            std::str::from_utf8(valid)?
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let document_start = font_data_str.trim_start_matches('\u{FEFF}').trim_start();
+    if document_start.starts_with("<?xml") || document_start.starts_with("<font") {
+        return parse_xml_fnt(document_start, recovery);
+    }
+
+    let mut char_data_map = BTreeMap::new();
+    let mut kerning_pairs = HashMap::new();
+    let mut info = FontInfo { face: String::new(), size: 0, spacing: (0, 0), outline: 0, padding: None };
+    let mut metrics = None;
+    let mut page_files = BTreeMap::new();
+
+    for (index, line) in font_data_str.lines().enumerate() {
+        let line_number = index + 1;
+
+        if line.starts_with("info ") {
+            if let Some(parsed) = recovery.check(line_number, parse_info_line(line))? {
+                info = parsed;
+            }
+        } else if line.starts_with("common ") {
+            metrics = recovery.check(line_number, parse_common_line(line))?.or(metrics);
+        } else if line.starts_with("page ") {
+            if let Some((id, file)) = recovery.check(line_number, parse_page_line(line))? {
+                page_files.insert(id, file);
+            }
+        } else if line.starts_with("chars ") {
+            recovery.expected_chars = parse_count_line(line).ok();
+        } else if line.starts_with("kernings ") {
+            recovery.expected_kernings = parse_count_line(line).ok();
+        } else if line.starts_with("char id=") {
+            if let Some(char_data) = recovery.check(line_number, parse_char_line(line))? {
+                char_data_map.insert(char_data.id, char_data);
+            }
+        } else if line.starts_with("kerning first=") {
+            if let Some((first, second, amount)) = recovery.check(line_number, parse_kerning_line(line))? {
+                kerning_pairs.insert((first, second), amount);
+            }
+        }
+    }
+
+    Ok((char_data_map, kerning_pairs, info, metrics, page_files.into_values().collect()))
+}
+
+// Parse the XML flavor of .fnt. Every element's attributes are the same key="value" pairs the text
+// format uses, so each <info>, <char> and <kerning> tag goes through the text line parsers
+fn parse_xml_fnt(document: &str, recovery: &mut Recovery) -> Result<FontTables, Box<dyn Error>> {
+    let mut char_data_map = BTreeMap::new();
+    let mut kerning_pairs = HashMap::new();
+    let mut info = FontInfo { face: String::new(), size: 0, spacing: (0, 0), outline: 0, padding: None };
+    let mut metrics = None;
+    let mut page_files = BTreeMap::new();
+
+    let mut tags = document.split('<');
+    let mut line_number = tags.next().map_or(0, |before| before.matches('\n').count()) + 1;
+
+    for tag in tags {
+        let tag_line = line_number;
+        line_number += tag.matches('\n').count();
+
+        let unterminated = || format!("Error: unterminated XML tag '<{}'", tag.trim_end()).into();
+        let Some(element) = recovery.check(tag_line, tag.find('>').map(|end| &tag[..end]).ok_or_else(unterminated))? else {
+            continue;
+        };
+        let name_end = element.find(|ch: char| ch.is_whitespace() || ch == '/').unwrap_or(element.len());
+
+        match &element[..name_end] {
+            "info" => {
+                if let Some(parsed) = recovery.check(tag_line, parse_info_line(element))? {
+                    info = FontInfo { face: decode_xml_entities(&parsed.face), ..parsed };
+                }
+            }
+            "common" => metrics = recovery.check(tag_line, parse_common_line(element))?.or(metrics),
+            "page" => {
+                if let Some((id, file)) = recovery.check(tag_line, parse_page_line(element))? {
+                    page_files.insert(id, decode_xml_entities(&file));
+                }
+            }
+            "chars" => recovery.expected_chars = parse_count_line(element).ok(),
+            "kernings" => recovery.expected_kernings = parse_count_line(element).ok(),
+            "char" => {
+                if let Some(char_data) = recovery.check(tag_line, parse_char_line(element))? {
+                    char_data_map.insert(char_data.id, char_data);
+                }
+            }
+            "kerning" => {
+                if let Some((first, second, amount)) = recovery.check(tag_line, parse_kerning_line(element))? {
+                    kerning_pairs.insert((first, second), amount);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((char_data_map, kerning_pairs, info, metrics, page_files.into_values().collect()))
+}
+
+fn decode_xml_entities(value: &str) -> String {
+    value.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+// Binary BMFont files (version 3) start with "BMF" and the version byte
+const BINARY_FNT_MAGIC: &[u8] = b"BMF\x03";
+const BINARY_CHAR_SIZE: usize = 20;
+const BINARY_KERNING_SIZE: usize = 10;
+
+// Parse the blocks of a binary .fnt that follow the magic header into the same tables as the text format.
+// Every block starts with a type byte and a little-endian u32 size
+fn parse_binary_fnt(mut bytes: &[u8]) -> Result<FontTables, Box<dyn Error>> {
+    let mut char_data_map = BTreeMap::new();
+    let mut kerning_pairs = HashMap::new();
+    let mut info = FontInfo { face: String::new(), size: 0, spacing: (0, 0), outline: 0, padding: None };
+    let mut metrics = None;
+    let mut page_files = BTreeMap::new();
+
+    while !bytes.is_empty() {
+        let mut header = BinaryReader { bytes, block: "block header" };
+        let block_type = header.u8()?;
+        let block_size = header.u32()? as usize;
+        if header.bytes.len() < block_size {
+            return Err(format!("Error: binary font block {} needs {} bytes but only {} are left", block_type, block_size, header.bytes.len()).into());
+        }
+        let (block, rest) = header.bytes.split_at(block_size);
+        bytes = rest;
+
+        match block_type {
+            1 => info = parse_binary_info(BinaryReader { bytes: block, block: "info block" })?,
+            4 => {
+                if block.len() % BINARY_CHAR_SIZE != 0 {
+                    return Err(format!("Error: binary font chars block is {} bytes, not a multiple of {}", block.len(), BINARY_CHAR_SIZE).into());
+                }
+                for record in block.chunks_exact(BINARY_CHAR_SIZE) {
+                    let char_data = parse_binary_char(BinaryReader { bytes: record, block: "chars block" })?;
+                    char_data_map.insert(char_data.id, char_data);
+                }
+            }
+            5 => {
+                if block.len() % BINARY_KERNING_SIZE != 0 {
+                    return Err(format!("Error: binary font kerning block is {} bytes, not a multiple of {}", block.len(), BINARY_KERNING_SIZE).into());
+                }
+                for record in block.chunks_exact(BINARY_KERNING_SIZE) {
+                    let mut reader = BinaryReader { bytes: record, block: "kerning block" };
+                    let (first, second, amount) = (reader.u32()?, reader.u32()?, reader.i16()?);
+                    kerning_pairs.insert((first, second), amount.into());
+                }
+            }
+            2 => {
+                let mut reader = BinaryReader { bytes: block, block: "common block" };
+                let (line_height, base) = (reader.u16()?.into(), reader.u16()?.into());
+                let (scale_w, scale_h) = (reader.u16()?.into(), reader.u16()?.into());
+                metrics = Some(FontMetrics { line_height, base, scale_w, scale_h });
+            }
+            3 => {
+                // Null-terminated file names, one per page in id order
+                let mut reader = BinaryReader { bytes: block, block: "pages block" };
+                while !reader.bytes.is_empty() {
+                    page_files.insert(page_files.len() as u32, reader.null_terminated()?);
+                }
+            }
+            _ => return Err(format!("Error: unknown binary font block type {}", block_type).into()),
+        }
+    }
+
+    Ok((char_data_map, kerning_pairs, info, metrics, page_files.into_values().collect()))
+}
+
+fn parse_binary_info(mut reader: BinaryReader) -> Result<FontInfo, Box<dyn Error>> {
+    let size = reader.i16()?.into();
+    reader.take(5)?; // bitField, charSet, stretchH, aa
+    let padding = (reader.u8()?.into(), reader.u8()?.into(), reader.u8()?.into(), reader.u8()?.into());
+    // Stored as bytes, but exporters write negative spacing (like this repo's font) as two's complement
+    let spacing = ((reader.u8()? as i8).into(), (reader.u8()? as i8).into());
+    let outline = reader.u8()?.into();
+    let face = reader.null_terminated()?;
+
+    Ok(FontInfo { face, size, spacing, outline, padding: Some(padding) })
+}
+
+fn parse_binary_char(mut reader: BinaryReader) -> Result<CharData, Box<dyn Error>> {
+    let id = reader.u32()?;
+    let (x, y, width, height) = (reader.u16()?.into(), reader.u16()?.into(), reader.u16()?.into(), reader.u16()?.into());
+    let xoffset = reader.i16()?.into();
+    let yoffset = reader.i16()?.into();
+    let xadvance = reader.i16()?;
+    let xadvance = u32::try_from(xadvance)
+        .map_err(|_| format!("Error: char {} in the binary font has a negative xadvance ({})", id, xadvance))?;
+    let page = reader.u8()?.into();
+
+    Ok(CharData { id, x, y, width, height, xoffset, yoffset, xadvance, page, expansion: (0, 0, 0, 0) })
+}
+
+// Little-endian reads from one block of a binary .fnt, failing with the block's name instead of panicking when it is cut short
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    block: &'static str,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        if self.bytes.len() < len {
+            return Err(format!("Error: binary font {} is truncated", self.block).into());
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, Box<dyn Error>> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, Box<dyn Error>> {
+        Ok(self.u16()? as i16)
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn null_terminated(&mut self) -> Result<String, Box<dyn Error>> {
+        let len = self.bytes.iter().position(|byte| *byte == 0)
+            .ok_or(format!("Error: binary font {} has an unterminated string", self.block))?;
+        let text = std::str::from_utf8(self.take(len)?)?.to_string();
+        self.take(1)?;
+        Ok(text)
+    }
+}
+
+// Split a .fnt line into key=value fields, keeping quoted values (face="Some Font") in one piece
+fn parse_fields(line: &str) -> HashMap<&str, String> {
+    let mut fields = HashMap::new();
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let Some(eq) = rest[..token_end].find('=') else {
+            rest = rest[token_end..].trim_start();
+            continue;
+        };
+
+        let key = &rest[..eq];
+        let after_eq = &rest[eq + 1..];
+        let (value, remaining) = match after_eq.strip_prefix('"') {
+            Some(quoted) => {
+                let close = quoted.find('"').unwrap_or(quoted.len());
+                (quoted[..close].to_string(), quoted.get(close + 1..).unwrap_or(""))
+            }
+            None => {
+                let value_end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                (after_eq[..value_end].to_string(), &after_eq[value_end..])
+            }
+        };
+
+        fields.insert(key, value);
+        rest = remaining.trim_start();
+    }
+
+    fields
+}
+
+fn parse_info_line(line: &str) -> Result<FontInfo, Box<dyn Error>> {
+    let parts = parse_fields(line);
+
+    let face = parts.get("face").cloned().unwrap_or_default();
+    let size = match parts.get("size") {
+        Some(size) => size.parse()
+            .map_err(|e| format!("Error parsing size '{}' from line '{}': {}", size, line, e))?,
+        None => 0,
+    };
+
+    let spacing = match parts.get("spacing") {
+        Some(spacing) => {
+            let (horizontal, vertical) = spacing.split_once(',').unwrap_or((spacing, "0"));
+            let parse = |value: &str| value.parse::<i32>()
+                .map_err(|e| format!("Error parsing spacing '{}' from line '{}': {}", spacing, line, e));
+            (parse(horizontal)?, parse(vertical)?)
+        }
+        None => (0, 0),
+    };
+
+    let outline = match parts.get("outline") {
+        Some(outline) => outline.parse()
+            .map_err(|e| format!("Error parsing outline '{}' from line '{}': {}", outline, line, e))?,
+        None => 0,
+    };
+
+    let padding = match parts.get("padding") {
+        Some(padding) => {
+            let sides = padding.split(',')
+                .map(|side| side.trim().parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|e| format!("Error parsing padding '{}' from line '{}': {}", padding, line, e))?;
+            match sides[..] {
+                [up, right, down, left] => Some((up, right, down, left)),
+                _ => return Err(format!("Error parsing padding '{}' from line '{}': expected 4 values", padding, line).into()),
+            }
+        }
+        None => None,
+    };
+
+    Ok(FontInfo { face, size, spacing, outline, padding })
+}
+
+fn parse_common_line(line: &str) -> Result<FontMetrics, Box<dyn Error>> {
+    let parts = parse_fields(line);
+    let field = |key: &str, required: bool| match parts.get(key) {
+        Some(value) => value.parse::<u32>()
+            .map_err(|e| format!("Error parsing {} '{}' from line '{}': {}", key, value, line, e)),
+        None if required => Err(format!("Error: {} not found in line '{}'", key, line)),
+        None => Ok(0),
+    };
+
+    Ok(FontMetrics {
+        line_height: field("lineHeight", true)?,
+        base: field("base", true)?,
+        scale_w: field("scaleW", false)?,
+        scale_h: field("scaleH", false)?,
+    })
+}
+
+impl CharData {
+    // The rect including any expansion applied at load time, as (x, y, width, height)
+    fn source_rect(&self) -> (u32, u32, u32, u32) {
+        let (up, right, down, left) = self.expansion;
+        (self.x - left, self.y - up, self.width + left + right, self.height + up + down)
+    }
+
+    // Atlas columns the renderer copies for this glyph: the trim's columns cut off each side, but
+    // never more than the font's declared padding and never so much that nothing of the rect is left.
+    // Fonts that don't declare padding get the full trim
+    fn columns(&self, info: &FontInfo, trim: &GlyphTrim) -> (u32, u32) {
+        let (x, _, width, _) = self.source_rect();
+        let (left, right) = match info.padding {
+            Some((_, right, _, left)) => (left.min(trim.crop_left), right.min(trim.crop_right)),
+            None => (trim.crop_left, trim.crop_right),
+        };
+
+        if width > left + right {
+            (x.saturating_add(left), width - left - right)
+        } else {
+            (x, width)
+        }
+    }
+}
+
+// Gap left between repacked glyphs so they can't bleed into each other
+const REPACK_PADDING: u32 = 1;
+const REPACK_ATLAS_WIDTH: u32 = 256;
+
+// Repack only the glyphs used by `chars`, from whichever pages they are on, into one tight atlas
+// (simple shelf packing) and return the matching .fnt text along with it. Kerning pairs between retained characters are kept.
+pub fn trim_font(font: &Font, chars: &str, page_file: &str) -> (String, RgbaImage) {
+    let font_data = &font.glyphs;
+    let mut ids: Vec<u32> = chars.chars()
+        .filter(|ch| font.has(*ch))
+        .map(|ch| ch as u32)
+        .collect();
+
+    // layout derives the baseline from the largest yoffset in the font, so that glyph has to stay
+    if let Some(char_data) = font_data.values().max_by_key(|char_data| (char_data.yoffset, Reverse(char_data.id))) {
+        ids.push(char_data.id);
+    }
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut order = ids.clone();
+    // Copy expanded rects whole so the border pixels they pulled in survive the repack
+    let region_size = |id: &u32| {
+        let (_, _, width, height) = font_data[id].source_rect();
+        (width, height)
+    };
+    order.sort_by_key(|id| (Reverse(region_size(id).1), *id));
+
+    let widest = ids.iter().map(|id| region_size(id).0).max().unwrap_or(0);
+    let atlas_width = REPACK_ATLAS_WIDTH.max(widest);
+    let mut positions = HashMap::new();
+    let (mut shelf_x, mut shelf_y, mut shelf_height, mut used_width) = (0, 0, 0, 0);
+
+    for id in &order {
+        let (width, height) = region_size(id);
+        if shelf_x + width > atlas_width {
+            shelf_x = 0;
+            shelf_y += shelf_height + REPACK_PADDING;
+            shelf_height = 0;
+        }
+        positions.insert(*id, (shelf_x, shelf_y));
+        used_width = used_width.max(shelf_x + width);
+        shelf_x += width + REPACK_PADDING;
+        shelf_height = shelf_height.max(height);
+    }
+
+    let mut atlas = RgbaImage::new(used_width.max(1), (shelf_y + shelf_height).max(1));
+    let padding = font.info().padding
+        .map(|(up, right, down, left)| format!(" padding={},{},{},{}", up, right, down, left))
+        .unwrap_or_default();
+    let mut lines = vec![
+        format!(
+            "info face=\"{}\" size={} spacing={},{} outline={}{}",
+            font.info().face, font.info().size, font.info().spacing.0, font.info().spacing.1, font.info().outline, padding
+        ),
+        match font.metrics() {
+            Some(metrics) => format!(
+                "common lineHeight={} base={} scaleW={} scaleH={} pages=1 packed=0",
+                metrics.line_height, metrics.base, atlas.width(), atlas.height()
+            ),
+            None => format!("common scaleW={} scaleH={} pages=1 packed=0", atlas.width(), atlas.height()),
+        },
+        format!("page id=0 file=\"{}\"", page_file),
+        format!("chars count={}", ids.len()),
+    ];
+
+    for id in &ids {
+        let char_data = &font_data[id];
+        let (source_x, source_y, width, height) = char_data.source_rect();
+        let (region_x, region_y) = positions[id];
+        imageops::replace(&mut atlas, &font.pages()[char_data.page as usize].crop_imm(source_x, source_y, width, height), region_x.into(), region_y.into());
+
+        // The written rect is the original one, inside its copied region
+        let (up, _, _, left) = char_data.expansion;
+        let (x, y) = (region_x + left, region_y + up);
+
+        lines.push(format!(
+            "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page=0",
+            id, x, y, char_data.width, char_data.height, char_data.xoffset, char_data.yoffset, char_data.xadvance
+        ));
+    }
+
+    let mut kept_pairs: Vec<_> = font.kerning_pairs.iter()
+        .filter(|((first, second), _)| ids.binary_search(first).is_ok() && ids.binary_search(second).is_ok())
+        .collect();
+    kept_pairs.sort();
+
+    lines.push(format!("kernings count={}", kept_pairs.len()));
+    for ((first, second), amount) in kept_pairs {
+        lines.push(format!("kerning first={} second={} amount={}", first, second, amount));
+    }
+
+    (lines.join("\n") + "\n", atlas)
+}
+
+// Unicode blocks the coverage report groups glyphs into
+const UNICODE_BLOCKS: &[(&str, u32, u32)] = &[
+    ("Basic Latin", 0x0000, 0x007F),
+    ("Latin-1 Supplement", 0x0080, 0x00FF),
+    ("Latin Extended-A", 0x0100, 0x017F),
+    ("Latin Extended-B", 0x0180, 0x024F),
+    ("IPA Extensions", 0x0250, 0x02AF),
+    ("Greek and Coptic", 0x0370, 0x03FF),
+    ("Cyrillic", 0x0400, 0x04FF),
+    ("Hebrew", 0x0590, 0x05FF),
+    ("Arabic", 0x0600, 0x06FF),
+    ("Latin Extended Additional", 0x1E00, 0x1EFF),
+    ("General Punctuation", 0x2000, 0x206F),
+    ("Currency Symbols", 0x20A0, 0x20CF),
+    ("Letterlike Symbols", 0x2100, 0x214F),
+    ("Arrows", 0x2190, 0x21FF),
+    ("Mathematical Operators", 0x2200, 0x22FF),
+    ("Box Drawing", 0x2500, 0x257F),
+    ("Block Elements", 0x2580, 0x259F),
+    ("Geometric Shapes", 0x25A0, 0x25FF),
+    ("Miscellaneous Symbols", 0x2600, 0x26FF),
+    ("Hiragana", 0x3040, 0x309F),
+    ("Katakana", 0x30A0, 0x30FF),
+    ("CJK Unified Ideographs", 0x4E00, 0x9FFF),
+];
+
+// How many code points of one Unicode block the font has, as contiguous ranges
+pub struct BlockCoverage {
+    pub name: &'static str,
+    pub covered: u32,
+    pub total: u32,
+    pub ranges: Vec<(u32, u32)>,
+}
+
+// What a font covers and, given a sample text, which of its characters it lacks
+pub struct CoverageReport {
+    pub glyph_count: usize,
+    pub blocks: Vec<BlockCoverage>,
+    pub outside_blocks: u32,
+    pub missing: Vec<char>,
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} glyphs", self.glyph_count)?;
+        for block in &self.blocks {
+            let ranges: Vec<String> = block.ranges.iter()
+                .map(|(start, end)| if start == end { format!("U+{:04X}", start) } else { format!("U+{:04X}-U+{:04X}", start, end) })
+                .collect();
+            writeln!(f, "{}: {}/{} ({})", block.name, block.covered, block.total, ranges.join(", "))?;
+        }
+        if self.outside_blocks > 0 {
+            writeln!(f, "Other: {}", self.outside_blocks)?;
+        }
+        if !self.missing.is_empty() {
+            let missing: Vec<String> = self.missing.iter().map(|ch| format!("'{}' (U+{:04X})", ch, *ch as u32)).collect();
+            writeln!(f, "Missing from sample: {}", missing.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// Group the font's glyphs by Unicode block and list the sample's characters that have no glyph
+pub fn coverage_report(font: &Font, sample: Option<&str>) -> CoverageReport {
+    let ids: Vec<u32> = font.chars().map(|ch| ch as u32).collect();
+
+    let mut blocks = Vec::new();
+    let mut in_blocks = 0;
+    for (name, first, last) in UNICODE_BLOCKS {
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for id in ids.iter().copied().filter(|id| (*first..=*last).contains(id)) {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == id => *end = id,
+                _ => ranges.push((id, id)),
+            }
+        }
+        if !ranges.is_empty() {
+            let covered = ranges.iter().map(|(start, end)| end - start + 1).sum();
+            in_blocks += covered;
+            blocks.push(BlockCoverage { name, covered, total: last - first + 1, ranges });
+        }
+    }
+
+    let mut missing = Vec::new();
+    for ch in sample.unwrap_or("").chars().filter(|ch| !ch.is_control()) {
+        if !font.has(ch) && !missing.contains(&ch) {
+            missing.push(ch);
+        }
+    }
+
+    CoverageReport { glyph_count: ids.len(), blocks, outside_blocks: ids.len() as u32 - in_blocks, missing }
+}
+
+// Overview of a loaded font for font listings: the info line, counts, atlas size and coverage
+pub struct FontSummary {
+    pub face: String,
+    pub size: i32,
+    pub glyph_count: usize,
+    pub kerning_pair_count: usize,
+    pub metrics: Option<FontMetrics>,
+    pub atlas_size: (u32, u32),
+    pub page_count: usize,
+    pub coverage: CoverageReport,
+    pub attribution: FontAttribution,
+}
+
+impl FontSummary {
+    // Covered/total count of every Unicode block the font has glyphs in, on one line
+    pub fn block_counts(&self) -> String {
+        let blocks: Vec<String> = self.coverage.blocks.iter()
+            .map(|block| format!("{} {}/{}", block.name, block.covered, block.total))
+            .collect();
+        blocks.join(", ")
+    }
+}
+
+impl fmt::Display for FontSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Face: {}", self.face)?;
+        writeln!(f, "Size: {}", self.size)?;
+        writeln!(f, "Glyphs: {}", self.glyph_count)?;
+        writeln!(f, "Kerning pairs: {}", self.kerning_pair_count)?;
+        if let Some(metrics) = &self.metrics {
+            writeln!(f, "Line height: {} (base {})", metrics.line_height, metrics.base)?;
+        }
+        writeln!(f, "Atlas: {} x {} ({} page{})", self.atlas_size.0, self.atlas_size.1, self.page_count, if self.page_count == 1 { "" } else { "s" })?;
+        for (label, value) in [("Author", &self.attribution.author), ("License", &self.attribution.license), ("URL", &self.attribution.url)] {
+            if let Some(value) = value {
+                writeln!(f, "{}: {}", label, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn font_summary(font: &Font) -> FontSummary {
+    FontSummary {
+        face: font.info.face.clone(),
+        size: font.info.size,
+        glyph_count: font.glyphs.len(),
+        kerning_pair_count: font.kerning_pairs.len(),
+        metrics: font.metrics,
+        atlas_size: font.pages[0].dimensions(),
+        page_count: font.pages.len(),
+        coverage: coverage_report(font, None),
+        attribution: font.attribution.clone(),
+    }
+}
+
+// Text of a credits.txt for everything drawn with these fonts, one block per font in order. Fonts
+// without any attribution are left out, and one credited twice is listed once
+pub fn credits_text(attributions: &[&FontAttribution]) -> String {
+    let mut credited: Vec<&FontAttribution> = Vec::new();
+    for attribution in attributions.iter().filter(|attribution| !attribution.is_empty()) {
+        if !credited.contains(attribution) {
+            credited.push(attribution);
+        }
+    }
+
+    let blocks: Vec<String> = credited.iter()
+        .map(|attribution| {
+            let mut block = attribution.name.clone().unwrap_or("Unnamed font".to_string());
+            for (label, value) in [("Author", &attribution.author), ("License", &attribution.license), ("URL", &attribution.url)] {
+                if let Some(value) = value {
+                    block.push_str(&format!("\n  {}: {}", label, value));
+                }
+            }
+            block
+        })
+        .collect();
+    format!("Fonts\n\n{}\n", blocks.join("\n\n"))
+}
+
+fn parse_char_line(line: &str) -> Result<CharData, Box<dyn Error>> {
+    let parts = parse_fields(line);
+
+    let id = parts.get("id")
+        .ok_or("Error: ID not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing ID '{}' from line '{}': {}", parts.get("id").unwrap(), line, e))?;
+
+    let x = parts.get("x")
+        .ok_or("Error: X coordinate not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing X coordinate '{}' from line '{}': {}", parts.get("x").unwrap(), line, e))?;
+
+    let y = parts.get("y")
+        .ok_or("Error: Y coordinate not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing Y coordinate '{}' from line '{}': {}", parts.get("y").unwrap(), line, e))?;
+
+    let width = parts.get("width")
+        .ok_or("Error: Width not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing width '{}' from line '{}': {}", parts.get("width").unwrap(), line, e))?;
+
+    let height = parts.get("height")
+        .ok_or("Error: Height not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing height '{}' from line '{}': {}", parts.get("height").unwrap(), line, e))?;
+
+    // Not every exporter writes xoffset; 0 leaves the glyph at the pen position
+    let xoffset = match parts.get("xoffset") {
+        Some(value) => value.parse()
+            .map_err(|e| format!("Error parsing X offset '{}' from line '{}': {}", value, line, e))?,
+        None => 0,
+    };
+
+    let yoffset = parts.get("yoffset")
+        .ok_or("Error: Y offset not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing Y offset '{}' from line '{}': {}", parts.get("yoffset").unwrap(), line, e))?;
+
+    let xadvance = parts.get("xadvance")
+        .ok_or("Error: Xadvance not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing Xadvance '{}' from line '{}': {}", parts.get("xadvance").unwrap(), line, e))?;
+
+    let page = match parts.get("page") {
+        Some(value) => value.parse()
+            .map_err(|e| format!("Error parsing page '{}' from line '{}': {}", value, line, e))?,
+        None => 0,
+    };
+
+    Ok(CharData { id, x, y, width, height, xoffset, yoffset, xadvance, page, expansion: (0, 0, 0, 0) })
+}
+
+// The count on a "chars count=N" or "kernings count=N" line. Only used to report what a lenient load
+// recovered, so callers ignore a malformed one
+fn parse_count_line(line: &str) -> Result<usize, Box<dyn Error>> {
+    let parts = parse_fields(line);
+
+    let count = parts.get("count")
+        .ok_or("Error: Count not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing count '{}' from line '{}': {}", parts.get("count").unwrap(), line, e))?;
+
+    Ok(count)
+}
+
+fn parse_page_line(line: &str) -> Result<(u32, String), Box<dyn Error>> {
+    let parts = parse_fields(line);
+
+    let id = parts.get("id")
+        .ok_or("Error: Page ID not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing page ID '{}' from line '{}': {}", parts.get("id").unwrap(), line, e))?;
+    let file = parts.get("file").ok_or("Error: Page file not found")?.clone();
+
+    Ok((id, file))
+}
+
+fn parse_kerning_line(line: &str) -> Result<(u32, u32, i32), Box<dyn Error>> {
+    let parts = parse_fields(line);
+
+    let first = parts.get("first")
+        .ok_or("Error: First not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing First '{}' from line '{}': {}", parts.get("first").unwrap(), line, e))?;
+
+    let second = parts.get("second")
+        .ok_or("Error: Second not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing Second '{}' from line '{}': {}", parts.get("second").unwrap(), line, e))?;
+
+    let amount = parts.get("amount")
+        .ok_or("Error: Amount not found")?
+        .parse()
+        .map_err(|e| format!("Error parsing Amount '{}' from line '{}': {}", parts.get("amount").unwrap(), line, e))?;
+
+    Ok((first, second, amount))
+}
+
+// Zero-width and bidi format characters that are invisible but still get looked up as glyphs
+const INVISIBLE_FORMAT_CHARS: &[char] = &[
+    '\u{061C}', // Arabic letter mark
+    '\u{200B}', '\u{200C}', '\u{200D}', // Zero-width space, non-joiner, joiner
+    '\u{200E}', '\u{200F}', // Left-to-right and right-to-left marks
+    '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', // Bidi embeddings and overrides
+    '\u{2060}', // Word joiner
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}', // Bidi isolates
+    '\u{FEFF}', // Byte order mark / zero-width no-break space
+];
+
+// Strip C0/C1 control characters (except newline and tab) plus zero-width and bidi format characters.
+// Returns the cleaned text and a description of every kind of character that was removed
+pub fn sanitize_text(text: &str) -> (String, Vec<String>) {
+    let mut removed: Vec<(char, usize)> = Vec::new();
+    let sanitized = text.chars()
+        .filter(|ch| {
+            let strip = (ch.is_control() && *ch != '\n' && *ch != '\t') || INVISIBLE_FORMAT_CHARS.contains(ch);
+            if strip {
+                match removed.iter_mut().find(|(removed_ch, _)| removed_ch == ch) {
+                    Some((_, count)) => *count += 1,
+                    None => removed.push((*ch, 1)),
+                }
+            }
+            !strip
+        })
+        .collect();
+
+    let report = removed.iter()
+        .map(|(ch, count)| format!("Removed {} x U+{:04X}", count, *ch as u32))
+        .collect();
+
+    (sanitized, report)
+}
+
+const FORMATTING_CODE_PREFIX: char = '§';
+
+// The game's 16 text colors, for the codes §0 to §9 and §a to §f
+const FORMATTING_COLORS: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00], [0x00, 0x00, 0xAA], [0x00, 0xAA, 0x00], [0x00, 0xAA, 0xAA],
+    [0xAA, 0x00, 0x00], [0xAA, 0x00, 0xAA], [0xFF, 0xAA, 0x00], [0xAA, 0xAA, 0xAA],
+    [0x55, 0x55, 0x55], [0x55, 0x55, 0xFF], [0x55, 0xFF, 0x55], [0x55, 0xFF, 0xFF],
+    [0xFF, 0x55, 0x55], [0xFF, 0x55, 0xFF], [0xFF, 0xFF, 0x55], [0xFF, 0xFF, 0xFF],
+];
+
+// How a character is drawn once the § codes before it are applied
+#[derive(Clone, Copy)]
+struct GlyphStyle {
+    bold: bool,
+    tint: Rgba<u8>,
+}
+
+// The characters a text draws, each with its style. § codes are read the way the game reads them,
+// in either case: a color code sets the color, keeping the tint's alpha, and ends a §l; §l starts
+// bold and §r goes back to the options' style. Other codes, including the game's obfuscated,
+// strikethrough, underline and italic ones, and a § at the very end are dropped without effect
+fn styled_chars(text: &str, options: &RenderOptions) -> Vec<(usize, char, GlyphStyle)> {
+    let base = GlyphStyle { bold: options.bold, tint: options.tint };
+    let mut style = base;
+    let mut styled = Vec::with_capacity(text.len());
+    let mut chars = text.chars().enumerate();
+
+    while let Some((index, ch)) = chars.next() {
+        if ch != FORMATTING_CODE_PREFIX {
+            styled.push((index, ch, style));
+            continue;
+        }
+        match chars.next().map(|(_, code)| code.to_ascii_lowercase()) {
+            Some(code @ ('0'..='9' | 'a'..='f')) => {
+                let [red, green, blue] = FORMATTING_COLORS[code.to_digit(16).unwrap_or(15) as usize];
+                style = GlyphStyle { bold: base.bold, tint: Rgba([red, green, blue, options.tint[3]]) };
+            }
+            Some('l') => style.bold = true,
+            Some('r') => style = base,
+            _ => {}
+        }
+    }
+
+    styled
+}
+
+// The text without its § codes: the characters layout actually draws
+pub fn strip_formatting_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == FORMATTING_CODE_PREFIX {
+            chars.next();
+        } else {
+            stripped.push(ch);
+        }
+    }
+    stripped
+}
+
+// The § codes that bring back the color and bold in effect at the end of `text`, for starting the
+// next line of a split or wrapped text in the same style. Empty when the style is the options' own
+pub fn formatting_codes_at_end(text: &str) -> String {
+    let (mut color, mut bold) = (None, false);
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != FORMATTING_CODE_PREFIX {
+            continue;
+        }
+        match chars.next().map(|code| code.to_ascii_lowercase()) {
+            Some(code @ ('0'..='9' | 'a'..='f')) => (color, bold) = (Some(code), false),
+            Some('l') => bold = true,
+            Some('r') => (color, bold) = (None, false),
+            _ => {}
+        }
+    }
+
+    let mut codes: String = color.map(|code| format!("{}{}", FORMATTING_CODE_PREFIX, code)).unwrap_or_default();
+    if bold {
+        codes.push(FORMATTING_CODE_PREFIX);
+        codes.push('l');
+    }
+    codes
+}
+
+// Where a glyph is cropped from the font atlas and where it lands on the text canvas
+pub struct GlyphPlacement {
+    pub char_id: u32,
+    pub crop_x: u32,
+    pub crop_y: u32,
+    pub crop_width: u32,
+    pub crop_height: u32,
+    pub page: u32,
+    pub x: i64,
+    pub y: i64,
+    // Pen position the glyph hangs from, before its xoffset and crop expansion move the rect
+    pub pen_x: i64,
+    // How far the pen moves after this glyph, and how far kerning moved it just before
+    pub advance: u32,
+    pub kerning: i64,
+    // Whether rasterizing adds the bold copy, whose extra column the advance already includes, and
+    // the color multiplied into the glyph's pixels. Both come from the options and any § codes
+    pub bold: bool,
+    pub tint: Rgba<u8>,
+    pub source: GlyphSource,
+}
+
+// Where a placed glyph came from, so a render that mixes the font's own glyphs with stand-ins can be
+// checked glyph by glyph. The default is a glyph the font has for the character the text asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlyphSource {
+    // The character the text had before the disallowed policy swapped this one in
+    pub substituted_from: Option<char>,
+    // The font has no glyph for the character, so its missing glyph is drawn in its place
+    pub missing_glyph: bool,
+}
+
+impl GlyphSource {
+    pub fn is_font_glyph(&self) -> bool {
+        *self == GlyphSource::default()
+    }
+}
+
+// How many glyphs did not come straight from the font, such as "2 glyphs drawn as the missing glyph,
+// 1 substituted", or None when they all did
+pub fn glyph_source_summary<'a>(glyphs: impl IntoIterator<Item = &'a GlyphPlacement>) -> Option<String> {
+    let (mut missing, mut substituted) = (0, 0);
+    for glyph in glyphs {
+        missing += glyph.source.missing_glyph as usize;
+        substituted += glyph.source.substituted_from.is_some() as usize;
+    }
+
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+    let mut parts = Vec::new();
+    if missing > 0 {
+        parts.push(format!("{} glyph{} drawn as the missing glyph", missing, plural(missing)));
+    }
+    if substituted > 0 {
+        parts.push(format!("{} substituted", substituted));
+    }
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join(", ")),
+    }
+}
+
+// Output of the layout step: canvas size, baseline row and the placement of every glyph
+pub struct TextLayout {
+    pub width: u32,
+    pub height: u32,
+    // Columns up to the right edge of the rightmost glyph; less than width when the pen ends past it
+    pub ink_width: u32,
+    pub baseline: i32,
+    pub glyphs: Vec<GlyphPlacement>,
+    // Characters of the text the font has no glyph for, in order of first appearance
+    pub missing: Vec<char>,
+    // Whether rasterizing adds the drop shadow and the outline; the layout already made room for them
+    pub shadow: bool,
+    pub outline: Option<Outline>,
+    pub gradient: Option<(Rgba<u8>, Rgba<u8>)>,
+    // Characters outside the allowed set that the disallowed policy swapped for others
+    pub replaced: Vec<CharReplacement>,
+}
+
+// Characters a font is allowed to draw, for fonts where only some of the glyphs are styled properly.
+// Spaces are always allowed
+#[derive(Debug, Clone)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    // Parse a list of characters and ranges such as "A-Z0-9!?.-". A '-' at either end means itself
+    pub fn parse(spec: &str) -> Result<CharSet, Box<dyn Error>> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut ranges = Vec::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+            match chars.get(index + 1..index + 3) {
+                Some(&['-', last]) => {
+                    let first = chars[index];
+                    if last < first {
+                        return Err(format!("Error: the range {}-{} in the allowed characters runs backwards", first, last).into());
+                    }
+                    ranges.push((first, last));
+                    index += 3;
+                }
+                _ => {
+                    ranges.push((chars[index], chars[index]));
+                    index += 1;
+                }
+            }
+        }
+
+        Ok(CharSet { ranges })
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        ch == ' ' || self.ranges.iter().any(|(first, last)| (*first..=*last).contains(&ch))
+    }
+}
+
+// What layout does with a character outside the allowed set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisallowedPolicy {
+    // Fail, naming every such character and where it is
+    #[default]
+    Fail,
+    // Use the uppercase letter when that is allowed, and fail otherwise
+    Uppercase,
+    // Use the uppercase letter when that is allowed, and this character otherwise
+    Replace(char),
+}
+
+// A character of the text, counted from 0, and what the policy replaced it with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharReplacement {
+    pub index: usize,
+    pub ch: char,
+    pub replacement: Option<char>,
+}
+
+impl fmt::Display for CharReplacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' (U+{:04X}) at character {}", self.ch, self.ch as u32, self.index + 1)?;
+        match self.replacement {
+            Some(replacement) => write!(f, " became '{}'", replacement),
+            None => Ok(()),
+        }
+    }
+}
+
+// The text with every character outside `allowed` handled by `policy`, and what was replaced
+fn restrict_text(text: &str, allowed: &CharSet, policy: DisallowedPolicy) -> Result<(String, Vec<CharReplacement>), RenderError> {
+    let mut restricted = String::with_capacity(text.len());
+    let mut replaced = Vec::new();
+    let mut rejected = Vec::new();
+
+    let mut in_code = false;
+    for (index, ch) in text.chars().enumerate() {
+        // § codes are never drawn, so they pass whatever the set; layout applies and drops them
+        let is_code = in_code || ch == FORMATTING_CODE_PREFIX;
+        in_code = !in_code && ch == FORMATTING_CODE_PREFIX;
+        if is_code || allowed.contains(ch) {
+            restricted.push(ch);
+            continue;
+        }
+
+        let mut uppercase = ch.to_uppercase();
+        let replacement = match (uppercase.next(), uppercase.next(), policy) {
+            (Some(upper), None, DisallowedPolicy::Uppercase | DisallowedPolicy::Replace(_)) if allowed.contains(upper) => Some(upper),
+            (_, _, DisallowedPolicy::Replace(replacement)) => Some(replacement),
+            _ => None,
+        };
+        match replacement {
+            Some(replacement) => {
+                restricted.push(replacement);
+                replaced.push(CharReplacement { index, ch, replacement: Some(replacement) });
+            }
+            None => rejected.push(CharReplacement { index, ch, replacement: None }),
+        }
+    }
+
+    match rejected.is_empty() {
+        true => Ok((restricted, replaced)),
+        false => Err(RenderError::DisallowedChars(rejected)),
+    }
+}
+
+// How many pixels of a glyph, counting what its effects draw around it, fall outside the canvas on
+// each side
+#[derive(Debug, Clone)]
+pub struct GlyphClip {
+    pub char_id: u32,
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl fmt::Display for GlyphClip {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sides: Vec<String> = [("left", self.left), ("right", self.right), ("top", self.top), ("bottom", self.bottom)]
+            .iter()
+            .filter(|(_, lost)| *lost > 0)
+            .map(|(side, lost)| format!("{} px on the {}", lost, side))
+            .collect();
+        let ch = char::from_u32(self.char_id).unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        write!(f, "'{}' loses {}", ch, sides.join(", "))
+    }
+}
+
+// The images render_text stacks together, listed from bottom to top
+pub struct RenderLayers {
+    pub guides: RgbaImage,
+    pub baseline: RgbaImage,
+    pub text: RgbaImage,
+    pub timings: RenderTimings,
+}
+
+impl RenderLayers {
+    // Size of the stacked image. Below 1x the guides are shorter than the text, so the taller layer wins
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.guides.width().max(self.text.width()), self.guides.height().max(self.text.height()))
+    }
+}
+
+// Wall-clock time spent in each step of producing a texture. render_layers fills in the steps it runs;
+// loading the font, the background and saving are up to the caller
+#[derive(Debug, Clone, Default)]
+pub struct RenderTimings {
+    pub font_load: Duration,
+    pub layout: Duration,
+    pub glyph_blit: Duration,
+    pub guides: Duration,
+    pub background: Duration,
+    pub encode: Duration,
+    pub write: Duration,
+}
+
+impl RenderTimings {
+    // Run `step`, adding the time it took to `phase`
+    pub fn time<T>(phase: &mut Duration, step: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = step();
+        *phase += started.elapsed();
+        result
+    }
+}
+
+impl fmt::Display for RenderTimings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let phases = [
+            ("font load", self.font_load),
+            ("layout", self.layout),
+            ("glyph blit", self.glyph_blit),
+            ("guides", self.guides),
+            ("background", self.background),
+            ("encode", self.encode),
+            ("write", self.write),
+        ];
+        for (name, duration) in phases {
+            writeln!(f, "{:<12}{:>9.3} ms", name, duration.as_secs_f64() * 1000.0)?;
+        }
+        Ok(())
+    }
+}
+
+// Errors from the render steps that callers may want to tell apart
+#[derive(Debug, Clone)]
+pub enum RenderError {
+    // The measured output can't be represented as an image buffer
+    OutputTooLarge { width: u64, height: u64 },
+    // Empty or whitespace-only text, or text the font has no glyphs for, would give a blank texture
+    NothingToRender,
+    // Characters outside the allowed set that the disallowed policy could not replace
+    DisallowedChars(Vec<CharReplacement>),
+    // Glyphs that would be cut off at the canvas edges, with strict_clipping
+    GlyphsClipped(Vec<GlyphClip>),
+    // A background image with no pixels, which can't be tiled
+    EmptyTile,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::OutputTooLarge { width, height } => {
+                write!(f, "output would be {} x {} px, which is too large to allocate", thousands(*width), thousands(*height))
+            }
+            RenderError::NothingToRender => write!(f, "the text has no characters the font can draw"),
+            RenderError::DisallowedChars(chars) => {
+                let listed: Vec<String> = chars.iter().map(|ch| ch.to_string()).collect();
+                write!(f, "the text uses characters outside the allowed set: {}", listed.join(", "))
+            }
+            RenderError::GlyphsClipped(clips) => {
+                let listed: Vec<String> = clips.iter().map(|clip| clip.to_string()).collect();
+                write!(f, "glyphs would be clipped at the canvas edge: {}", listed.join(", "))
+            }
+            RenderError::EmptyTile => write!(f, "the background image has no pixels to tile"),
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+// Where randomized effects get their seed. Every effect that picks anything at random asks the
+// render's SeedSource for a generator, so one setting decides whether renders repeat.
+// FromText seeds from the 64-bit FNV-1a hash of the text's UTF-8 bytes (offset basis
+// 0xcbf29ce484222325, prime 0x100000001b3). The same title then looks the same on every machine
+// and run. That hash is part of the output and must not change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeedSource {
+    #[default]
+    FromText,
+    Fixed(u64),
+    // A new seed for every render
+    Random,
+}
+
+impl SeedSource {
+    pub fn seed(self, text: &str) -> u64 {
+        match self {
+            SeedSource::FromText => text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3)),
+            SeedSource::Fixed(seed) => seed,
+            SeedSource::Random => RandomState::new().build_hasher().finish(),
+        }
+    }
+
+    pub fn rng(self, text: &str) -> EffectRng {
+        EffectRng { state: self.seed(text) }
+    }
+}
+
+// SplitMix64: small, fast and the same on every platform, which is all effects need
+pub struct EffectRng {
+    state: u64,
+}
+
+impl EffectRng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..bound, or 0 when bound is 0
+    pub fn below(&mut self, bound: u32) -> u32 {
+        match bound {
+            0 => 0,
+            _ => (((self.next_u64() >> 32) * bound as u64) >> 32) as u32,
+        }
+    }
+}
+
+// Safety limits checked after measuring and before any large image is allocated
+#[derive(Debug, Clone)]
+pub struct RenderLimits {
+    pub max_chars: usize,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub max_pixels: u64,
+}
+
+impl Default for RenderLimits {
+    fn default() -> Self {
+        RenderLimits { max_chars: 4096, max_width: 16_384, max_height: 16_384, max_pixels: 16_777_216 }
+    }
+}
+
+impl RenderLimits {
+    pub fn check_text(&self, text: &str) -> Result<(), Box<dyn Error>> {
+        let char_count = text.chars().count();
+        if char_count > self.max_chars {
+            return Err(format!("text has {} characters; limit is {}", thousands(char_count as u64), thousands(self.max_chars as u64)).into());
+        }
+        Ok(())
+    }
+
+    pub fn check_canvas(&self, width: u32, height: u32) -> Result<(), Box<dyn Error>> {
+        if width > self.max_width {
+            return Err(format!("output would be {} px wide; limit is {}", thousands(width.into()), thousands(self.max_width.into())).into());
+        }
+        if height > self.max_height {
+            return Err(format!("output would be {} px high; limit is {}", thousands(height.into()), thousands(self.max_height.into())).into());
+        }
+        let pixels = width as u64 * height as u64;
+        if pixels > self.max_pixels {
+            return Err(format!("output would have {} pixels; limit is {}", thousands(pixels), thousands(self.max_pixels)).into());
+        }
+        Ok(())
+    }
+}
+
+// 412000 -> "412,000"
+fn thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+const BASELINE_COLOR: Rgba<u8> = Rgba([255, 0, 0, 255]); // Red color for baseline
+const HIGHLIGHT_COLOR: Rgba<u8> = Rgba([0, 255, 0, 128]); // 50% transparent green for highlight
+const UPPER_BAND_COLOR: Rgba<u8> = Rgba([128, 0, 128, 255]); // Purple, drawn with the highlight's alpha
+const LOWER_BAND_COLOR: Rgba<u8> = Rgba([0, 255, 255, 255]); // Cyan, drawn with the highlight's alpha
+const GUIDE_OUTLINE_COLOR: Rgba<u8> = Rgba([0, 255, 0, 255]);
+
+// How glyphs are spaced. Legacy is the tool's long-standing spacing: glyph rects trimmed and advances
+// shortened by the font's GlyphTrim. Vanilla follows the game's font renderer instead, so a texture
+// lines up with text drawn in game at GUI scale 1: every advance is the font's xadvance exactly,
+// glyph rects are used whole and the space is as wide as its own glyph. Exported spaces glyphs the
+// way the .fnt was exported, like Vanilla plus the info line's horizontal spacing between characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutProfile {
+    #[default]
+    Legacy,
+    Vanilla,
+    Exported,
+}
+
+impl LayoutProfile {
+    fn glyph_trim(self, font: &Font) -> GlyphTrim {
+        match self {
+            LayoutProfile::Legacy => font.glyph_trim,
+            LayoutProfile::Vanilla | LayoutProfile::Exported => GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 },
+        }
+    }
+
+    // Pixels between characters when the options give no letter spacing. Only Exported takes the .fnt's
+    // spacing: Legacy's advance trim was tuned on the embedded font, spacing=-2,-2 and all, and the game
+    // Vanilla follows knows nothing of it
+    fn default_tracking(self, font: &Font) -> i32 {
+        match self {
+            LayoutProfile::Legacy | LayoutProfile::Vanilla => 0,
+            LayoutProfile::Exported => font.info().spacing.0,
+        }
+    }
+}
+
+pub const MAX_OUTLINE_THICKNESS: u32 = 3;
+
+// Color and thickness of the outline. Every pixel within `thickness` columns and rows of the ink
+// gets the color, so corners come out square like the font's pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outline {
+    pub color: Rgba<u8>,
+    thickness: u32,
+}
+
+impl Outline {
+    // Thicknesses outside 1 to MAX_OUTLINE_THICKNESS are clamped into that range
+    pub fn new(color: Rgba<u8>, thickness: u32) -> Self {
+        Outline { color, thickness: thickness.clamp(1, MAX_OUTLINE_THICKNESS) }
+    }
+
+    pub fn thickness(&self) -> u32 {
+        self.thickness
+    }
+}
+
+// Everything a render needs besides the font and the text. Start from the defaults, which match the
+// window's initial settings, and chain the setters: RenderOptions::default().kerning(true).scale(2.0)
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub use_kerning: bool,
+    // Fraction of a pair's kerning still applied when a single space separates it
+    pub kern_across_spaces: Option<f32>,
+    // Scale of the render: the text, the baseline and the guides are scaled together, nearest neighbor,
+    // so the bands line up with the glyphs and the output is as large as the scaled text
+    pub scale_factor: f32,
+    // Scale the UV guides alone instead, at most 32 px tall, over text left at the font's size. This is
+    // the output of older versions, whose bands don't line up with the glyphs
+    pub legacy_guide_scale: bool,
+    pub guides_mirrored: bool,
+    // Which UV helper the guide layer shows
+    pub guides: GuideTemplate,
+    // Extra pixels between neighboring characters, on top of their advances and kerning. Negative values
+    // tighten the text, but never so far that a character doesn't move the pen at least 1 px. None
+    // leaves it to the layout profile, see LayoutProfile::default_tracking
+    pub letter_spacing: Option<i32>,
+    // The red debug line at the baseline row, in its own layer. Off unless asked for, since it would
+    // end up in the shipped texture
+    pub draw_baseline: bool,
+    // Empty rows above and below the line
+    pub vertical_padding: u32,
+    pub layout_profile: LayoutProfile,
+    // Like the game: every glyph is drawn a second time one pixel to the right and advances one pixel further
+    pub bold: bool,
+    // Like the game: a quarter-brightness copy of the text one pixel down and to the right, beneath it
+    pub shadow: bool,
+    // Multiplied into every glyph pixel, alpha included, so white text comes out in this color with
+    // its antialiased edges intact. Pure white changes nothing
+    pub tint: Rgba<u8>,
+    // A ring around the glyphs' ink, beneath the text, for titles on busy backgrounds. The canvas
+    // grows by its thickness on every side
+    pub outline: Option<Outline>,
+    // Replaces the color of the glyph pixels, keeping their alpha, with a left to right blend from the
+    // first color at the leftmost inked column to the second at the rightmost. Overrides the tint and
+    // any § color codes, and the shadow darkens the blend instead of the glyph colors
+    pub gradient: Option<(Rgba<u8>, Rgba<u8>)>,
+    // Seed of every randomized effect
+    pub seed: SeedSource,
+    // When set, characters outside it are handled by disallowed_policy even if the font has them
+    pub allowed_chars: Option<CharSet>,
+    pub disallowed_policy: DisallowedPolicy,
+    pub limits: RenderLimits,
+    // Fail the render when a glyph would be clipped at the canvas edge, instead of leaving callers to
+    // check clipped_glyphs and warn
+    pub strict_clipping: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            use_kerning: false,
+            kern_across_spaces: None,
+            scale_factor: 1.5,
+            legacy_guide_scale: false,
+            guides_mirrored: false,
+            guides: GuideTemplate::current_bands(),
+            letter_spacing: None,
+            draw_baseline: false,
+            vertical_padding: 5,
+            layout_profile: LayoutProfile::Legacy,
+            bold: false,
+            shadow: false,
+            tint: Rgba([255, 255, 255, 255]),
+            outline: None,
+            gradient: None,
+            seed: SeedSource::FromText,
+            allowed_chars: None,
+            disallowed_policy: DisallowedPolicy::Fail,
+            limits: RenderLimits::default(),
+            strict_clipping: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    pub fn kerning(mut self, use_kerning: bool) -> Self {
+        self.use_kerning = use_kerning;
+        self
+    }
+
+    pub fn kern_across_spaces(mut self, fraction: Option<f32>) -> Self {
+        self.kern_across_spaces = fraction;
+        self
+    }
+
+    pub fn scale(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    pub fn legacy_guide_scale(mut self, legacy_guide_scale: bool) -> Self {
+        self.legacy_guide_scale = legacy_guide_scale;
+        self
+    }
+
+    pub fn mirrored_guides(mut self, guides_mirrored: bool) -> Self {
+        self.guides_mirrored = guides_mirrored;
+        self
+    }
+
+    pub fn guides(mut self, template: GuideTemplate) -> Self {
+        self.guides = template;
+        self
+    }
+
+    pub fn letter_spacing(mut self, pixels: Option<i32>) -> Self {
+        self.letter_spacing = pixels;
+        self
+    }
+
+    pub fn draw_baseline(mut self, draw_baseline: bool) -> Self {
+        self.draw_baseline = draw_baseline;
+        self
+    }
+
+    pub fn vertical_padding(mut self, rows: u32) -> Self {
+        self.vertical_padding = rows;
+        self
+    }
+
+    pub fn layout_profile(mut self, layout_profile: LayoutProfile) -> Self {
+        self.layout_profile = layout_profile;
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    pub fn shadow(mut self, shadow: bool) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    pub fn tint(mut self, tint: Rgba<u8>) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn outline(mut self, outline: Option<Outline>) -> Self {
+        self.outline = outline;
+        self
+    }
+
+    pub fn gradient(mut self, gradient: Option<(Rgba<u8>, Rgba<u8>)>) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn seed(mut self, seed: SeedSource) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn allowed_chars(mut self, allowed_chars: Option<CharSet>, policy: DisallowedPolicy) -> Self {
+        self.allowed_chars = allowed_chars;
+        self.disallowed_policy = policy;
+        self
+    }
+
+    pub fn limits(mut self, limits: RenderLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn strict_clipping(mut self, strict_clipping: bool) -> Self {
+        self.strict_clipping = strict_clipping;
+        self
+    }
+}
+
+// Kept for existing callers; render_text_with takes the same settings as RenderOptions
+pub fn render_text(
+    font: &Font,
+    text: &str,
+    use_kerning: bool,
+    kern_across_spaces: Option<f32>,
+    scale_factor: f32,
+    guides_mirrored: bool,
+    limits: &RenderLimits,
+) -> Result<RgbaImage, Box<dyn Error>> {
+    let options = RenderOptions::default()
+        .kerning(use_kerning)
+        .kern_across_spaces(kern_across_spaces)
+        .scale(scale_factor)
+        .mirrored_guides(guides_mirrored)
+        .limits(limits.clone());
+
+    render_text_with(font, text, &options)
+}
+
+pub fn render_text_with(font: &Font, text: &str, options: &RenderOptions) -> Result<RgbaImage, Box<dyn Error>> {
+    let layers = render_layers_with(font, text, options)?;
+    let (width, height) = layers.dimensions();
+
+    Ok(compose(width, height, &[&layers.guides, &layers.baseline, &layers.text]))
+}
+
+// What render_text_into drew: the full size of the rendered text and how many of its columns and rows
+// fell outside the target on each side
+#[derive(Debug, Clone)]
+pub struct RenderInfo {
+    pub width: u32,
+    pub height: u32,
+    pub clipped_left: u32,
+    pub clipped_right: u32,
+    pub clipped_top: u32,
+    pub clipped_bottom: u32,
+    pub timings: RenderTimings,
+}
+
+// Like render_text, but stacks the layers straight onto an existing image with the top-left corner at
+// (dest_x, dest_y) instead of allocating a separate output image. Anything that falls outside the
+// target is clipped and reported in the returned info
+pub fn render_text_into(
+    font: &Font,
+    text: &str,
+    options: &RenderOptions,
+    target: &mut RgbaImage,
+    dest_x: i64,
+    dest_y: i64,
+) -> Result<RenderInfo, Box<dyn Error>> {
+    let layers = render_layers_with(font, text, options)?;
+    let (width, height) = layers.dimensions();
+    compose_into(target, dest_x, dest_y, &[&layers.guides, &layers.baseline, &layers.text]);
+
+    let clip = |before: i64, size: u32, limit: u32| {
+        let size = size as i64;
+        ((-before).clamp(0, size) as u32, (before + size - limit as i64).clamp(0, size) as u32)
+    };
+    let (clipped_left, clipped_right) = clip(dest_x, width, target.width());
+    let (clipped_top, clipped_bottom) = clip(dest_y, height, target.height());
+
+    Ok(RenderInfo { width, height, clipped_left, clipped_right, clipped_top, clipped_bottom, timings: layers.timings })
+}
+
+pub fn render_layers(
+    font: &Font,
+    text: &str,
+    use_kerning: bool,
+    kern_across_spaces: Option<f32>,
+    scale_factor: f32,
+    guides_mirrored: bool,
+    limits: &RenderLimits,
+) -> Result<RenderLayers, Box<dyn Error>> {
+    let options = RenderOptions::default()
+        .kerning(use_kerning)
+        .kern_across_spaces(kern_across_spaces)
+        .scale(scale_factor)
+        .mirrored_guides(guides_mirrored)
+        .limits(limits.clone());
+
+    render_layers_with(font, text, &options)
+}
+
+// Run every render step but keep the layers apart so they can be previewed or exported on their own.
+// Text that would come out blank is an error rather than an empty image
+pub fn render_layers_with(font: &Font, text: &str, options: &RenderOptions) -> Result<RenderLayers, Box<dyn Error>> {
+    let mut layout_time = Duration::ZERO;
+    options.limits.check_text(text)?;
+    let text_layout = RenderTimings::time(&mut layout_time, || layout_with(font, text, options))?;
+    let mut layers = render_layout_layers(font, &text_layout, options)?;
+    layers.timings.layout = layout_time;
+
+    Ok(layers)
+}
+
+// The steps of render_layers_with after layout, for callers that need the layout as well. The layout
+// must come from layout_with with the same options
+pub fn render_layout_layers(font: &Font, text_layout: &TextLayout, options: &RenderOptions) -> Result<RenderLayers, Box<dyn Error>> {
+    let mut timings = RenderTimings::default();
+    if !text_layout.glyphs.iter().any(|glyph| glyph.crop_width > 0 && glyph.crop_height > 0) {
+        return Err(RenderError::NothingToRender.into());
+    }
+    options.limits.check_canvas(text_layout.width, text_layout.height)?;
+    if options.strict_clipping {
+        let clipped = clipped_glyphs(text_layout, text_layout.width, text_layout.height);
+        if !clipped.is_empty() {
+            return Err(RenderError::GlyphsClipped(clipped).into());
+        }
+    }
+
+    // The guides are drawn at the size of the output, the text and the baseline scaled to it
+    let (width, height) = match options.legacy_guide_scale {
+        true => (text_layout.width, scaled_guide_height(text_layout.height, options.scale_factor)),
+        false => scaled_text_size(text_layout.width, text_layout.height, options.scale_factor)?,
+    };
+    options.limits.check_canvas(width, height)?;
+
+    let text_layer = RenderTimings::time(&mut timings.glyph_blit, || rasterize_glyphs(text_layout, font));
+    let (baseline_layer, guide_layer) = RenderTimings::time(&mut timings.guides, || {
+        let baseline_layer = match options.draw_baseline {
+            true => baseline_guide(text_layout),
+            false => RgbaImage::new(text_layout.width, text_layout.height),
+        };
+        (baseline_layer, render_guides(&options.guides, text_layout, font, width, height, options.guides_mirrored))
+    });
+
+    let mut layers = RenderLayers { guides: guide_layer, baseline: baseline_layer, text: text_layer, timings };
+    if !options.legacy_guide_scale {
+        layers.text = scale_nearest(&layers.text, width, height);
+        layers.baseline = scale_nearest(&layers.baseline, width, height);
+    }
+
+    Ok(layers)
+}
+
+// Size of a text canvas scaled by `scale_factor`, at least a pixel each way.
+// Scaling up a canvas near the u32 limit overflows it rather than saturating
+fn scaled_text_size(width: u32, height: u32, scale_factor: f32) -> Result<(u32, u32), RenderError> {
+    let scale = |length: u32| ((length as f64 * scale_factor as f64).round() as u64).max(1);
+    checked_canvas_size(scale(width), scale(height))
+}
+
+// Nearest neighbor resize, so every pixel stays crisp. The guides map their columns with the same
+// nearest_source, which is what keeps the highlight on the scaled glyphs
+pub fn scale_nearest(image: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    if image.dimensions() == (width, height) {
+        return image.clone();
+    }
+    RgbaImage::from_fn(width, height, |x, y| {
+        *image.get_pixel(nearest_source(x, width, image.width()), nearest_source(y, height, image.height()))
+    })
+}
+
+// The pixel of a `source` long row or column under the center of pixel `index` once it is scaled to
+// `scaled` pixels
+fn nearest_source(index: u32, scaled: u32, source: u32) -> u32 {
+    let center = (2 * index as u64 + 1) * source as u64 / (2 * scaled as u64);
+    (center as u32).min(source.saturating_sub(1))
+}
+
+// Measure the text and work out where every glyph goes, without touching any pixels.
+// kern_across_spaces is the fraction of a pair's kerning still applied when a single space separates it
+pub fn layout(
+    font: &Font,
+    text: &str,
+    use_kerning: bool,
+    kern_across_spaces: Option<f32>,
+) -> Result<TextLayout, RenderError> {
+    layout_with(font, text, &RenderOptions::default().kerning(use_kerning).kern_across_spaces(kern_across_spaces))
+}
+
+pub fn layout_with(font: &Font, text: &str, options: &RenderOptions) -> Result<TextLayout, RenderError> {
+    let (text, replaced) = match &options.allowed_chars {
+        Some(allowed) => restrict_text(text, allowed, options.disallowed_policy)?,
+        None => (text.to_string(), Vec::new()),
+    };
+    let styled = styled_chars(&text, options);
+    let padding = i32::try_from(options.vertical_padding).unwrap_or(i32::MAX);
+    let max_height = styled.iter()
+        .filter_map(|(_, ch, _)| font.layout_glyph(*ch))
+        .fold(0i64, |height, char_data| height.max(char_data.height as i64 + char_data.yoffset as i64));
+
+    // With standard metrics the line box sits `padding` rows down and glyph tops are measured from it
+    let standard_metrics = font.metrics.filter(|_| font.standard_vertical_metrics);
+    let canvas_height = match standard_metrics {
+        Some(metrics) => metrics.line_height as u64 + 2 * padding as u64,
+        None => max_height as u64 + 2 * padding as u64,
+    };
+    checked_canvas_size(0, canvas_height)?;
+
+    let base_line: i32 = match standard_metrics {
+        Some(metrics) => i32::try_from(metrics.base).unwrap_or(i32::MAX).saturating_add(padding),
+        None => font.glyphs.values()
+            .map(|char_data| char_data.yoffset)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(padding), // Adjust baseline for the extra canvas height
+    };
+
+    let mut glyphs = Vec::new();
+    // Pen positions are 64 bits so long strings can't wrap around before the size check
+    let mut cursor_x: i64 = 0;
+    // Leftmost and rightmost columns the glyphs' own rects reach, leaving out crop expansion.
+    // Negative kerning or xoffset can put the leftmost before 0
+    let mut min_x: i64 = 0;
+    let mut max_x: i64 = 0;
+    let trim = options.layout_profile.glyph_trim(font);
+    // An outline baked into the atlas already widens every advance; with our own outline on top the
+    // text would be spaced for both, so the baked one comes off
+    let baked_outline = if options.outline.is_some() { font.info().outline as i64 } else { 0 };
+    let adjusted = |advance: u32| (advance as i64 - trim.advance_adjust as i64 - baked_outline).max(0);
+    let letter_spacing = options.letter_spacing.unwrap_or_else(|| options.layout_profile.default_tracking(font));
+    // Bold widens only glyphs with ink, so spaces keep their width
+    let bold_advance = |char_data: Option<&CharData>, bold: bool| match char_data {
+        Some(char_data) if bold && char_data.width > 0 => 1,
+        _ => 0,
+    };
+    let mut last_char: Option<char> = None;
+    // How far the previous character moved the pen, so negative letter spacing can't undo all of it
+    let mut last_advance: i64 = 0;
+    let mut second_last_char: Option<char> = None;
+
+    for &(index, ch, style) in &styled {
+        // Columns the bold copy and the shadow reach past the glyph's rect
+        let overhang = style.bold as i64 + options.shadow as i64;
+        if last_char.is_some() {
+            // Tighter spacing still leaves every character at least 1 px past the one before it
+            cursor_x += (letter_spacing as i64).max((1 - last_advance).min(0));
+        }
+        let pen_before_kerning = cursor_x;
+        if options.use_kerning {
+            if let Some(last) = last_char {
+                let mut kerning = font.kerning(last, ch);
+                if let (0, ' ', Some(fraction), Some(before_space)) = (kerning, last, options.kern_across_spaces, second_last_char) {
+                    kerning = (font.kerning(before_space, ch) as f32 * fraction).round() as i32;
+                }
+
+                cursor_x += kerning as i64;
+            }
+        }
+
+        if let Some(char_data) = font.layout_glyph(ch) {
+            let (crop_x, crop_width) = char_data.columns(font.info(), &trim);
+            let (_, crop_y, _, crop_height) = char_data.source_rect();
+            let (up, right, _, left) = char_data.expansion;
+            let glyph_x = cursor_x + (char_data.xoffset - font.usual_xoffset) as i64;
+            if char_data.width > 0 {
+                min_x = min_x.min(glyph_x);
+                max_x = max_x.max(glyph_x + crop_width as i64 - left as i64 - right as i64 + overhang);
+            }
+            glyphs.push(GlyphPlacement {
+                char_id: ch as u32,
+                crop_x,
+                crop_y,
+                crop_width,
+                crop_height,
+                page: char_data.page,
+                // Expansion grows the rect around the glyph, so the original pixels stay where they were
+                x: glyph_x - left as i64,
+                y: match standard_metrics {
+                    Some(_) => padding as i64 + char_data.yoffset as i64 - up as i64,
+                    None => base_line as i64 - char_data.height as i64 - char_data.yoffset as i64 - up as i64,
+                },
+                pen_x: cursor_x,
+                advance: (adjusted(char_data.xadvance) + bold_advance(Some(char_data), style.bold)) as u32,
+                kerning: cursor_x - pen_before_kerning,
+                bold: style.bold,
+                tint: style.tint,
+                source: GlyphSource {
+                    substituted_from: replaced.iter().find(|replaced| replaced.index == index).map(|replaced| replaced.ch),
+                    missing_glyph: font.is_missing(ch),
+                },
+            });
+        }
 
-    let mut char_data_map = HashMap::new();
-    let mut kerning_pairs = HashMap::new();
+        if let Some(advance) = font.layout_advance(ch) {
+            last_advance = adjusted(advance) + bold_advance(font.layout_glyph(ch), style.bold);
+            cursor_x += last_advance;
+        } else {
+            last_advance = 0;
+        }
 
-    for line in font_data_str.lines() {
+        second_last_char = last_char;
+        last_char = Some(ch);
+    }
 
-        if line.starts_with("char id=") {
-            let char_data = parse_char_line(&line)?;
-            char_data_map.insert(char_data.id, char_data);
-        } else if line.starts_with("kerning first=") {
-            let (first, second, amount) = parse_kerning_line(&line)?;
-            kerning_pairs.insert((first, second), amount);
+    // Shift everything right so no glyph starts left of the canvas. The canvas ends where the pen or
+    // the rightmost glyph does, so the measured width is exactly what gets drawn. An outline adds its
+    // thickness on every side
+    let margin = options.outline.map_or(0, |outline| outline.thickness() as i64);
+    let shift = -min_x + margin;
+    for glyph in &mut glyphs {
+        glyph.x += shift;
+        glyph.pen_x += shift;
+        glyph.y += margin;
+    }
+    let base_line = base_line.saturating_add(margin as i32);
+    let canvas_height = canvas_height + 2 * margin as u64;
+    // The shadow hangs a row below the glyphs; without padding to take it, the canvas grows by that row
+    let shadow_bottom = glyphs.iter()
+        .filter(|glyph| options.shadow && glyph.crop_width > 0)
+        .map(|glyph| (glyph.y + glyph.crop_height as i64 + 1).max(0) as u64)
+        .max()
+        .unwrap_or(0);
+    let (total_width, canvas_height) = checked_canvas_size((max_x.max(cursor_x) + shift + margin) as u64, canvas_height.max(shadow_bottom))?;
+
+    let mut missing: Vec<char> = Vec::new();
+    for ch in styled.iter().map(|(_, ch, _)| *ch).filter(|ch| font.is_missing(*ch)) {
+        if !missing.contains(&ch) {
+            missing.push(ch);
         }
     }
 
-    Ok((char_data_map, kerning_pairs))
+    let ink_width = u32::try_from(max_x + shift + margin).unwrap_or(total_width).min(total_width);
+
+    Ok(TextLayout { width: total_width, height: canvas_height, ink_width, baseline: base_line, glyphs, missing, shadow: options.shadow, outline: options.outline, gradient: options.gradient, replaced })
 }
 
-fn parse_char_line(line: &str) -> Result<CharData, Box<dyn Error>> {
-    let parts: HashMap<&str, String> = line.split_whitespace()
-        .filter(|part| part.contains('='))
-        .map(|part| {
-            let mut split = part.split('=');
-            (split.next().unwrap(), split.next().unwrap().to_string())
-        })
+// Size of a render_text_with image and where each character lands, worked out by the same layout step
+// without drawing anything
+pub struct TextMetrics {
+    pub width: u32,
+    pub height: u32,
+    pub baseline: i32,
+    pub positions: Vec<CharPosition>,
+    // Characters outside the allowed set that were swapped for others
+    pub replaced: Vec<CharReplacement>,
+}
+
+// A drawn character's pen position and how far it moves the pen, kerning excluded
+pub struct CharPosition {
+    pub ch: char,
+    pub x: i64,
+    pub advance: u32,
+}
+
+pub fn measure_text(font: &Font, text: &str, options: &RenderOptions) -> Result<TextMetrics, RenderError> {
+    let text_layout = layout_with(font, text, options)?;
+    let positions = text_layout.glyphs.iter()
+        .filter_map(|glyph| Some(CharPosition { ch: char::from_u32(glyph.char_id)?, x: glyph.pen_x, advance: glyph.advance }))
         .collect();
 
-    let id = parts.get("id")
-        .ok_or("Error: ID not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing ID '{}' from line '{}': {}", parts.get("id").unwrap(), line, e))?;
+    // The rendered image is as large as the scaled text, or with the legacy guide scale as tall as the
+    // taller of its guide layer and the text
+    let (width, height) = match options.legacy_guide_scale {
+        true => (text_layout.width, scaled_guide_height(text_layout.height, options.scale_factor).max(text_layout.height)),
+        false => scaled_text_size(text_layout.width, text_layout.height, options.scale_factor)?,
+    };
+    Ok(TextMetrics {
+        width,
+        height,
+        baseline: text_layout.baseline,
+        positions,
+        replaced: text_layout.replaced,
+    })
+}
 
-    let x = parts.get("x")
-        .ok_or("Error: X coordinate not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing X coordinate '{}' from line '{}': {}", parts.get("x").unwrap(), line, e))?;
+// Where to break a line that is too wide into two: of all the spaces, the one that leaves the wider
+// half narrowest. Both halves go through layout, so widths are real pixels rather than character
+// counts, and the second is measured in the style the first ends in. None when no space has drawn
+// text on both sides
+pub fn suggest_split<'a>(font: &Font, text: &'a str, options: &RenderOptions) -> Result<Option<(&'a str, &'a str)>, RenderError> {
+    let mut best: Option<(u32, (&str, &str))> = None;
 
-    let y = parts.get("y")
-        .ok_or("Error: Y coordinate not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing Y coordinate '{}' from line '{}': {}", parts.get("y").unwrap(), line, e))?;
+    for (index, _) in text.match_indices(' ') {
+        let (first, second) = (text[..index].trim_end(), text[index + 1..].trim_start());
+        if strip_formatting_codes(first).trim().is_empty() || strip_formatting_codes(second).trim().is_empty() {
+            continue;
+        }
 
-    let width = parts.get("width")
-        .ok_or("Error: Width not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing width '{}' from line '{}': {}", parts.get("width").unwrap(), line, e))?;
+        let second_width = layout_with(font, &format!("{}{}", formatting_codes_at_end(first), second), options)?.width;
+        let wider = layout_with(font, first, options)?.width.max(second_width);
+        if best.is_none_or(|(best_width, _)| wider < best_width) {
+            best = Some((wider, (first, second)));
+        }
+    }
 
-    let height = parts.get("height")
-        .ok_or("Error: Height not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing height '{}' from line '{}': {}", parts.get("height").unwrap(), line, e))?;
+    Ok(best.map(|(_, split)| split))
+}
 
-    let yoffset = parts.get("yoffset")
-        .ok_or("Error: Y offset not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing Y offset '{}' from line '{}': {}", parts.get("yoffset").unwrap(), line, e))?;
+// Break the text into lines no wider than max_width, each measured through layout so kerning counts.
+// Lines break at spaces, and runs of spaces become one. A word too wide for a line of its own is broken
+// between characters, never inside a § code, and a character wider than max_width still gets a line to
+// itself rather than failing the render. Every line after the first starts with the § codes the text
+// before it ended in, so each can be laid out on its own, and codes with nothing drawn after them on a
+// line go to the next one
+pub fn wrap_lines(font: &Font, text: &str, max_width: u32, options: &RenderOptions) -> Result<Vec<String>, RenderError> {
+    let fits = |line: &str| -> Result<bool, RenderError> { Ok(layout_with(font, line, options)?.width <= max_width) };
+    let drawn = |line: &str| !strip_formatting_codes(line).is_empty();
+    let mut lines = Vec::new();
+    let mut line = String::new();
 
-    let xadvance = parts.get("xadvance")
-        .ok_or("Error: Xadvance not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing Xadvance '{}' from line '{}': {}", parts.get("xadvance").unwrap(), line, e))?;
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        // Nothing drawn yet means nothing to put a space after
+        let candidate = if drawn(&line) { format!("{} {}", line, word) } else { format!("{}{}", line, word) };
+        if fits(&candidate)? {
+            line = candidate;
+            continue;
+        }
+        let mut rest = match drawn(&line) {
+            true => {
+                let carried = formatting_codes_at_end(&line);
+                lines.push(std::mem::take(&mut line));
+                format!("{}{}", carried, word)
+            }
+            false => candidate,
+        };
+
+        while !fits(&rest)? {
+            // Where the word can break: after a drawn character, so a § stays with its code and codes
+            // stay with the character after them
+            let mut breaks = Vec::new();
+            let mut chars = rest.char_indices();
+            while let Some((index, ch)) = chars.next() {
+                if ch == FORMATTING_CODE_PREFIX {
+                    chars.next();
+                } else if index + ch.len_utf8() < rest.len() {
+                    breaks.push(index + ch.len_utf8());
+                }
+            }
+            // The longest start of the word that fits, or its first drawn character when none does
+            let Some(&first_break) = breaks.first() else { break };
+            let mut cut = first_break;
+            for &end in &breaks[1..] {
+                if !fits(&rest[..end])? {
+                    break;
+                }
+                cut = end;
+            }
+            let (head, tail) = rest.split_at(cut);
+            let carried = formatting_codes_at_end(head);
+            lines.push(head.to_string());
+            rest = format!("{}{}", carried, tail);
+        }
+        line = rest;
+    }
 
-    Ok(CharData { id, x, y, width, height, yoffset, xadvance })
+    match lines.last_mut() {
+        Some(last) if !drawn(&line) => last.push_str(&line),
+        _ => lines.push(line),
+    }
+    Ok(lines)
 }
 
-fn parse_kerning_line(line: &str) -> Result<(u32, u32, i32), Box<dyn Error>> {
-    let parts: HashMap<&str, String> = line.split_whitespace()
-        .filter(|part| part.contains('='))
-        .map(|part| {
-            let mut split = part.split('=');
-            (split.next().unwrap(), split.next().unwrap().to_string())
-        })
-        .collect();
+// Convert measured dimensions back to u32, refusing sizes whose RGBA buffer length would overflow
+fn checked_canvas_size(width: u64, height: u64) -> Result<(u32, u32), RenderError> {
+    let too_large = RenderError::OutputTooLarge { width, height };
+    let buffer_len = width.checked_mul(height).and_then(|pixels| pixels.checked_mul(4)).ok_or(too_large.clone())?;
 
-    let first = parts.get("first")
-        .ok_or("Error: First not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing First '{}' from line '{}': {}", parts.get("first").unwrap(), line, e))?;
+    match (u32::try_from(width), u32::try_from(height), usize::try_from(buffer_len)) {
+        (Ok(width), Ok(height), Ok(buffer_len)) if buffer_len <= isize::MAX as usize => Ok((width, height)),
+        _ => Err(too_large),
+    }
+}
 
-    let second = parts.get("second")
-        .ok_or("Error: Second not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing Second '{}' from line '{}': {}", parts.get("second").unwrap(), line, e))?;
+// Report every glyph that doesn't fully fit on a canvas of the given size. A glyph covers its crop
+// rect plus what its effects add: the outline's thickness on every side, and a column to the right
+// for the bold copy and a column and a row for the shadow
+pub fn clipped_glyphs(text_layout: &TextLayout, canvas_width: u32, canvas_height: u32) -> Vec<GlyphClip> {
+    let outline = text_layout.outline.map_or(0, |outline| outline.thickness() as i64);
+    let shadow = text_layout.shadow as i64;
 
-    let amount = parts.get("amount")
-        .ok_or("Error: Amount not found")?
-        .parse()
-        .map_err(|e| format!("Error parsing Amount '{}' from line '{}': {}", parts.get("amount").unwrap(), line, e))?;
+    text_layout.glyphs.iter()
+        .filter(|glyph| glyph.crop_width > 0 && glyph.crop_height > 0)
+        .filter_map(|glyph| {
+            let (left_edge, top_edge) = (glyph.x - outline, glyph.y - outline);
+            let right_edge = glyph.x + glyph.crop_width as i64 + glyph.bold as i64 + shadow + outline;
+            let bottom_edge = glyph.y + glyph.crop_height as i64 + shadow + outline;
+            let (width, height) = (right_edge - left_edge, bottom_edge - top_edge);
+            let clip = GlyphClip {
+                char_id: glyph.char_id,
+                left: (-left_edge).clamp(0, width) as u32,
+                right: (right_edge - canvas_width as i64).clamp(0, width) as u32,
+                top: (-top_edge).clamp(0, height) as u32,
+                bottom: (bottom_edge - canvas_height as i64).clamp(0, height) as u32,
+            };
 
-    Ok((first, second, amount))
+            (clip.left + clip.right + clip.top + clip.bottom > 0).then_some(clip)
+        })
+        .collect()
 }
 
-pub fn render_text(
-    font_data: &HashMap<u32, CharData>,
-    kerning_pairs: &HashMap<(u32, u32), i32>,
-    font_image: &DynamicImage,
-    text: &str,
-    use_kerning: bool,
-    scale_factor: f32,
-) -> Result<RgbaImage, Box<dyn Error>> {
-    let (total_width, max_height) = text.chars().fold((0, 0), |(width, height), ch| {
-        font_data.get(&(ch as u32)).map_or((width, height), |char_data| {
-            (width + char_data.xadvance.saturating_sub(2), height.max(char_data.height as i32 + char_data.yoffset))
-        })
-    });
+// Copy each placed glyph from its atlas page onto a transparent text layer
+pub fn rasterize_glyphs(text_layout: &TextLayout, font: &Font) -> RgbaImage {
+    let mut text_layer = RgbaImage::new(text_layout.width, text_layout.height);
+    let copies = |glyph: &GlyphPlacement| -> &[i64] { if glyph.bold { &[0, 1] } else { &[0] } };
+    let glyph_image = |glyph: &GlyphPlacement| {
+        let mut image = font.pages()[glyph.page as usize].crop_imm(glyph.crop_x, glyph.crop_y, glyph.crop_width, glyph.crop_height).to_rgba8();
+        if glyph.tint != Rgba([255, 255, 255, 255]) {
+            for pixel in image.pixels_mut() {
+                for (channel, tint) in pixel.0.iter_mut().zip(glyph.tint.0) {
+                    *channel = (*channel as u32 * tint as u32 / 255) as u8;
+                }
+            }
+        }
+        image
+    };
+
+    let draw_glyphs = |layer: &mut RgbaImage| {
+        for glyph in &text_layout.glyphs {
+            let char_img = glyph_image(glyph);
+            for dx in copies(glyph) {
+                imageops::overlay(layer, &char_img, glyph.x + dx, glyph.y);
+            }
+        }
+    };
 
-    let canvas_height = max_height as u32 + 10; // Original padding (5) + 5 extra pixels
-    let mut target_image = RgbaImage::new(total_width, canvas_height);
-    let mut highlight_image = RgbaImage::new(total_width, canvas_height);
+    // The gradient recolors the finished glyphs as a whole, so the shadow and the outline are made
+    // from that layer rather than glyph by glyph
+    if let Some((from, to)) = text_layout.gradient {
+        let mut glyph_layer = RgbaImage::new(text_layout.width, text_layout.height);
+        draw_glyphs(&mut glyph_layer);
+        apply_gradient(&mut glyph_layer, from, to);
+        if text_layout.shadow {
+            let mut shadow = glyph_layer.clone();
+            for pixel in shadow.pixels_mut() {
+                darken_for_shadow(pixel);
+            }
+            imageops::overlay(&mut text_layer, &shadow, 1, 1);
+        }
+        if let Some(outline) = text_layout.outline {
+            imageops::overlay(&mut text_layer, &outline_layer(&glyph_layer, outline), 0, 0);
+        }
+        imageops::overlay(&mut text_layer, &glyph_layer, 0, 0);
+        return text_layer;
+    }
 
-    let base_line: i32 = font_data.values()
-        .map(|char_data| char_data.yoffset)
-        .max()
-        .unwrap_or(0) + 5; // Adjust baseline for the extra canvas height
+    // The whole shadow goes down first so no glyph's shadow covers its neighbor
+    if text_layout.shadow {
+        for glyph in &text_layout.glyphs {
+            let mut shadow = glyph_image(glyph);
+            for pixel in shadow.pixels_mut() {
+                darken_for_shadow(pixel);
+            }
+            for dx in copies(glyph) {
+                imageops::overlay(&mut text_layer, &shadow, glyph.x + dx + 1, glyph.y + 1);
+            }
+        }
+    }
+
+    match text_layout.outline {
+        // The outline is stamped from the glyphs alone, then goes between the shadow and the text
+        Some(outline) => {
+            let mut glyph_layer = RgbaImage::new(text_layout.width, text_layout.height);
+            draw_glyphs(&mut glyph_layer);
+            imageops::overlay(&mut text_layer, &outline_layer(&glyph_layer, outline), 0, 0);
+            imageops::overlay(&mut text_layer, &glyph_layer, 0, 0);
+        }
+        None => draw_glyphs(&mut text_layer),
+    }
+
+    text_layer
+}
 
-    for x in 0..total_width {
-        target_image.put_pixel(x, base_line as u32, Rgba([255, 0, 0, 255])); // Red color for baseline
+// The game's shadow color: each channel a quarter as bright
+fn darken_for_shadow(pixel: &mut Rgba<u8>) {
+    for channel in &mut pixel.0[..3] {
+        *channel = (*channel & 0xFC) >> 2;
     }
+}
 
-    let mut cursor_x: u32 = 0;
-    let mut last_char_id: Option<u32> = None;
+// Sets the color of every inked pixel to the blend of `from` and `to` at its column, keeping its
+// alpha. The blend runs from the leftmost inked column to the rightmost, so both ends get the exact
+// colors; a single inked column gets `from`
+fn apply_gradient(glyph_layer: &mut RgbaImage, from: Rgba<u8>, to: Rgba<u8>) {
+    let (width, height) = glyph_layer.dimensions();
+    let inked = |x: u32| (0..height).any(|y| glyph_layer.get_pixel(x, y)[3] > 0);
+    let (Some(left), Some(right)) = ((0..width).find(|x| inked(*x)), (0..width).rev().find(|x| inked(*x))) else {
+        return;
+    };
+    let span = (right - left).max(1) as i32;
 
-    for ch in text.chars() {
-        let char_id = ch as u32;
+    for (x, _, pixel) in glyph_layer.enumerate_pixels_mut().filter(|(_, _, pixel)| pixel[3] > 0) {
+        let offset = x.saturating_sub(left).min(right - left) as i32;
+        for channel in 0..3 {
+            let (start, end) = (from[channel] as i32, to[channel] as i32);
+            pixel[channel] = (start + ((end - start) * offset + span / 2 * (end - start).signum()) / span) as u8;
+        }
+    }
+}
+
+// The outline color around every inked pixel, as opaque as the most opaque ink within reach so
+// antialiased edges get a soft outline. Where the ink itself is, the text covers it
+fn outline_layer(glyph_layer: &RgbaImage, outline: Outline) -> RgbaImage {
+    let (width, height) = glyph_layer.dimensions();
+    let reach = outline.thickness as i64;
+    let mut layer = RgbaImage::new(width, height);
 
-        if use_kerning {
-            if let Some(last_id) = last_char_id {
-                if let Some(kerning) = kerning_pairs.get(&(last_id, char_id)) {
-                    cursor_x = (cursor_x as i32 + kerning).max(0) as u32;
+    for (x, y, pixel) in glyph_layer.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] > 0) {
+        let alpha = (outline.color[3] as u32 * pixel[3] as u32 / 255) as u8;
+        for ny in (y as i64 - reach).max(0)..=(y as i64 + reach).min(height as i64 - 1) {
+            for nx in (x as i64 - reach).max(0)..=(x as i64 + reach).min(width as i64 - 1) {
+                let target = layer.get_pixel_mut(nx as u32, ny as u32);
+                if target[3] < alpha {
+                    *target = Rgba([outline.color[0], outline.color[1], outline.color[2], alpha]);
                 }
             }
         }
+    }
+
+    layer
+}
+
+// Render a short label with the font itself, without guides or baseline
+pub fn render_label(font: &Font, text: &str) -> Result<RgbaImage, RenderError> {
+    Ok(rasterize_glyphs(&layout(font, text, false, None)?, font))
+}
+
+const SHEET_PADDING: u32 = 2;
+const SHEET_GRID_COLOR: Rgba<u8> = Rgba([128, 128, 128, 255]);
+const SHEET_CELL_COLOR: Rgba<u8> = Rgba([32, 32, 32, 255]); // Dark so the white bitmap font stays readable
+
+// Contact sheet of every glyph in the font, each rendered on its own through the normal glyph path
+// and labeled with its code point in hex, so per-glyph crop problems show up at a glance
+pub fn glyph_sheet(font: &Font, columns: u32) -> Result<RgbaImage, RenderError> {
+    let cells = font.chars()
+        .map(|ch| Ok((render_label(font, &ch.to_string())?, render_label(font, &format!("{:X}", ch as u32))?)))
+        .collect::<Result<Vec<(RgbaImage, RgbaImage)>, RenderError>>()?;
+
+    let cell_width = cells.iter().map(|(glyph, label)| glyph.width().max(label.width())).max().unwrap_or(0) + SHEET_PADDING * 2;
+    let glyph_height = cells.iter().map(|(glyph, _)| glyph.height()).max().unwrap_or(0);
+    let label_height = cells.iter().map(|(_, label)| label.height()).max().unwrap_or(0);
+    let cell_height = glyph_height + label_height + SHEET_PADDING * 2;
+
+    let columns = columns.max(1);
+    let rows = (cells.len() as u32).div_ceil(columns).max(1);
+    let mut sheet = RgbaImage::new(columns * (cell_width + 1) + 1, rows * (cell_height + 1) + 1);
+
+    // 1px grid lines around every cell
+    for (x, y, pixel) in sheet.enumerate_pixels_mut() {
+        let on_grid = x % (cell_width + 1) == 0 || y % (cell_height + 1) == 0;
+        *pixel = if on_grid { SHEET_GRID_COLOR } else { SHEET_CELL_COLOR };
+    }
+
+    for (i, (glyph, label)) in cells.iter().enumerate() {
+        let cell_x = (i as u32 % columns) * (cell_width + 1) + 1;
+        let cell_y = (i as u32 / columns) * (cell_height + 1) + 1;
+
+        let glyph_x = cell_x + (cell_width - glyph.width()) / 2;
+        let label_x = cell_x + (cell_width - label.width()) / 2;
+        imageops::overlay(&mut sheet, glyph, glyph_x.into(), (cell_y + SHEET_PADDING).into());
+        imageops::overlay(&mut sheet, label, label_x.into(), (cell_y + SHEET_PADDING + glyph_height).into());
+    }
+
+    Ok(sheet)
+}
+
+// One PNG for bug reports: each labeled panel stacked top to bottom with 1px separator lines,
+// then a footer line. Labels use the font itself so the output only depends on the inputs
+pub fn diagnostic_image(font: &Font, panels: &[(&str, &RgbaImage)], footer: &str) -> Result<RgbaImage, RenderError> {
+    let labels = panels.iter()
+        .map(|(label, _)| render_label(font, label))
+        .collect::<Result<Vec<RgbaImage>, RenderError>>()?;
+    let footer = render_label(font, footer)?;
+
+    let rows: Vec<&RgbaImage> = labels.iter().zip(panels).flat_map(|(label, (_, panel))| [label, *panel]).chain([&footer]).collect();
+    let width = rows.iter().map(|row| row.width()).max().unwrap_or(0) + SHEET_PADDING * 2;
+    let height = panels.iter().map(|(_, panel)| panel.height() + 1).sum::<u32>()
+        + labels.iter().chain([&footer]).map(|label| label.height()).sum::<u32>()
+        + SHEET_PADDING * 2;
+    checked_canvas_size(width.into(), height.into())?;
+
+    let mut diagnostic = RgbaImage::from_pixel(width, height, SHEET_CELL_COLOR);
+    let mut y = SHEET_PADDING;
+    for (label, (_, panel)) in labels.iter().zip(panels) {
+        imageops::overlay(&mut diagnostic, label, SHEET_PADDING.into(), y.into());
+        y += label.height();
+        imageops::overlay(&mut diagnostic, *panel, SHEET_PADDING.into(), y.into());
+        y += panel.height();
+
+        for x in 0..width {
+            diagnostic.put_pixel(x, y, SHEET_GRID_COLOR);
+        }
+        y += 1;
+    }
+    imageops::overlay(&mut diagnostic, &footer, SHEET_PADDING.into(), y.into());
+
+    Ok(diagnostic)
+}
+
+// The game window a title screen mockup shows, in screen pixels. 1080 rows allow every GUI scale up
+// to MAX_MOCKUP_GUI_SCALE, since the game needs 240 GUI pixels of height at a scale
+pub const MOCKUP_SCREEN_SIZE: (u32, u32) = (1920, 1080);
+pub const MAX_MOCKUP_GUI_SCALE: u32 = 4;
+// How far down the title screen draws its logo, in GUI pixels
+const TITLE_SCREEN_LOGO_Y: u32 = 30;
+// Stand-in for the title screen panorama: a sky fading to haze, drawn in code rather than shipped
+const MOCKUP_SKY_COLOR: Rgba<u8> = Rgba([96, 136, 200, 255]);
+const MOCKUP_HAZE_COLOR: Rgba<u8> = Rgba([186, 206, 230, 255]);
+const MOCKUP_LABEL_TINT: Rgba<u8> = Rgba([255, 255, 85, 255]);
+
+// Where a title `title_width` screen pixels wide goes on a screen `screen_width` wide at `gui_scale`,
+// in screen pixels. Like the title screen's logo it is centered on the screen's width in GUI pixels,
+// rounded up the way the game rounds it, and TITLE_SCREEN_LOGO_Y GUI pixels down
+pub fn title_screen_position(title_width: u32, screen_width: u32, gui_scale: u32) -> (u32, u32) {
+    let gui_scale = gui_scale.max(1);
+    let gui_width = screen_width.div_ceil(gui_scale);
+    let gui_x = (gui_width / 2).saturating_sub(title_width.div_ceil(gui_scale) / 2);
+    (gui_x * gui_scale, TITLE_SCREEN_LOGO_Y * gui_scale)
+}
+
+// How `title` looks in game at `gui_scale`: every texel blown up to gui_scale x gui_scale screen
+// pixels and placed on a stand-in title screen where the logo goes. The corner says it is a preview,
+// so the mockup is never mistaken for the texture. The screen grows to fit a title wider than it
+pub fn title_screen_mockup(font: &Font, title: &RgbaImage, gui_scale: u32) -> Result<RgbaImage, RenderError> {
+    let gui_scale = gui_scale.clamp(1, MAX_MOCKUP_GUI_SCALE);
+    let scaled = upscale(title, gui_scale);
+    let (position_x, position_y) = title_screen_position(scaled.width(), MOCKUP_SCREEN_SIZE.0.max(scaled.width()), gui_scale);
+    let (width, height) = checked_canvas_size(
+        MOCKUP_SCREEN_SIZE.0.max(scaled.width()).into(),
+        MOCKUP_SCREEN_SIZE.1.max(position_y + scaled.height() + TITLE_SCREEN_LOGO_Y * gui_scale).into(),
+    )?;
+
+    let backdrop = BackgroundSource::Gradient { from: MOCKUP_SKY_COLOR, to: MOCKUP_HAZE_COLOR, vertical: true };
+    let mut mockup = generate_background(&backdrop, width, height, &BackgroundOptions::default())?;
+    imageops::overlay(&mut mockup, &scaled, position_x.into(), position_y.into());
+
+    let mut label = render_label(font, &format!("Preview at GUI scale {}, not the texture", gui_scale))?;
+    for pixel in label.pixels_mut() {
+        for (channel, tint) in pixel.0.iter_mut().zip(MOCKUP_LABEL_TINT.0) {
+            *channel = (*channel as u32 * tint as u32 / 255) as u8;
+        }
+    }
+    let label = upscale(&label, gui_scale);
+    let margin = (SHEET_PADDING * gui_scale) as i64;
+    imageops::overlay(&mut mockup, &label, margin, height as i64 - label.height() as i64 - margin);
+
+    Ok(mockup)
+}
+
+// Every pixel repeated `factor` times across and down, the way the game draws texels at a GUI scale
+fn upscale(image: &RgbaImage, factor: u32) -> RgbaImage {
+    RgbaImage::from_fn(image.width() * factor, image.height() * factor, |x, y| *image.get_pixel(x / factor, y / factor))
+}
+
+// Colors of the per-glyph debug overlay
+pub struct DebugOverlayColors {
+    pub advance_box: Rgba<u8>,
+    pub ink_box: Rgba<u8>,
+    pub kerning: Rgba<u8>,
+    pub origin: Rgba<u8>,
+}
+
+impl Default for DebugOverlayColors {
+    fn default() -> Self {
+        DebugOverlayColors {
+            advance_box: Rgba([0, 128, 255, 255]),
+            ink_box: Rgba([255, 0, 255, 255]),
+            kerning: Rgba([255, 255, 0, 255]),
+            origin: Rgba([255, 128, 0, 255]),
+        }
+    }
+}
+
+// Font debugging layer the size of the text layer: every glyph's advance box, the bounding box of
+// its opaque pixels, a tick along the top row spanning any kerning shift and a dot at the pen origin.
+// Everything comes from the layout placements, so it shows exactly what rasterize_glyphs draws
+pub fn debug_overlay(text_layout: &TextLayout, font: &Font, colors: &DebugOverlayColors) -> RgbaImage {
+    let mut overlay = RgbaImage::new(text_layout.width, text_layout.height);
+    let bottom = text_layout.height as i64 - 1;
+
+    for glyph in &text_layout.glyphs {
+        let right = glyph.pen_x + glyph.advance.max(1) as i64 - 1;
+        draw_rect_outline(&mut overlay, (glyph.pen_x, 0), (right, bottom), colors.advance_box);
+
+        let ink = font.pages()[glyph.page as usize].crop_imm(glyph.crop_x, glyph.crop_y, glyph.crop_width, glyph.crop_height)
+            .pixels()
+            .filter(|(_, _, pixel)| pixel[3] != 0)
+            .fold(None, |bounds: Option<(u32, u32, u32, u32)>, (x, y, _)| Some(match bounds {
+                Some((left, top, right, bottom)) => (left.min(x), top.min(y), right.max(x), bottom.max(y)),
+                None => (x, y, x, y),
+            }));
+        if let Some((left, top, right, bottom)) = ink {
+            let top_left = (glyph.x + left as i64, glyph.y + top as i64);
+            draw_rect_outline(&mut overlay, top_left, (glyph.x + right as i64, glyph.y + bottom as i64), colors.ink_box);
+        }
+
+        let pen_before_kerning = glyph.pen_x - glyph.kerning;
+        for x in pen_before_kerning.min(glyph.pen_x)..pen_before_kerning.max(glyph.pen_x) {
+            put_pixel_clipped(&mut overlay, x, 0, colors.kerning);
+        }
+
+        put_pixel_clipped(&mut overlay, glyph.pen_x, text_layout.baseline.into(), colors.origin);
+    }
+
+    overlay
+}
+
+fn draw_rect_outline(image: &mut RgbaImage, (left, top): (i64, i64), (right, bottom): (i64, i64), color: Rgba<u8>) {
+    for x in left..=right {
+        put_pixel_clipped(image, x, top, color);
+        put_pixel_clipped(image, x, bottom, color);
+    }
+    for y in top..=bottom {
+        put_pixel_clipped(image, left, y, color);
+        put_pixel_clipped(image, right, y, color);
+    }
+}
+
+fn put_pixel_clipped(image: &mut RgbaImage, x: i64, y: i64, color: Rgba<u8>) {
+    if let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) {
+        if x < image.width() && y < image.height() {
+            image.put_pixel(x, y, color);
+        }
+    }
+}
+
+// Draw the red debug line across the full width at the baseline row
+pub fn baseline_guide(text_layout: &TextLayout) -> RgbaImage {
+    let mut baseline_layer = RgbaImage::new(text_layout.width, text_layout.height);
+
+    if (0..text_layout.height as i32).contains(&text_layout.baseline) {
+        for x in 0..text_layout.width {
+            baseline_layer.put_pixel(x, text_layout.baseline as u32, BASELINE_COLOR);
+        }
+    }
+
+    baseline_layer
+}
+
+// UV unwrap helper drawn in the guide layer. Every template makes a layer as wide as the text and as
+// tall as the scaled guides, so switching templates never changes the texture's size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuideTemplate {
+    // The tool's original helper for the title model: every column with ink highlighted, recolored
+    // in the rows the model maps to its two bands. Only the color channels of the bands are used
+    CurrentBands { highlight: Rgba<u8>, upper_band: Rgba<u8>, lower_band: Rgba<u8> },
+    // The outline of every inked glyph's columns, over the full guide height
+    PerGlyphBoxes { color: Rgba<u8> },
+    // A 1px frame around the edge of the guide layer
+    BorderFrame { color: Rgba<u8> },
+    None,
+}
 
-        if let Some(char_data) = font_data.get(&char_id) {
-            let crop_x = char_data.x.saturating_add(1);
-            let crop_width = char_data.width.saturating_sub(2).max(1);
-            let char_img = font_image.crop_imm(crop_x, char_data.y, crop_width, char_data.height);
-            let render_y = base_line - char_data.height as i32 - char_data.yoffset;
+impl GuideTemplate {
+    pub fn current_bands() -> Self {
+        GuideTemplate::CurrentBands { highlight: HIGHLIGHT_COLOR, upper_band: UPPER_BAND_COLOR, lower_band: LOWER_BAND_COLOR }
+    }
+
+    pub fn per_glyph_boxes() -> Self {
+        GuideTemplate::PerGlyphBoxes { color: GUIDE_OUTLINE_COLOR }
+    }
+
+    pub fn border_frame() -> Self {
+        GuideTemplate::BorderFrame { color: GUIDE_OUTLINE_COLOR }
+    }
+}
 
-            imageops::overlay(&mut target_image, &char_img, cursor_x.into(), render_y.into());
+impl Default for GuideTemplate {
+    fn default() -> Self {
+        GuideTemplate::current_bands()
+    }
+}
 
-            cursor_x += char_data.xadvance.saturating_sub(3);
+// Draw the guide layer for a laid out line in the chosen template at width x height, the line's columns
+// scaled to the width the way scale_nearest scales them. Mirroring flips the guides horizontally for
+// models that map the texture mirrored, leaving the text as is
+pub fn render_guides(template: &GuideTemplate, text_layout: &TextLayout, font: &Font, width: u32, height: u32, mirrored: bool) -> RgbaImage {
+    let mut guide_layer = match *template {
+        GuideTemplate::CurrentBands { highlight, upper_band, lower_band } => {
+            let inked = inked_columns(text_layout, font);
+            let scaled: Vec<bool> = (0..width).map(|x| inked[nearest_source(x, width, text_layout.width) as usize]).collect();
+            let mut guide_layer = highlight_columns(&scaled, height, highlight);
+            recolor_guide_bands(&mut guide_layer, upper_band, lower_band);
+            guide_layer
         }
+        GuideTemplate::PerGlyphBoxes { color } => glyph_boxes(text_layout, width, height, color),
+        GuideTemplate::BorderFrame { color } => {
+            let mut guide_layer = RgbaImage::new(width, height);
+            outline(&mut guide_layer, 0, width, color);
+            guide_layer
+        }
+        GuideTemplate::None => RgbaImage::new(width, height),
+    };
 
-        last_char_id = Some(char_id);
+    if mirrored {
+        imageops::flip_horizontal_in_place(&mut guide_layer);
     }
 
-    let highlight_color = Rgba([0, 255, 0, 128]); // 50% transparent green for highlight
-    let baseline_color = Rgba([255, 0, 0, 255]); // Red color for baseline
-    for x in 0..total_width {
-        let mut column_has_text = false;
-        for y in 0..canvas_height {
-            let pixel = target_image.get_pixel(x, y);
-            if pixel.0[3] != 0 && *pixel != baseline_color {
-                column_has_text = true;
-                break;
+    guide_layer
+}
+
+// Which columns of the line rasterize_glyphs puts ink in, worked out from where each glyph is placed
+// and which columns of its atlas rect have ink: every copy bold adds, the shadow a column right and a
+// row down, and the outline's reach either side. Rows that fall off the canvas don't count
+fn inked_columns(text_layout: &TextLayout, font: &Font) -> Vec<bool> {
+    let (width, height) = (text_layout.width as i64, text_layout.height as i64);
+    let mut inked = vec![false; text_layout.width as usize];
+    let mut mark = |x: i64, y: i64, spread: i64| {
+        if (0..height).contains(&y) {
+            for x in (x - spread).max(0)..=(x + spread).min(width - 1) {
+                inked[x as usize] = true;
             }
         }
-        if column_has_text {
-            for y in 0..canvas_height {
-                highlight_image.put_pixel(x, y, highlight_color);
+    };
+
+    for glyph in &text_layout.glyphs {
+        let page = &font.pages()[glyph.page as usize];
+        let copies: &[i64] = if glyph.bold { &[0, 1] } else { &[0] };
+        for (column, row) in (0..glyph.crop_width).flat_map(|column| (0..glyph.crop_height).map(move |row| (column, row))) {
+            let alpha = page.get_pixel(glyph.crop_x + column, glyph.crop_y + row)[3] as u32 * glyph.tint[3] as u32 / 255;
+            if alpha == 0 {
+                continue;
             }
+            // The outline is as opaque as the ink it surrounds, so faint enough ink has none
+            let reach = text_layout.outline
+                .filter(|outline| outline.color[3] as u32 * alpha / 255 > 0)
+                .map_or(0, |outline| outline.thickness() as i64);
+            let (x, y) = (glyph.x + column as i64, glyph.y + row as i64);
+            for dx in copies {
+                mark(x + dx, y, reach);
+                if text_layout.shadow {
+                    mark(x + dx + 1, y + 1, 0);
+                }
+            }
+        }
+    }
+
+    inked
+}
+
+// Fill the inked columns over the full height of the guide layer
+fn highlight_columns(inked: &[bool], height: u32, color: Rgba<u8>) -> RgbaImage {
+    let mut highlight_image = RgbaImage::new(inked.len() as u32, height);
+
+    for (x, _) in inked.iter().enumerate().filter(|(_, inked)| **inked) {
+        for y in 0..height {
+            highlight_image.put_pixel(x as u32, y, color);
         }
     }
 
+    highlight_image
+}
+
+// Outline the columns of every glyph that has ink, clipped to the layer
+fn glyph_boxes(text_layout: &TextLayout, width: u32, height: u32, color: Rgba<u8>) -> RgbaImage {
+    let mut guide_layer = RgbaImage::new(width, height);
+    // The first column of the layer scaled from `column` of the line or one right of it
+    let scaled = |column: i64| (0..width).find(|x| nearest_source(*x, width, text_layout.width) as i64 >= column).unwrap_or(width);
+
+    for glyph in text_layout.glyphs.iter().filter(|glyph| glyph.crop_width > 0 && glyph.crop_height > 0) {
+        let left = scaled(glyph.x);
+        let right = scaled(glyph.x + glyph.crop_width as i64);
+        outline(&mut guide_layer, left, right, color);
+    }
+
+    guide_layer
+}
+
+// A 1px rectangle over the columns left..right and the full height of the layer
+fn outline(layer: &mut RgbaImage, left: u32, right: u32, color: Rgba<u8>) {
+    if left >= right || layer.height() == 0 {
+        return;
+    }
+    let bottom = layer.height() - 1;
+
+    for x in left..right {
+        layer.put_pixel(x, 0, color);
+        layer.put_pixel(x, bottom, color);
+    }
+    for y in 0..=bottom {
+        layer.put_pixel(left, y, color);
+        layer.put_pixel(right - 1, y, color);
+    }
+}
 
 // Resize the highlight image if necessary
-    let new_height = (canvas_height as f32 * scale_factor).round() as u32;
-    let final_height = new_height.min(32); // Ensure the height does not exceed 32 pixels
-    highlight_image = imageops::resize(&highlight_image, total_width, final_height, imageops::FilterType::Nearest);
+pub fn scale_guides(highlight_image: &RgbaImage, scale_factor: f32) -> RgbaImage {
+    let final_height = scaled_guide_height(highlight_image.height(), scale_factor);
 
-// Define new colors (without alpha channel)
-    let cyan = Rgba([0, 255, 255, 0]); // Cyan without alpha
-    let purple = Rgba([128, 0, 128, 0]); // Purple without alpha
+    imageops::resize(highlight_image, highlight_image.width(), final_height, imageops::FilterType::Nearest)
+}
 
-    for y in 0..final_height {
-        for x in 0..total_width {
-            let original_pixel = highlight_image.get_pixel(x, y);
-            let mut new_pixel = *original_pixel; // Create a copy of the original pixel
+fn scaled_guide_height(height: u32, scale_factor: f32) -> u32 {
+    let new_height = (height as f32 * scale_factor).round() as u32;
+    new_height.min(32) // Ensure the height does not exceed 32 pixels
+}
 
-            if y >= 27 && y <= 32 {
-                // Set the cyan color while keeping the original alpha
-                new_pixel = Rgba([cyan[0], cyan[1], cyan[2], original_pixel[3]]);
-            } else if y >= 21 && y <= 25 {
-                // Set the purple color while keeping the original alpha
-                new_pixel = Rgba([purple[0], purple[1], purple[2], original_pixel[3]]);
-            }
+// Recolor the guide rows the model maps to, keeping each pixel's alpha
+fn recolor_guide_bands(guide_layer: &mut RgbaImage, upper_band: Rgba<u8>, lower_band: Rgba<u8>) {
+    for (_, y, pixel) in guide_layer.enumerate_pixels_mut() {
+        if (27..=32).contains(&y) {
+            // Set the lower band color while keeping the original alpha
+            *pixel = Rgba([lower_band[0], lower_band[1], lower_band[2], pixel[3]]);
+        } else if (21..=25).contains(&y) {
+            // Set the upper band color while keeping the original alpha
+            *pixel = Rgba([upper_band[0], upper_band[1], upper_band[2], pixel[3]]);
+        }
+    }
+}
+
+// Stack layers from bottom to top onto a transparent canvas of the given size
+pub fn compose(width: u32, height: u32, layers: &[&RgbaImage]) -> RgbaImage {
+    let mut final_image = RgbaImage::new(width, height);
+    compose_into(&mut final_image, 0, 0, layers);
+
+    final_image
+}
+
+// Stack layers from bottom to top onto an existing image at the given offset, clipping at its edges
+pub fn compose_into(target: &mut RgbaImage, x: i64, y: i64, layers: &[&RgbaImage]) {
+    for layer in layers {
+        imageops::overlay(target, *layer, x, y);
+    }
+}
+
+// What a background is generated from
+pub enum BackgroundSource<'a> {
+    Image(&'a DynamicImage),
+    SolidColor(Rgba<u8>),
+    Checker { cell_size: u32, colors: (Rgba<u8>, Rgba<u8>) },
+    Gradient { from: Rgba<u8>, to: Rgba<u8>, vertical: bool },
+}
+
+// How the source is laid out across the background
+pub struct BackgroundOptions {
+    pub offset: (i32, i32), // Shifts the tile grid, wrapping around
+    pub scale: u32, // Integer nearest-neighbor upscale of the source
+    pub mirror_x: bool, // Flip every other tile horizontally
+    pub mirror_y: bool, // Flip every other tile vertically
+}
 
-            highlight_image.put_pixel(x, y, new_pixel); // Place the new pixel
+impl Default for BackgroundOptions {
+    fn default() -> Self {
+        BackgroundOptions { offset: (0, 0), scale: 1, mirror_x: false, mirror_y: false }
+    }
+}
+
+// generate a background of exactly width x height from any source. An image source has to have
+// at least one pixel each way
+pub fn generate_background(source: &BackgroundSource, width: u32, height: u32, options: &BackgroundOptions) -> Result<RgbaImage, RenderError> {
+    let scale = options.scale.max(1);
+    if let BackgroundSource::Image(image) = source {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(RenderError::EmptyTile);
         }
     }
 
-// Create the final image and overlay the highlight and text images
-    let mut final_image = RgbaImage::new(total_width, final_height);
-    imageops::overlay(&mut final_image, &highlight_image, 0, 0); // Place the highlight
-    imageops::overlay(&mut final_image, &target_image, 0, 0); // Then, place the original text
+    Ok(RgbaImage::from_fn(width, height, |x, y| {
+        let x = x as i64 - options.offset.0 as i64;
+        let y = y as i64 - options.offset.1 as i64;
 
-    Ok(final_image)
+        match source {
+            BackgroundSource::Image(image) => {
+                let sx = tile_coordinate(x, image.width(), scale, options.mirror_x);
+                let sy = tile_coordinate(y, image.height(), scale, options.mirror_y);
+                // Blend onto a transparent pixel so the result matches overlaying the tile
+                let mut pixel = Rgba([0, 0, 0, 0]);
+                pixel.blend(&image.get_pixel(sx, sy));
+                pixel
+            }
+            BackgroundSource::SolidColor(color) => *color,
+            BackgroundSource::Checker { cell_size, colors } => {
+                let cell = (*cell_size).max(1) as i64 * scale as i64;
+                if (x.div_euclid(cell) + y.div_euclid(cell)) % 2 == 0 { colors.0 } else { colors.1 }
+            }
+            BackgroundSource::Gradient { from, to, vertical } => {
+                let (position, length) = if *vertical { (y, height) } else { (x, width) };
+                let t = position.rem_euclid(length as i64) as f32 / (length.max(2) - 1) as f32;
+                lerp_color(*from, *to, t)
+            }
+        }
+    }))
 }
 
-// generate background based on an image that gets tiled
-pub fn tile_background(bg_image: &DynamicImage, width: u32, height: u32) -> RgbaImage {
-    let bg_width = bg_image.width();
-    let bg_height = bg_image.height();
-
-    let num_horizontal_tiles = ((width + bg_width - 1) / bg_width).max(1);
-    let tiled_width = num_horizontal_tiles * bg_width;
-    let tiled_bg = RgbaImage::new(tiled_width, height);
-
-    tile_background_helper(&bg_image, &tiled_bg, bg_width, bg_height, 0, 0, tiled_width, height)
-}
-
-fn tile_background_helper(
-    bg_image: &DynamicImage,
-    tiled_bg: &RgbaImage,
-    bg_width: u32,
-    bg_height: u32,
-    current_x: u32,
-    current_y: u32,
-    total_width: u32,
-    total_height: u32,
-) -> RgbaImage {
-    let mut new_tiled_bg = tiled_bg.clone();
-
-    if current_y >= total_height {
-        return new_tiled_bg;
-    }
-
-    if current_x < total_width {
-        let crop = bg_image.crop_imm(0, 0, bg_width, bg_height);
-        imageops::overlay(&mut new_tiled_bg, &crop, current_x as i64, current_y as i64);
-
-        return tile_background_helper(
-            bg_image,
-            &new_tiled_bg,
-            bg_width,
-            bg_height,
-            current_x + bg_width,
-            current_y,
-            total_width,
-            total_height,
-        );
+// Map an output coordinate to a source pixel, repeating (and optionally mirroring) the scaled tile
+fn tile_coordinate(position: i64, source_size: u32, scale: u32, mirror: bool) -> u32 {
+    let tile_size = source_size as i64 * scale as i64;
+    let mut offset = position.rem_euclid(tile_size);
+
+    if mirror && position.div_euclid(tile_size) % 2 != 0 {
+        offset = tile_size - 1 - offset;
+    }
+
+    (offset / scale as i64) as u32
+}
+
+fn lerp_color(from: Rgba<u8>, to: Rgba<u8>, t: f32) -> Rgba<u8> {
+    let channel = |i: usize| (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8;
+    Rgba([channel(0), channel(1), channel(2), channel(3)])
+}
+
+// Recover what was drawn over a known background: pixels within `tolerance` of it on every channel become transparent
+pub fn extract_text_layer(composite: &RgbaImage, background: &RgbaImage, tolerance: u8) -> RgbaImage {
+    RgbaImage::from_fn(composite.width(), composite.height(), |x, y| {
+        let pixel = *composite.get_pixel(x, y);
+        let matches_background = background.get_pixel_checked(x, y).is_some_and(|bg_pixel| {
+            pixel.0.iter().zip(bg_pixel.0.iter()).all(|(a, b)| a.abs_diff(*b) <= tolerance)
+        });
+
+        if matches_background { Rgba([0, 0, 0, 0]) } else { pixel }
+    })
+}
+
+const DIFF_COLOR: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+// Result of comparing a render against a reference image
+pub struct ImageDiff {
+    pub changed_pixels: u64,
+    pub image: RgbaImage,
+}
+
+// Compare two images pixel for pixel. The diff image covers both, shows the reference dimmed to a
+// third of its brightness and marks every changed pixel red; pixels only one image has count as changed
+pub fn diff_images(expected: &RgbaImage, actual: &RgbaImage) -> ImageDiff {
+    let mut changed_pixels = 0;
+    let image = RgbaImage::from_fn(expected.width().max(actual.width()), expected.height().max(actual.height()), |x, y| {
+        match (expected.get_pixel_checked(x, y), actual.get_pixel_checked(x, y)) {
+            (Some(before), Some(after)) if before == after => {
+                let [r, g, b, a] = before.0;
+                let dim = |channel: u8| (channel as u32 * a as u32 / (255 * 3)) as u8;
+                Rgba([dim(r), dim(g), dim(b), 255])
+            }
+            _ => {
+                changed_pixels += 1;
+                DIFF_COLOR
+            }
+        }
+    });
+
+    ImageDiff { changed_pixels, image }
+}
+
+// Width of a strip of whole tiles covering at least the requested width (and at least one tile)
+pub fn tiled_width(tile_width: u32, width: u32) -> Result<u32, RenderError> {
+    if tile_width == 0 {
+        return Err(RenderError::EmptyTile);
     }
+    let tiled_width = width.div_ceil(tile_width).max(1) as u64 * tile_width as u64;
+    u32::try_from(tiled_width).map_err(|_| RenderError::OutputTooLarge { width: tiled_width, height: 0 })
+}
+
+// generate background based on an image that gets tiled
+pub fn tile_background(bg_image: &DynamicImage, width: u32, height: u32) -> Result<RgbaImage, RenderError> {
+    let tiled_width = tiled_width(bg_image.width(), width)?;
 
-    tile_background_helper(
-        bg_image,
-        &new_tiled_bg,
-        bg_width,
-        bg_height,
-        0,
-        current_y + bg_height,
-        total_width,
-        total_height,
-    )
+    generate_background(&BackgroundSource::Image(bg_image), tiled_width, height, &BackgroundOptions::default())
 }
@@ -0,0 +1,67 @@
+// Each way of handling characters outside the allowed set, which the font has glyphs for
+use minecraft_titles::{CharReplacement, CharSet, DisallowedPolicy, RenderError, RenderOptions, layout_with, measure_text};
+mod common;
+use common::embedded_font;
+
+fn restricted(policy: DisallowedPolicy) -> RenderOptions {
+    RenderOptions::default().allowed_chars(Some(CharSet::parse("A-Z0-9!").unwrap()), policy)
+}
+
+fn drawn(text: &str, options: &RenderOptions) -> String {
+    let metrics = measure_text(&embedded_font(), text, options).unwrap();
+    metrics.positions.iter().map(|position| position.ch).collect()
+}
+
+#[test]
+fn parses_ranges_and_single_characters() {
+    let set = CharSet::parse("A-C9-").unwrap();
+    assert!(set.contains('A') && set.contains('B') && set.contains('C') && set.contains('9') && set.contains('-'));
+    assert!(!set.contains('D') && !set.contains('8'));
+    assert!(set.contains(' '));
+    assert!(CharSet::parse("Z-A").is_err());
+}
+
+#[test]
+fn allowed_text_is_unchanged() {
+    assert_eq!(drawn("HELLO 42!", &restricted(DisallowedPolicy::Fail)), "HELLO 42!");
+    assert_eq!(
+        measure_text(&embedded_font(), "HELLO", &restricted(DisallowedPolicy::Fail)).unwrap().width,
+        measure_text(&embedded_font(), "HELLO", &RenderOptions::default()).unwrap().width
+    );
+}
+
+#[test]
+fn fail_names_every_disallowed_character() {
+    let result = layout_with(&embedded_font(), "Hi?", &restricted(DisallowedPolicy::Fail));
+    let Err(RenderError::DisallowedChars(chars)) = result else {
+        panic!("expected the disallowed characters to fail the layout");
+    };
+
+    assert_eq!(chars, vec![
+        CharReplacement { index: 1, ch: 'i', replacement: None },
+        CharReplacement { index: 2, ch: '?', replacement: None },
+    ]);
+    assert!(RenderError::DisallowedChars(chars).to_string().contains("'i' (U+0069) at character 2"));
+}
+
+#[test]
+fn measuring_fails_the_same_way() {
+    assert!(measure_text(&embedded_font(), "Hi", &restricted(DisallowedPolicy::Fail)).is_err());
+}
+
+#[test]
+fn uppercase_fixes_letters_and_still_fails_on_the_rest() {
+    let options = restricted(DisallowedPolicy::Uppercase);
+    assert_eq!(drawn("Hello", &options), "HELLO");
+    assert_eq!(layout_with(&embedded_font(), "Hello", &options).unwrap().replaced.len(), 4);
+    assert!(layout_with(&embedded_font(), "Hello?", &options).is_err());
+}
+
+#[test]
+fn replace_uppercases_first_then_substitutes() {
+    let options = restricted(DisallowedPolicy::Replace('!'));
+    assert_eq!(drawn("Hi? ok", &options), "HI! OK");
+
+    let replaced = layout_with(&embedded_font(), "Hi?", &options).unwrap().replaced;
+    assert_eq!(replaced[1], CharReplacement { index: 2, ch: '?', replacement: Some('!') });
+}
@@ -0,0 +1,91 @@
+// Every background source fills exactly the requested size, also when it isn't a multiple of the
+// tile or cell, and a tile without pixels is an error rather than a division by zero
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{BackgroundOptions, BackgroundSource, RenderError, generate_background, tile_background, tiled_width};
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+// A 5x3 tile with a different color in every pixel
+fn tile() -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(5, 3, tile_pixel))
+}
+
+fn tile_pixel(x: u32, y: u32) -> Rgba<u8> {
+    Rgba([x as u8 * 50, y as u8 * 100, 0, 255])
+}
+
+#[test]
+fn image_repeats_across_a_partial_last_tile() {
+    let background = generate_background(&BackgroundSource::Image(&tile()), 37, 19, &BackgroundOptions::default()).unwrap();
+    assert_eq!(background.dimensions(), (37, 19));
+    assert!(background.enumerate_pixels().all(|(x, y, pixel)| *pixel == tile_pixel(x % 5, y % 3)));
+}
+
+#[test]
+fn image_options_scale_mirror_and_offset_the_tiles() {
+    let options = BackgroundOptions { offset: (3, 1), scale: 2, mirror_x: true, mirror_y: false };
+    let background = generate_background(&BackgroundSource::Image(&tile()), 23, 7, &options).unwrap();
+    assert_eq!(background.dimensions(), (23, 7));
+
+    for (x, y, pixel) in background.enumerate_pixels() {
+        let (x, y) = ((x as i64 - 3).rem_euclid(20), (y as i64 - 1).rem_euclid(6));
+        // Every other 10 px wide tile runs backwards
+        let x = if x >= 10 { 19 - x } else { x };
+        assert_eq!(*pixel, tile_pixel(x as u32 / 2, y as u32 / 2));
+    }
+}
+
+#[test]
+fn solid_color_fills_any_size() {
+    let background = generate_background(&BackgroundSource::SolidColor(RED), 13, 1, &BackgroundOptions::default()).unwrap();
+    assert_eq!(background.dimensions(), (13, 1));
+    assert!(background.pixels().all(|pixel| *pixel == RED));
+}
+
+#[test]
+fn checker_cuts_off_the_last_cells() {
+    let checker = BackgroundSource::Checker { cell_size: 4, colors: (RED, BLUE) };
+    let background = generate_background(&checker, 37, 19, &BackgroundOptions::default()).unwrap();
+    assert_eq!(background.dimensions(), (37, 19));
+    for (x, y, pixel) in background.enumerate_pixels() {
+        assert_eq!(*pixel, if (x / 4 + y / 4) % 2 == 0 { RED } else { BLUE }, "({}, {})", x, y);
+    }
+}
+
+#[test]
+fn gradient_runs_from_edge_to_edge() {
+    for (width, height, vertical) in [(11, 3, false), (3, 7, true)] {
+        let gradient = BackgroundSource::Gradient { from: RED, to: BLUE, vertical };
+        let background = generate_background(&gradient, width, height, &BackgroundOptions::default()).unwrap();
+        assert_eq!(background.dimensions(), (width, height));
+        assert_eq!(*background.get_pixel(0, 0), RED);
+        assert_eq!(*background.get_pixel(width - 1, height - 1), BLUE);
+
+        // Each row or column along the other direction is the same
+        for (x, y, pixel) in background.enumerate_pixels() {
+            assert_eq!(pixel, if vertical { background.get_pixel(0, y) } else { background.get_pixel(x, 0) });
+        }
+    }
+}
+
+#[test]
+fn tiling_rounds_up_to_whole_tiles() {
+    assert_eq!(tiled_width(5, 37).unwrap(), 40);
+    assert_eq!(tiled_width(5, 40).unwrap(), 40);
+    assert_eq!(tiled_width(5, 0).unwrap(), 5);
+
+    let background = tile_background(&tile(), 37, 19).unwrap();
+    assert_eq!(background.dimensions(), (40, 19));
+    assert!(background.enumerate_pixels().all(|(x, y, pixel)| *pixel == tile_pixel(x % 5, y % 3)));
+}
+
+#[test]
+fn empty_tiles_are_an_error() {
+    for empty in [RgbaImage::new(0, 3), RgbaImage::new(5, 0)] {
+        let empty = DynamicImage::ImageRgba8(empty);
+        assert!(matches!(generate_background(&BackgroundSource::Image(&empty), 8, 8, &BackgroundOptions::default()), Err(RenderError::EmptyTile)));
+        assert!(matches!(tile_background(&empty, 8, 8), Err(RenderError::EmptyTile)));
+    }
+    assert!(matches!(tiled_width(0, 8), Err(RenderError::EmptyTile)));
+}
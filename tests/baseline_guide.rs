@@ -0,0 +1,37 @@
+// The red baseline line is optional, and turning it off must leave every other pixel alone; run
+// with UPDATE_GOLDEN=1 to rewrite tests/golden after an intended change
+use minecraft_titles::{RenderOptions, layout_with, render_layers_with, render_text_with};
+mod common;
+use common::{embedded_font, assert_golden};
+
+#[test]
+fn both_states_match_the_golden_images() {
+    let font = embedded_font();
+    assert_golden("baseline_guide_on.png", &render_text_with(&font, "Hello World", &RenderOptions::default().draw_baseline(true)).unwrap());
+    assert_golden("baseline_guide_off.png", &render_text_with(&font, "Hello World", &RenderOptions::default().draw_baseline(false)).unwrap());
+}
+
+#[test]
+fn off_by_default() {
+    let font = embedded_font();
+    assert!(!RenderOptions::default().draw_baseline);
+    assert_golden("baseline_guide_off.png", &render_text_with(&font, "Hello World", &RenderOptions::default()).unwrap());
+}
+
+#[test]
+fn only_the_baseline_row_differs() {
+    let font = embedded_font();
+    let with = render_layers_with(&font, "Hello World", &RenderOptions::default().draw_baseline(true)).unwrap();
+    let without = render_layers_with(&font, "Hello World", &RenderOptions::default().draw_baseline(false)).unwrap();
+
+    // The highlight comes from the text alone, so its columns are the same either way
+    assert!(with.guides == without.guides);
+    assert!(with.text == without.text);
+    assert!(without.baseline.pixels().all(|pixel| pixel[3] == 0));
+
+    // At the default 1.5 the baseline is scaled with the text: output row y shows row (2y + 1) / 3
+    let baseline = layout_with(&font, "Hello World", &RenderOptions::default()).unwrap().baseline as u32;
+    for (x, y, pixel) in with.baseline.enumerate_pixels() {
+        assert_eq!(pixel[3] > 0, (2 * y + 1) / 3 == baseline, "pixel {},{}", x, y);
+    }
+}
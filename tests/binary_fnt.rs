@@ -0,0 +1,57 @@
+// The binary BMFont fixture encodes src/assets/MinecraftDebugger-bitmap.fnt block by block, so both
+// flavors have to load into the same glyphs, kerning, info and metrics and render the same
+#![allow(deprecated)]
+use std::collections::{BTreeSet, HashMap};
+use minecraft_titles::{CharData, Font, RenderOptions, load_font_data, page_files, render_text_with};
+mod common;
+use common::embedded_font;
+
+const TEXT_FNT: &[u8] = include_bytes!("../src/assets/MinecraftDebugger-bitmap.fnt");
+const BINARY_FNT: &[u8] = include_bytes!("fixtures/MinecraftDebugger-bitmap.bin.fnt");
+
+fn binary_font() -> Font {
+    let atlas = image::load_from_memory(include_bytes!("../src/assets/MinecraftDebugger-bitmap.png")).unwrap();
+    Font::from_fnt(BINARY_FNT, vec![atlas]).unwrap()
+}
+
+fn fields(char_data: &CharData) -> (u32, u32, u32, u32, u32, i32, i32, u32, u32) {
+    (
+        char_data.id(), char_data.x(), char_data.y(), char_data.width(), char_data.height(),
+        char_data.xoffset(), char_data.yoffset(), char_data.xadvance(), char_data.page(),
+    )
+}
+
+#[test]
+fn binary_fixture_parses_to_the_same_tables() {
+    let (text_glyphs, text_kerning) = load_font_data(TEXT_FNT).unwrap();
+    let (binary_glyphs, binary_kerning) = load_font_data(BINARY_FNT).unwrap();
+
+    assert_eq!(binary_glyphs.len(), 250);
+    let ids = |glyphs: &HashMap<u32, CharData>| glyphs.keys().copied().collect::<BTreeSet<u32>>();
+    assert_eq!(ids(&binary_glyphs), ids(&text_glyphs));
+    for (id, char_data) in &text_glyphs {
+        assert_eq!(fields(&binary_glyphs[id]), fields(char_data), "glyph {}", id);
+    }
+
+    assert_eq!(binary_kerning.len(), 26);
+    assert_eq!(binary_kerning, text_kerning);
+}
+
+#[test]
+fn binary_fixture_loads_the_same_font() {
+    let (text, binary) = (embedded_font(), binary_font());
+
+    let (text_info, binary_info) = (text.info(), binary.info());
+    assert_eq!(binary_info.face, "Minecraft_Debugger bitmap");
+    assert_eq!(
+        (&binary_info.face, binary_info.size, binary_info.spacing, binary_info.outline, binary_info.padding),
+        (&text_info.face, text_info.size, text_info.spacing, text_info.outline, text_info.padding),
+    );
+    let metrics = |font: &Font| font.metrics().map(|metrics| (metrics.line_height, metrics.base, metrics.scale_w, metrics.scale_h));
+    assert_eq!(metrics(&binary), Some((17, 11, 512, 128)));
+    assert_eq!(metrics(&binary), metrics(&text));
+    assert_eq!(page_files(BINARY_FNT).unwrap(), ["MinecraftDebugger-bitmap.png"]);
+
+    let options = RenderOptions::default().kerning(true);
+    assert!(render_text_with(&binary, "T-SHIRT .? HELLO", &options).unwrap() == render_text_with(&text, "T-SHIRT .? HELLO", &options).unwrap());
+}
@@ -0,0 +1,353 @@
+// Drive the built binary in headless mode and pin its exit codes, messages and output files
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust_bitmap_renderer"))
+        .args(args)
+        .output()
+        .expect("the binary should start")
+}
+
+// A fresh directory under the target dir for one test's files
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn renders_a_texture() {
+    let out = scratch_dir("renders_a_texture").join("title.png");
+    let output = run(&["--text", "HELLO", "--out", out.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let image = image::open(&out).unwrap();
+    assert!(image.width() > 0);
+    assert_eq!(image.height(), 33);
+}
+
+#[test]
+fn missing_required_arguments_are_a_usage_error() {
+    let output = run(&["--text", "HELLO"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--out is required"));
+    assert!(stderr(&output).contains("Usage:"));
+}
+
+#[test]
+fn unknown_argument_is_a_usage_error() {
+    let output = run(&["--text", "HELLO", "--out", "x.png", "--frobnicate"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown argument '--frobnicate'"));
+}
+
+#[test]
+fn missing_font_fails_with_its_path() {
+    let dir = scratch_dir("missing_font_fails_with_its_path");
+    let font = dir.join("nope.fnt");
+    let out = dir.join("title.png");
+    let output = run(&["--text", "HELLO", "--out", out.to_str().unwrap(), "--font", font.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("nope.fnt"), "{}", stderr(&output));
+    assert!(!out.exists());
+}
+
+#[test]
+fn unwritable_output_fails() {
+    let out = scratch_dir("unwritable_output_fails").join("missing_dir").join("title.png");
+    let output = run(&["--text", "HELLO", "--out", out.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Error:"), "{}", stderr(&output));
+}
+
+#[test]
+fn blank_text_fails() {
+    let out = scratch_dir("blank_text_fails").join("title.png");
+    let output = run(&["--text", "   ", "--out", out.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("no characters the font can draw"));
+    assert!(!out.exists());
+}
+
+#[test]
+fn debug_overlay_matches_the_text_layer() {
+    let dir = scratch_dir("debug_overlay_matches_the_text_layer");
+    let (out, overlay) = (dir.join("title.png"), dir.join("overlay.png"));
+    let output = run(&["--text", "HELLO", "--out", out.to_str().unwrap(), "--debug-overlay", overlay.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let overlay = image::open(&overlay).unwrap();
+    assert!(overlay.width() > 0 && overlay.width() <= image::open(&out).unwrap().width());
+}
+
+#[test]
+fn frames_render_an_equally_sized_strip() {
+    let out = scratch_dir("frames_render_an_equally_sized_strip").join("loading.png");
+    let output = run(&["--frame", "LOADING", "--frame", "LOADING...", "--frame-time", "4", "--out", out.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let image = image::open(&out).unwrap();
+    assert_eq!(image.height(), 2 * 33);
+    let mcmeta = fs::read_to_string(out.with_extension("png.mcmeta")).unwrap();
+    assert!(mcmeta.contains("\"frametime\": 4"));
+    assert!(mcmeta.contains(&format!("\"width\": {}", image.width())));
+    assert!(mcmeta.contains("\"height\": 33"));
+}
+
+// A plain magenta atlas to paste titles into
+fn write_atlas(dir: &std::path::Path, width: u32, height: u32) -> PathBuf {
+    let path = dir.join("atlas.png");
+    image::RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 255, 255])).save(&path).unwrap();
+    path
+}
+
+#[test]
+fn into_pastes_over_the_atlas_at_the_position() {
+    let atlas = write_atlas(&scratch_dir("into_pastes_over_the_atlas_at_the_position"), 512, 128);
+    let output = run(&["--text", "HELLO", "--into", atlas.to_str().unwrap(), "--at", "16,64"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let image = image::open(&atlas).unwrap().to_rgba8();
+    assert_eq!(image.dimensions(), (512, 128));
+    assert_eq!(image.get_pixel(15, 64).0, [255, 0, 255, 255]);
+    assert_eq!(image.get_pixel(16, 63).0, [255, 0, 255, 255]);
+    assert_ne!(image.get_pixel(16, 64).0, [255, 0, 255, 255]);
+    let leftovers: Vec<_> = fs::read_dir(atlas.parent().unwrap()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(leftovers, vec!["atlas.png"]);
+}
+
+#[test]
+fn into_fails_when_the_texture_does_not_fit() {
+    let dir = scratch_dir("into_fails_when_the_texture_does_not_fit");
+    let atlas = write_atlas(&dir, 512, 128);
+    let out = dir.join("titled.png");
+    let output = run(&["--text", "HELLO", "--into", atlas.to_str().unwrap(), "--at", "500,0", "--out", out.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("does not fit"), "{}", stderr(&output));
+    assert!(!out.exists());
+
+    let output = run(&["--text", "HELLO", "--into", atlas.to_str().unwrap(), "--at", "500,0", "--out", out.to_str().unwrap(), "--clip"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Warning: Clipped"), "{}", stderr(&output));
+    assert_eq!(image::open(&out).unwrap().width(), 512);
+}
+
+#[test]
+fn right_alignment_lines_up_the_right_edges() {
+    let dir = scratch_dir("right_alignment_lines_up_the_right_edges");
+    let (out, overlay) = (dir.join("title.png"), dir.join("overlay.png"));
+    let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG AGAIN";
+    let output = run(&["--text", text, "--auto-split", "--align", "right", "--out", out.to_str().unwrap(), "--debug-overlay", overlay.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    // The overlay boxes every glyph; the rightmost box of both lines ends on the same column
+    let overlay = image::open(&overlay).unwrap().to_rgba8();
+    let half = overlay.height() / 2;
+    let rightmost = |rows: std::ops::Range<u32>| (0..overlay.width()).rev().find(|x| rows.clone().any(|y| overlay.get_pixel(*x, y)[3] > 0));
+    assert_eq!(rightmost(0..half), rightmost(half..overlay.height()));
+}
+
+#[test]
+fn dry_run_checks_the_allowed_characters() {
+    let output = run(&["--text", "HELLO", "--allowed-chars", "A-Z", "--dry-run"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains(" HELLO"));
+
+    let output = run(&["--text", "Hello", "--allowed-chars", "A-Z", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("'e' (U+0065) at character 2"), "{}", stderr(&output));
+
+    let output = run(&["--text", "Hello", "--allowed-chars", "A-Z", "--on-disallowed", "uppercase", "--dry-run"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Warning: Outside the allowed characters"), "{}", stderr(&output));
+}
+
+#[test]
+fn concurrent_saves_to_one_file_leave_a_whole_image() {
+    let dir = scratch_dir("concurrent_saves_to_one_file_leave_a_whole_image");
+    let out = dir.join("title.png");
+    let children: Vec<_> = ["HELLO", "WORLD", "HELLO WORLD", "AGAIN"].iter()
+        .map(|text| {
+            Command::new(env!("CARGO_BIN_EXE_rust_bitmap_renderer"))
+                .args(["--text", text, "--out", out.to_str().unwrap()])
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    assert_eq!(image::open(&out).unwrap().height(), 33);
+    let leftovers: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(leftovers, vec!["title.png"]);
+}
+
+// Copies of the tool numbering their outputs in one folder at once each claim a name of their own,
+// and every frame strip gets its .mcmeta under the same number
+#[test]
+fn concurrent_numbered_saves_each_get_a_number() {
+    let dir = scratch_dir("concurrent_numbered_saves_each_get_a_number");
+    let out = dir.join("title.png");
+    let children: Vec<_> = (0..8)
+        .map(|index| {
+            let mut command = Command::new(env!("CARGO_BIN_EXE_rust_bitmap_renderer"));
+            match index % 2 {
+                0 => command.args(["--text", "HELLO"]),
+                _ => command.args(["--frame", "LOADING", "--frame", "LOADING..."]),
+            };
+            command.args(["--numbered", "--out", out.to_str().unwrap()]).stdout(Stdio::piped()).spawn().unwrap()
+        })
+        .collect();
+    let mut printed: Vec<String> = children.into_iter()
+        .map(|child| {
+            let output = child.wait_with_output().unwrap();
+            assert!(output.status.success(), "{}", stderr(&output));
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        })
+        .collect();
+
+    printed.sort();
+    let mut expected: Vec<String> = (1..=8).map(|number| dir.join(format!("title_{}.png", number)).display().to_string()).collect();
+    expected.sort();
+    assert_eq!(printed, expected);
+    assert!(!out.exists());
+    for path in &printed {
+        let image = image::open(path).unwrap();
+        let mcmeta = PathBuf::from(format!("{}.mcmeta", path));
+        // The strips are two frames tall, and only they have an .mcmeta
+        assert_eq!(image.height() == 2 * 33, mcmeta.exists(), "{}", path);
+        if mcmeta.exists() {
+            assert!(fs::read_to_string(&mcmeta).unwrap().contains(&format!("\"width\": {}", image.width())));
+        }
+    }
+    let leftovers = fs::read_dir(&dir).unwrap().filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().ends_with(".tmp")).count();
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn png_credits_the_font_from_its_sidecar() {
+    let dir = scratch_dir("png_credits_the_font_from_its_sidecar");
+    fs::write(dir.join("title.fnt"), include_bytes!("../src/assets/MinecraftDebugger-bitmap.fnt").as_slice()).unwrap();
+    fs::write(dir.join("MinecraftDebugger-bitmap.png"), include_bytes!("../src/assets/MinecraftDebugger-bitmap.png").as_slice()).unwrap();
+    fs::write(dir.join("font.toml"), "author = \"Someone\"\nlicense = \"CC BY 4.0\"\n").unwrap();
+    let out = dir.join("title.png");
+    let output = run(&["--text", "HELLO", "--font", dir.join("title.fnt").to_str().unwrap(), "--out", out.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let reader = png::Decoder::new(fs::File::open(&out).unwrap()).read_info().unwrap();
+    let chunks = &reader.info().uncompressed_latin1_text;
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].keyword, "Source-Font");
+    assert_eq!(chunks[0].text, "Minecraft_Debugger bitmap by Someone, CC BY 4.0");
+}
+
+// Renders that change what the self test draws must update its hash; see src/self_test.rs
+#[test]
+fn self_test_passes() {
+    let out = scratch_dir("self_test_passes").join("check.png");
+    let output = run(&["--self-test", "--self-test-out", out.to_str().unwrap()]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.trim_end().ends_with("PASS"), "{}", stdout);
+    assert!(stdout.contains("Hash match: OK"), "{}", stdout);
+    assert!(image::open(&out).unwrap().width() > 0);
+}
+
+#[test]
+fn newlines_split_the_text_into_lines() {
+    let out = scratch_dir("newlines_split_the_text_into_lines").join("title.png");
+    let output = run(&["--text", "HELLO\r\nWORLD\n\n", "--out", out.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(image::open(&out).unwrap().height(), 2 * 33);
+}
+
+#[test]
+fn length_limit_covers_every_line() {
+    let out = scratch_dir("length_limit_covers_every_line").join("title.png");
+    let text = vec!["HELLO WORLD"; 400].join("\n");
+    let output = run(&["--text", &text, "--out", out.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("limit is 4,096"), "{}", stderr(&output));
+    assert!(!out.exists());
+}
+
+#[test]
+fn from_clipboard_cannot_be_combined_with_text() {
+    let output = run(&["--text", "HELLO", "--from-clipboard", "--out", "x.png"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--text and --from-clipboard cannot be used together"));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_clipboard_needs_windows() {
+    let out = scratch_dir("from_clipboard_needs_windows").join("title.png");
+    let output = run(&["--from-clipboard", "--out", out.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("only supported on Windows"), "{}", stderr(&output));
+}
+
+#[test]
+fn mockup_is_saved_next_to_the_texture() {
+    let dir = scratch_dir("mockup_is_saved_next_to_the_texture");
+    let out = dir.join("title.png");
+    let output = run(&["--text", "HELLO", "--mockup", "2", "--out", out.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(image::open(&out).unwrap().height(), 33);
+    assert_eq!(image::open(dir.join("title.mockup.png")).unwrap().width(), 1920);
+
+    let output = run(&["--text", "HELLO", "--mockup", "5", "--out", out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--mockup must be a GUI scale from 1 to 4"), "{}", stderr(&output));
+}
+
+#[test]
+fn scale_output_saves_every_pixel_as_a_block() {
+    let dir = scratch_dir("scale_output_saves_every_pixel_as_a_block");
+    let (plain_out, scaled_out) = (dir.join("plain.png"), dir.join("scaled.png"));
+    assert!(run(&["--text", "HELLO", "--out", plain_out.to_str().unwrap()]).status.success());
+    let output = run(&["--text", "HELLO", "--scale-output", "3", "--out", scaled_out.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let plain = image::open(&plain_out).unwrap().to_rgba8();
+    let scaled = image::open(&scaled_out).unwrap().to_rgba8();
+    assert_eq!(scaled.dimensions(), (plain.width() * 3, plain.height() * 3));
+    assert!(scaled.enumerate_pixels().all(|(x, y, pixel)| pixel == plain.get_pixel(x / 3, y / 3)));
+
+    let output = run(&["--text", "HELLO", "--scale-output", "1.5", "--out", scaled_out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--scale-output must be a whole number from 1 to 4"), "{}", stderr(&output));
+}
+
+#[test]
+fn no_sanitize_looks_zero_width_characters_up_in_the_font() {
+    let out = scratch_dir("no_sanitize_looks_zero_width_characters_up_in_the_font").join("title.png");
+    let output = run(&["--text", "HEL\u{200B}LO", "--out", out.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Removed 1 x U+200B"), "{}", stderr(&output));
+
+    let output = run(&["--text", "HEL\u{200B}LO", "--no-sanitize", "--out", out.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stderr(&output).contains("Removed"), "{}", stderr(&output));
+    assert!(stderr(&output).contains("no glyph") && stderr(&output).contains("U+200B"), "{}", stderr(&output));
+}
@@ -0,0 +1,74 @@
+// Glyphs cut off at the canvas edges, counting what their effects draw around them, are reported
+// side by side, and strict_clipping turns them into an error
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphClip, Outline, RenderError, RenderOptions, clipped_glyphs, layout_with, render_layers_with};
+mod common;
+use common::embedded_font;
+
+// Solid 4x4 glyphs on a 6 row line: 'T' pokes 2 rows above it, 'B' 2 rows below it, and 'C' advances
+// exactly its width
+const CLIP_FNT: &str = "\
+info face=\"Clip\" size=4 padding=0,0,0,0
+common lineHeight=6 base=4 scaleW=32 scaleH=8 pages=1
+page id=0 file=\"clip.png\"
+char id=84 x=1 y=1 width=4 height=4 xoffset=0 yoffset=-2 xadvance=8 page=0
+char id=66 x=9 y=1 width=4 height=4 xoffset=0 yoffset=4 xadvance=8 page=0
+char id=67 x=17 y=1 width=4 height=4 xoffset=0 yoffset=1 xadvance=7 page=0";
+
+fn clip_font(expand_glyph_crop: u32) -> Font {
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_pixel(32, 8, Rgba([255, 255, 255, 255])));
+    let options = FontLoadOptions { standard_vertical_metrics: true, expand_glyph_crop, ..FontLoadOptions::default() };
+    Font::from_fnt_with(CLIP_FNT.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+fn clips(font: &Font, text: &str, options: &RenderOptions) -> Vec<(char, u32, u32, u32, u32)> {
+    let text_layout = layout_with(font, text, options).unwrap();
+    clipped_glyphs(&text_layout, text_layout.width, text_layout.height).iter().map(sides).collect()
+}
+
+fn sides(clip: &GlyphClip) -> (char, u32, u32, u32, u32) {
+    (char::from_u32(clip.char_id).unwrap(), clip.left, clip.right, clip.top, clip.bottom)
+}
+
+#[test]
+fn every_side_is_reported() {
+    let unpadded = RenderOptions::default().vertical_padding(0);
+    assert_eq!(clips(&clip_font(0), "T", &unpadded), [('T', 0, 0, 2, 0)]);
+    assert_eq!(clips(&clip_font(0), "B", &unpadded), [('B', 0, 0, 0, 2)]);
+
+    // The expanded rect grows around the glyph, so the first one hangs off the left edge and the
+    // last one off the right
+    assert_eq!(clips(&clip_font(1), "CC", &unpadded), [('C', 1, 0, 0, 0), ('C', 0, 1, 0, 0)]);
+
+    // Padding leaves room above and below
+    assert!(clips(&clip_font(0), "TB", &RenderOptions::default()).is_empty());
+}
+
+#[test]
+fn effects_count_as_part_of_the_glyph() {
+    let font = embedded_font();
+    for options in [RenderOptions::default().outline(Some(Outline::new(Rgba([0, 0, 0, 255]), 1))), RenderOptions::default().shadow(true)] {
+        let text_layout = layout_with(&font, "HI", &options).unwrap();
+        assert!(clipped_glyphs(&text_layout, text_layout.width, text_layout.height).is_empty());
+
+        // The glyph rect alone still fits a column less; its outline or shadow doesn't
+        let last = text_layout.glyphs.last().unwrap();
+        assert!(last.x + (last.crop_width as i64) < text_layout.width.into());
+        let clipped = clipped_glyphs(&text_layout, text_layout.width - 1, text_layout.height);
+        assert_eq!(clipped.iter().map(sides).collect::<Vec<_>>(), [('I', 0, 1, 0, 0)]);
+    }
+}
+
+#[test]
+fn strict_clipping_fails_the_render() {
+    let font = clip_font(0);
+    let options = RenderOptions::default().vertical_padding(0);
+    assert!(render_layers_with(&font, "T", &options).is_ok());
+
+    let error = render_layers_with(&font, "T", &options.strict_clipping(true)).err().unwrap();
+    match error.downcast_ref::<RenderError>() {
+        Some(RenderError::GlyphsClipped(clipped)) => assert_eq!(clipped.iter().map(sides).collect::<Vec<_>>(), [('T', 0, 0, 2, 0)]),
+        _ => panic!("expected GlyphsClipped, got {}", error),
+    }
+    assert!(error.to_string().contains("'T' loses 2 px on the top"), "{}", error);
+}
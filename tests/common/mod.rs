@@ -0,0 +1,21 @@
+// Helpers shared by the integration tests. Each test file that needs them declares `mod common;`,
+// and not every file uses every helper
+#![allow(dead_code)]
+use std::path::Path;
+use image::RgbaImage;
+use minecraft_titles::Font;
+
+pub fn embedded_font() -> Font {
+    let atlas = image::load_from_memory(include_bytes!("../../src/assets/MinecraftDebugger-bitmap.png")).unwrap();
+    Font::from_fnt(include_bytes!("../../src/assets/MinecraftDebugger-bitmap.fnt"), vec![atlas]).unwrap()
+}
+
+// Compare with tests/golden/<name>; run with UPDATE_GOLDEN=1 to rewrite it after an intended change
+pub fn assert_golden(name: &str, image: &RgbaImage) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        image.save(&path).unwrap();
+    }
+    let golden = image::open(&path).unwrap().to_rgba8();
+    assert!(golden == *image, "{} no longer matches {}", name, path.display());
+}
@@ -0,0 +1,41 @@
+// The debug overlay draws what the layout says: advance boxes, ink boxes, kerning ticks and pen
+// origins, in the colors asked for
+use image::Rgba;
+use minecraft_titles::{DebugOverlayColors, RenderOptions, debug_overlay, layout_with};
+mod common;
+use common::{embedded_font, assert_golden};
+
+#[test]
+fn overlay_matches_the_golden_image() {
+    let font = embedded_font();
+    let text_layout = layout_with(&font, "T-SHIRT .?", &RenderOptions::default().kerning(true)).unwrap();
+    assert_golden("debug_overlay_kerned.png", &debug_overlay(&text_layout, &font, &DebugOverlayColors::default()));
+}
+
+#[test]
+fn overlay_marks_every_placement() {
+    let font = embedded_font();
+    let colors = DebugOverlayColors {
+        advance_box: Rgba([1, 0, 0, 255]),
+        ink_box: Rgba([2, 0, 0, 255]),
+        kerning: Rgba([3, 0, 0, 255]),
+        origin: Rgba([4, 0, 0, 255]),
+    };
+    let text_layout = layout_with(&font, "T-A", &RenderOptions::default().kerning(true)).unwrap();
+    let overlay = debug_overlay(&text_layout, &font, &colors);
+    assert_eq!(overlay.dimensions(), (text_layout.width, text_layout.height));
+
+    let bottom = text_layout.height - 1;
+    for glyph in &text_layout.glyphs {
+        let (left, right) = (glyph.pen_x as u32, (glyph.pen_x + glyph.advance as i64 - 1) as u32);
+        assert_eq!(*overlay.get_pixel(right, bottom), colors.advance_box);
+        assert_eq!(*overlay.get_pixel(left, text_layout.baseline as u32), colors.origin);
+        assert!((0..overlay.height()).any(|y| *overlay.get_pixel(left + 1, y) == colors.ink_box));
+    }
+
+    // '-' is kerned a column closer to 'T', which the tick spans along the top row
+    let hyphen = &text_layout.glyphs[1];
+    assert_eq!(hyphen.kerning, -1);
+    assert_eq!(*overlay.get_pixel(hyphen.pen_x as u32, 0), colors.kerning);
+    assert_eq!(overlay.pixels().filter(|pixel| **pixel == colors.kerning).count(), 1);
+}
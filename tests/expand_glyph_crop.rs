@@ -0,0 +1,73 @@
+// expand_glyph_crop grows every glyph rect on each side until it reaches the atlas edge or another
+// glyph's rect, and warns about the glyphs a neighbor stopped
+use image::{DynamicImage, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, RenderOptions, layout_with};
+
+// A 16x8 atlas: 'A' in the top-left corner, 'B' a column to the right of it, 'C' right up against
+// 'B' and 'D' in the bottom-right corner. The font declares no padding, so the rects aren't trimmed
+const FIXTURE_FNT: &str = "\
+info face=\"Packed\" size=3 padding=0,0,0,0
+common lineHeight=3 base=3 scaleW=16 scaleH=8 pages=1
+page id=0 file=\"packed.png\"
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+char id=65 x=0 y=0 width=3 height=3 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=66 x=4 y=2 width=3 height=3 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=67 x=7 y=2 width=3 height=3 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=68 x=13 y=5 width=3 height=3 xoffset=0 yoffset=0 xadvance=6 page=0";
+
+fn packed_font(expand_glyph_crop: u32) -> Font {
+    let options = FontLoadOptions { expand_glyph_crop, ..FontLoadOptions::default() };
+    Font::from_fnt_with(FIXTURE_FNT.as_bytes(), vec![DynamicImage::ImageRgba8(RgbaImage::new(16, 8))], &options).unwrap()
+}
+
+// The atlas rect the renderer copies for `ch`, as (x, y, width, height)
+fn crop(font: &Font, ch: char) -> (u32, u32, u32, u32) {
+    let text_layout = layout_with(font, &ch.to_string(), &RenderOptions::default()).unwrap();
+    let glyph = &text_layout.glyphs[0];
+    (glyph.crop_x, glyph.crop_y, glyph.crop_width, glyph.crop_height)
+}
+
+#[test]
+fn no_expansion_keeps_the_declared_rects() {
+    let font = packed_font(0);
+    assert_eq!(crop(&font, 'A'), (0, 0, 3, 3));
+    assert_eq!(crop(&font, 'D'), (13, 5, 3, 3));
+    assert!(font.warnings().is_empty());
+}
+
+#[test]
+fn expansion_is_clamped_to_the_atlas() {
+    let font = packed_font(2);
+    // Nothing to grow into above or left of 'A', nor right of or below 'D'
+    let (x, y, _, _) = crop(&font, 'A');
+    assert_eq!((x, y), (0, 0));
+    assert_eq!(crop(&font, 'D'), (11, 3, 5, 5));
+}
+
+#[test]
+fn expansion_stops_at_a_neighbor() {
+    let font = packed_font(2);
+    // 'A' gets the one free column towards 'B' but all of its room below
+    assert_eq!(crop(&font, 'A'), (0, 0, 4, 5));
+    // 'B' keeps off 'A' to the left and can't grow at all into 'C'
+    assert_eq!(crop(&font, 'B'), (3, 0, 4, 7));
+    // 'C' can't grow into 'B', but to the right nothing is in the way
+    assert_eq!(crop(&font, 'C'), (7, 0, 5, 7));
+
+    assert_eq!(font.warnings(), ["Glyph crop expansion stopped at a neighboring glyph for U+0041, U+0042, U+0043"]);
+}
+
+#[test]
+fn expanded_rects_never_cover_another_glyph() {
+    let font = packed_font(5);
+    let original = packed_font(0);
+    let overlaps = |(x, y, width, height): (u32, u32, u32, u32), (other_x, other_y, other_width, other_height): (u32, u32, u32, u32)| {
+        x < other_x + other_width && other_x < x + width && y < other_y + other_height && other_y < y + height
+    };
+
+    for ch in ['A', 'B', 'C', 'D'] {
+        for other in ['A', 'B', 'C', 'D'].into_iter().filter(|other| *other != ch) {
+            assert!(!overlaps(crop(&font, ch), crop(&original, other)), "{} covers {}", ch, other);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// The spacing and outline of the .fnt info line. The Exported profile puts the declared horizontal
+// spacing between characters unless a letter spacing is given, and with our own outline on, any
+// outline baked into the atlas comes off every advance. The embedded font's spacing=-2,-2 changes
+// nothing in the other profiles
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, LayoutProfile, Outline, RenderOptions, layout_with, render_layers_with, render_text_with};
+mod common;
+use common::embedded_font;
+
+// 'A' is a solid 5 px block advancing 6
+const SPACED_FNT: &str = "\
+info face=\"Spaced\" size=5 spacing=2,2 outline=0 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=8 scaleH=5 pages=1
+page id=0 file=\"spaced.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+";
+
+// The same 'A' with a 1 px outline baked around it: a 7 px rect advancing 8
+const OUTLINED_FNT: &str = "\
+info face=\"Outlined\" size=5 spacing=0,0 outline=1 padding=0,0,0,0
+common lineHeight=7 base=6 scaleW=8 scaleH=7 pages=1
+page id=0 file=\"outlined.png\"
+char id=65 x=0 y=0 width=7 height=7 xoffset=0 yoffset=0 xadvance=8 page=0
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+";
+
+fn load(fnt: &str, atlas: RgbaImage) -> Font {
+    let options = FontLoadOptions { glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }, ..FontLoadOptions::default() };
+    Font::from_fnt_with(fnt.as_bytes(), vec![DynamicImage::ImageRgba8(atlas)], &options).unwrap()
+}
+
+fn spaced_font() -> Font {
+    load(SPACED_FNT, RgbaImage::from_fn(8, 5, |x, _| if x < 5 { Rgba([255, 255, 255, 255]) } else { Rgba([0, 0, 0, 0]) }))
+}
+
+fn outlined_font() -> Font {
+    // White ink inside a black ring
+    load(OUTLINED_FNT, RgbaImage::from_fn(8, 7, |x, y| match (x, y) {
+        (1..=5, 1..=5) => Rgba([255, 255, 255, 255]),
+        (0..=6, _) => Rgba([0, 0, 0, 255]),
+        _ => Rgba([0, 0, 0, 0]),
+    }))
+}
+
+// Where each glyph with ink lands
+fn placements(font: &Font, text: &str, options: &RenderOptions) -> Vec<i64> {
+    layout_with(font, text, options).unwrap().glyphs.iter().filter(|glyph| glyph.crop_width > 0).map(|glyph| glyph.x).collect()
+}
+
+fn inked_columns(font: &Font, text: &str, options: &RenderOptions) -> Vec<u32> {
+    let text_layer = render_layers_with(font, text, &options.clone().scale(1.0)).unwrap().text;
+    (0..text_layer.width()).filter(|x| (0..text_layer.height()).any(|y| text_layer.get_pixel(*x, y)[3] > 0)).collect()
+}
+
+#[test]
+fn exported_spacing_is_the_default_tracking() {
+    let font = spaced_font();
+    assert_eq!(font.info().spacing, (2, 2));
+    let exported = RenderOptions::default().layout_profile(LayoutProfile::Exported);
+
+    // 6 px advances plus the declared 2, so every 5 px 'A' is followed by a 3 px gap
+    assert_eq!(placements(&font, "AAA", &exported), [0, 8, 16]);
+    let expected: Vec<u32> = [0..5, 8..13, 16..21].into_iter().flatten().collect();
+    assert_eq!(inked_columns(&font, "AAA", &exported), expected);
+    assert_eq!(layout_with(&font, "AAA", &exported).unwrap().width, 22);
+
+    // A space counts as a character like any other
+    assert_eq!(placements(&font, "A A", &exported), [0, 13]);
+}
+
+#[test]
+fn a_letter_spacing_replaces_the_exported_spacing() {
+    let font = spaced_font();
+    let exported = RenderOptions::default().layout_profile(LayoutProfile::Exported);
+    assert_eq!(placements(&font, "AAA", &exported.clone().letter_spacing(Some(0))), [0, 6, 12]);
+    assert_eq!(placements(&font, "AAA", &exported.letter_spacing(Some(-1))), [0, 5, 10]);
+
+    // The other profiles leave the declared spacing out
+    for profile in [LayoutProfile::Legacy, LayoutProfile::Vanilla] {
+        assert_eq!(placements(&font, "AAA", &RenderOptions::default().layout_profile(profile)), [0, 6, 12], "{:?}", profile);
+    }
+}
+
+#[test]
+fn our_outline_takes_the_baked_one_off_the_advance() {
+    let font = outlined_font();
+    assert_eq!(font.info().outline, 1);
+    let plain = RenderOptions::default().layout_profile(LayoutProfile::Exported);
+    let outlined = plain.clone().outline(Some(Outline::new(Rgba([255, 0, 0, 255]), 1)));
+
+    // Without our outline the baked rings sit 1 px apart
+    assert_eq!(placements(&font, "AAA", &plain), [0, 8, 16]);
+    assert_eq!(inked_columns(&font, "AAA", &plain), [0..7, 8..15, 16..23].into_iter().flatten().collect::<Vec<u32>>());
+
+    // With it each advance loses the baked pixel, and our 1 px margin moves every glyph right by 1
+    assert_eq!(placements(&font, "AAA", &outlined), [1, 8, 15]);
+    let text_layout = layout_with(&font, "AAA", &outlined).unwrap();
+    assert_eq!(text_layout.width, 1 + 3 * 7 + 1);
+    // The rings touch, and our outline fills the canvas edge to edge
+    assert_eq!(inked_columns(&font, "AAA", &outlined), (0..text_layout.width).collect::<Vec<u32>>());
+}
+
+#[test]
+fn the_embedded_font_renders_as_before() {
+    let font = embedded_font();
+    assert_eq!((font.info().spacing, font.info().outline), ((-2, -2), 0));
+    for profile in [LayoutProfile::Legacy, LayoutProfile::Vanilla] {
+        let options = RenderOptions::default().layout_profile(profile).kerning(true);
+        let with_outline = options.clone().outline(Some(Outline::new(Rgba([0, 0, 0, 255]), 1)));
+        for options in [options, with_outline] {
+            let default = render_text_with(&font, "HELLO WORLD", &options).unwrap();
+            assert!(default == render_text_with(&font, "HELLO WORLD", &options.clone().letter_spacing(Some(0))).unwrap(), "{:?}", profile);
+        }
+    }
+}
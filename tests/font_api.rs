@@ -0,0 +1,86 @@
+// The Font type answers glyph, kerning and coverage questions about the loaded font, and the
+// deprecated load_font_data still returns the same tables as bare maps
+use minecraft_titles::{CharData, Font};
+mod common;
+use common::embedded_font;
+
+const FNT: &[u8] = include_bytes!("../src/assets/MinecraftDebugger-bitmap.fnt");
+
+fn fields(char_data: &CharData) -> (u32, u32, u32, u32, u32, i32, i32, u32, u32) {
+    (
+        char_data.id(), char_data.x(), char_data.y(), char_data.width(), char_data.height(),
+        char_data.xoffset(), char_data.yoffset(), char_data.xadvance(), char_data.page(),
+    )
+}
+
+#[test]
+fn glyphs_are_looked_up_by_char() {
+    let font = embedded_font();
+    assert_eq!(font.glyph('A').map(fields), Some((65, 109, 34, 11, 14, -1, -2, 11, 0)));
+    assert_eq!(font.glyph('.').map(fields), Some((46, 369, 62, 7, 6, -1, -2, 7, 0)));
+    assert_eq!(font.glyph(' ').map(fields), Some((32, 0, 0, 0, 0, -2, 8, 5, 0)));
+    assert!(font.glyph('日').is_none());
+
+    assert!(font.has('A') && font.has(' ') && font.has('\0'));
+    assert!(!font.has('日'));
+}
+
+#[test]
+fn kerning_is_zero_without_a_pair() {
+    let font = embedded_font();
+    assert_eq!(font.kerning('T', '-'), -1);
+    assert_eq!(font.kerning('L', 'T'), -2);
+    assert_eq!(font.kerning('.', '?'), -3);
+    // A pair only covers its own order: "-T" has a pair of its own, "TL" has none
+    assert_eq!(font.kerning('-', 'T'), -1);
+    assert_eq!(font.kerning('T', 'L'), 0);
+    assert_eq!(font.kerning('A', 'V'), 0);
+    assert_eq!(font.kerning('日', '本'), 0);
+}
+
+#[test]
+fn chars_come_in_code_point_order() {
+    let font = embedded_font();
+    let chars: Vec<char> = font.chars().collect();
+    assert_eq!(chars.len(), 250);
+    assert!(chars.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(chars[0], '\0');
+    assert!(chars.iter().all(|ch| font.has(*ch)));
+    assert!(chars.contains(&'A') && chars.contains(&' '));
+}
+
+#[test]
+fn info_and_pages_come_from_the_file() {
+    let font = embedded_font();
+    assert_eq!(font.info().face, "Minecraft_Debugger bitmap");
+    assert_eq!((font.info().size, font.info().spacing, font.info().padding), (10, (-2, -2), Some((2, 2, 2, 2))));
+    assert_eq!(font.page_files(), ["MinecraftDebugger-bitmap.png"]);
+    assert_eq!(font.pages().len(), 1);
+    assert_eq!((font.pages()[0].width(), font.pages()[0].height()), (512, 128));
+}
+
+#[test]
+fn from_fnt_bytes_decodes_the_atlas() {
+    let font = Font::from_fnt_bytes(FNT, include_bytes!("../src/assets/MinecraftDebugger-bitmap.png")).unwrap();
+    assert!(font.pages() == embedded_font().pages());
+    assert_eq!(font.glyph('A').map(fields), embedded_font().glyph('A').map(fields));
+
+    let error = Font::from_fnt_bytes(FNT, b"not a png").err().unwrap();
+    assert!(error.to_string().contains("could not decode the atlas image"), "{}", error);
+}
+
+#[test]
+#[allow(deprecated)]
+fn load_font_data_returns_the_same_tables() {
+    let font = embedded_font();
+    let (glyphs, kerning_pairs) = minecraft_titles::load_font_data(FNT).unwrap();
+
+    assert_eq!(glyphs.len(), font.chars().count());
+    for ch in font.chars() {
+        assert_eq!(glyphs.get(&(ch as u32)).map(fields), font.glyph(ch).map(fields), "{:?}", ch);
+    }
+    assert_eq!(kerning_pairs.len(), 26);
+    for ((first, second), amount) in &kerning_pairs {
+        assert_eq!(font.kerning(char::from_u32(*first).unwrap(), char::from_u32(*second).unwrap()), *amount);
+    }
+}
@@ -0,0 +1,59 @@
+// Font attribution: the sidecar format, merging it with what the .fnt says, and the credits text
+use minecraft_titles::{FontAttribution, credits_text};
+mod common;
+use common::embedded_font;
+
+fn attribution(name: &str, author: Option<&str>, license: Option<&str>, url: Option<&str>) -> FontAttribution {
+    FontAttribution {
+        name: Some(name.to_string()),
+        author: author.map(str::to_string),
+        license: license.map(str::to_string),
+        url: url.map(str::to_string),
+    }
+}
+
+#[test]
+fn sidecar_reads_the_string_keys() {
+    let sidecar = "\
+# Credits for the title font
+[font]
+name = \"Blocky \\\"Sans\\\"\"
+author = 'Someone'   # literal string
+license = \"CC BY 4.0\"
+url = \"https://example.com/blocky\"
+version = \"2\"
+";
+    let parsed = FontAttribution::from_toml(sidecar).unwrap();
+    assert_eq!(parsed, attribution("Blocky \"Sans\"", Some("Someone"), Some("CC BY 4.0"), Some("https://example.com/blocky")));
+    assert_eq!(FontAttribution::from_toml(&parsed.to_toml()).unwrap(), parsed);
+}
+
+#[test]
+fn sidecar_rejects_what_it_cannot_read() {
+    let error = FontAttribution::from_toml("author = \"Someone\"\nlicense\n").unwrap_err().to_string();
+    assert!(error.contains("line 2"), "{}", error);
+    let error = FontAttribution::from_toml("author = Someone\n").unwrap_err().to_string();
+    assert!(error.contains("line 1"), "{}", error);
+    assert!(FontAttribution::from_toml("author = \"Someone\" trailing\n").is_err());
+}
+
+#[test]
+fn sidecar_fills_in_the_face_name() {
+    let mut font = embedded_font();
+    assert_eq!(font.attribution(), &attribution("Minecraft_Debugger bitmap", None, None, None));
+
+    font.merge_attribution(FontAttribution { license: Some("OFL-1.1".to_string()), ..FontAttribution::default() });
+    assert_eq!(font.attribution(), &attribution("Minecraft_Debugger bitmap", None, Some("OFL-1.1"), None));
+    assert_eq!(font.attribution().to_string(), "Minecraft_Debugger bitmap, OFL-1.1");
+}
+
+#[test]
+fn credits_list_every_font_once() {
+    let title = attribution("Blocky", Some("Someone"), Some("CC BY 4.0"), Some("https://example.com/blocky"));
+    let fallback = attribution("Tiny", None, Some("OFL-1.1"), None);
+
+    assert_eq!(
+        credits_text(&[&title, &FontAttribution::default(), &fallback, &title]),
+        "Fonts\n\nBlocky\n  Author: Someone\n  License: CC BY 4.0\n  URL: https://example.com/blocky\n\nTiny\n  License: OFL-1.1\n"
+    );
+}
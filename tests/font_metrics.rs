@@ -0,0 +1,74 @@
+// The common line's lineHeight and base are parsed into FontMetrics. With standard vertical metrics
+// they set the canvas height and where glyphs hang from the baseline; a font without a common line
+// keeps the baseline guessed from its glyphs
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, RenderOptions, layout_with};
+mod common;
+use common::embedded_font;
+
+// 'A' sits on the baseline and 'g' hangs 3 rows below it, down to the bottom of the 9 px line
+const COMMON_LINE: &str = "common lineHeight=9 base=6 scaleW=16 scaleH=8 pages=1\n";
+const DESCENDER_FNT: &str = "\
+info face=\"Descender\" size=6 padding=0,0,0,0
+page id=0 file=\"descender.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=1 xadvance=6 page=0
+char id=103 x=6 y=0 width=5 height=6 xoffset=0 yoffset=3 xadvance=6 page=0
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+";
+
+fn load(fnt: &str, standard_vertical_metrics: bool) -> Font {
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_pixel(16, 8, Rgba([255, 255, 255, 255])));
+    let options = FontLoadOptions { standard_vertical_metrics, ..FontLoadOptions::default() };
+    Font::from_fnt_with(fnt.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+fn with_common_line() -> String {
+    DESCENDER_FNT.replacen("page id", &format!("{}page id", COMMON_LINE), 1)
+}
+
+// Top row of every glyph, the baseline and the canvas height
+fn vertical_layout(font: &Font, padding: u32) -> (Vec<i64>, i32, u32) {
+    let text_layout = layout_with(font, "Ag", &RenderOptions::default().vertical_padding(padding)).unwrap();
+    (text_layout.glyphs.iter().map(|glyph| glyph.y).collect(), text_layout.baseline, text_layout.height)
+}
+
+#[test]
+fn the_common_line_is_parsed() {
+    let metrics = |font: &Font| font.metrics().map(|metrics| (metrics.line_height, metrics.base, metrics.scale_w, metrics.scale_h));
+    assert_eq!(metrics(&embedded_font()), Some((17, 11, 512, 128)));
+    assert_eq!(metrics(&load(&with_common_line(), true)), Some((9, 6, 16, 8)));
+    assert_eq!(metrics(&load(DESCENDER_FNT, true)), None);
+}
+
+#[test]
+fn glyphs_hang_from_the_base() {
+    let font = load(&with_common_line(), true);
+    assert_eq!(vertical_layout(&font, 0), (vec![1, 3], 6, 9));
+    // Padding moves the whole line box down and adds to both ends of the canvas
+    assert_eq!(vertical_layout(&font, 2), (vec![3, 5], 8, 13));
+}
+
+#[test]
+fn the_line_height_holds_the_descender() {
+    let text_layout = layout_with(&load(&with_common_line(), true), "gA", &RenderOptions::default().vertical_padding(0)).unwrap();
+    let bottom = text_layout.glyphs.iter().map(|glyph| glyph.y + glyph.crop_height as i64).max().unwrap();
+    assert_eq!(bottom, text_layout.height as i64);
+    assert!(text_layout.glyphs.iter().all(|glyph| glyph.y >= 0));
+}
+
+#[test]
+fn without_a_common_line_the_baseline_is_guessed() {
+    // Asking for standard metrics makes no difference when the font has none to use
+    for padding in [0, 2] {
+        assert_eq!(vertical_layout(&load(DESCENDER_FNT, true), padding), vertical_layout(&load(DESCENDER_FNT, false), padding));
+    }
+    // And the common line is only used when asked for
+    assert_eq!(vertical_layout(&load(&with_common_line(), false), 2), vertical_layout(&load(DESCENDER_FNT, false), 2));
+}
+
+#[test]
+fn line_height_falls_back_to_the_tallest_glyph() {
+    assert_eq!(embedded_font().line_height(), 17);
+    assert_eq!(load(&with_common_line(), false).line_height(), 9);
+    assert_eq!(load(DESCENDER_FNT, false).line_height(), 6);
+}
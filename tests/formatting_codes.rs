@@ -0,0 +1,61 @@
+// § formatting codes: colors and bold apply to the glyphs after them, and the codes themselves
+// take no room and never reach the canvas
+use image::Rgba;
+use minecraft_titles::{CharSet, DisallowedPolicy, RenderOptions, formatting_codes_at_end, layout_with, render_text_with, strip_formatting_codes};
+mod common;
+use common::embedded_font;
+
+fn render(text: &str, options: &RenderOptions) -> image::RgbaImage {
+    render_text_with(&embedded_font(), text, options).unwrap()
+}
+
+#[test]
+fn color_codes_tint_what_follows() {
+    let gold = RenderOptions::default().tint(Rgba([0xFF, 0xAA, 0x00, 255]));
+    assert!(render("§6Gold", &RenderOptions::default()) == render("Gold", &gold));
+    assert!(render("§6Gold", &RenderOptions::default()) == render("§6G§6o§6l§6d", &RenderOptions::default()));
+}
+
+#[test]
+fn codes_take_no_width() {
+    let font = embedded_font();
+    let width = |text: &str, options: &RenderOptions| layout_with(&font, text, options).unwrap().width;
+
+    assert_eq!(width("§aGold §bText", &RenderOptions::default()), width("Gold Text", &RenderOptions::default()));
+    assert_eq!(width("§lBold", &RenderOptions::default()), width("Bold", &RenderOptions::default().bold(true)));
+    // Each of the four bold letters advances one pixel further, as in the game
+    assert_eq!(width("§lBold§r Text", &RenderOptions::default()), width("Bold Text", &RenderOptions::default()) + 4);
+}
+
+#[test]
+fn unknown_codes_and_a_trailing_sign_are_skipped() {
+    let plain = render("Hello", &RenderOptions::default());
+    assert!(render("§zHe§kllo", &RenderOptions::default()) == plain);
+    assert!(render("Hello§", &RenderOptions::default()) == plain);
+}
+
+#[test]
+fn reset_returns_to_the_options_style() {
+    let layout = layout_with(&embedded_font(), "§6§lA§rB§cC", &RenderOptions::default().tint(Rgba([255, 255, 255, 128]))).unwrap();
+    let styles: Vec<_> = layout.glyphs.iter().map(|glyph| (glyph.bold, glyph.tint)).collect();
+    assert_eq!(styles, vec![
+        (true, Rgba([0xFF, 0xAA, 0x00, 128])),
+        (false, Rgba([255, 255, 255, 128])),
+        (false, Rgba([0xFF, 0x55, 0x55, 128])),
+    ]);
+}
+
+#[test]
+fn codes_pass_the_allowed_characters() {
+    let allowed = CharSet::parse("A-Z").unwrap();
+    let options = RenderOptions::default().allowed_chars(Some(allowed), DisallowedPolicy::Fail);
+    assert!(layout_with(&embedded_font(), "§6GOLD §lBOLD", &options).is_ok());
+}
+
+#[test]
+fn codes_can_be_stripped_and_carried_over() {
+    assert_eq!(strip_formatting_codes("§6Gold §lBold§r Text§"), "Gold Bold Text");
+    assert_eq!(formatting_codes_at_end("§6Gold §lBold"), "§6§l");
+    assert_eq!(formatting_codes_at_end("§6Gold §lBold§r Text"), "");
+    assert_eq!(formatting_codes_at_end("§lBold §aGreen"), "§a");
+}
@@ -0,0 +1,28 @@
+// Every placed glyph records whether the font drew it for the character the text asked for, or a
+// stand-in did: the font's missing glyph, or a character the disallowed policy swapped in
+use minecraft_titles::{CharSet, DisallowedPolicy, GlyphSource, RenderOptions, glyph_source_summary, layout_with};
+mod common;
+use common::embedded_font;
+
+#[test]
+fn mixed_text_records_each_source() {
+    let options = RenderOptions::default().allowed_chars(Some(CharSet::parse("A-Z\u{4E00}").unwrap()), DisallowedPolicy::Uppercase);
+    let layout = layout_with(&embedded_font(), "Ab\u{4E00}C", &options).unwrap();
+
+    let sources: Vec<GlyphSource> = layout.glyphs.iter().map(|glyph| glyph.source).collect();
+    assert_eq!(sources, vec![
+        GlyphSource::default(),
+        GlyphSource { substituted_from: Some('b'), missing_glyph: false },
+        GlyphSource { substituted_from: None, missing_glyph: true },
+        GlyphSource::default(),
+    ]);
+    assert_eq!(glyph_source_summary(&layout.glyphs).as_deref(), Some("1 glyph drawn as the missing glyph, 1 substituted"));
+}
+
+#[test]
+fn plain_text_comes_from_the_font() {
+    let layout = layout_with(&embedded_font(), "Hello World", &RenderOptions::default()).unwrap();
+
+    assert!(layout.glyphs.iter().all(|glyph| glyph.source.is_font_glyph()));
+    assert_eq!(glyph_source_summary(&layout.glyphs), None);
+}
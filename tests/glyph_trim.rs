@@ -0,0 +1,65 @@
+// GlyphTrim sets how many columns come off each side of a glyph rect and how much every advance
+// loses. The defaults are the embedded font's, and measuring and rendering share one layout, so the
+// measured width is the rendered width for any trim
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderOptions, layout_with, measure_text, render_layers_with, render_text_with};
+mod common;
+use common::embedded_font;
+
+fn trimmed_font(glyph_trim: GlyphTrim) -> Font {
+    let atlas = image::load_from_memory(include_bytes!("../src/assets/MinecraftDebugger-bitmap.png")).unwrap();
+    let options = FontLoadOptions { glyph_trim, ..FontLoadOptions::default() };
+    Font::from_fnt_with(include_bytes!("../src/assets/MinecraftDebugger-bitmap.fnt"), vec![atlas], &options).unwrap()
+}
+
+// Source column, width and advance of the first glyph of `text`
+fn first_glyph(font: &Font, text: &str) -> (u32, u32, u32) {
+    let text_layout = layout_with(font, text, &RenderOptions::default()).unwrap();
+    let glyph = &text_layout.glyphs[0];
+    (glyph.crop_x, glyph.crop_width, glyph.advance)
+}
+
+#[test]
+fn the_defaults_trim_a_column_each_side_and_three_off_the_advance() {
+    let defaults = GlyphTrim::default();
+    assert_eq!((defaults.crop_left, defaults.crop_right, defaults.advance_adjust), (1, 1, 3));
+    let loaded = FontLoadOptions::default().glyph_trim;
+    assert_eq!((loaded.crop_left, loaded.crop_right, loaded.advance_adjust), (1, 1, 3));
+
+    // 'A' is 11 px wide at x 109 and advances 11
+    assert_eq!(first_glyph(&embedded_font(), "A"), (110, 9, 8));
+    assert!(render_text_with(&embedded_font(), "HELLO", &RenderOptions::default()).unwrap()
+        == render_text_with(&trimmed_font(GlyphTrim::default()), "HELLO", &RenderOptions::default()).unwrap());
+}
+
+#[test]
+fn the_trim_changes_rects_and_advances() {
+    assert_eq!(first_glyph(&trimmed_font(GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }), "A"), (109, 11, 11));
+    assert_eq!(first_glyph(&trimmed_font(GlyphTrim { crop_left: 2, crop_right: 0, advance_adjust: -1 }), "A"), (111, 9, 12));
+    // Cropping stops at the 2 px padding the font declares
+    assert_eq!(first_glyph(&trimmed_font(GlyphTrim { crop_left: 5, crop_right: 5, advance_adjust: 0 }), "A"), (111, 7, 11));
+    // An advance can't go below zero
+    assert_eq!(first_glyph(&trimmed_font(GlyphTrim { crop_left: 1, crop_right: 1, advance_adjust: 20 }), "A").2, 0);
+}
+
+#[test]
+fn the_measured_width_is_the_rendered_width() {
+    let trims = [
+        GlyphTrim::default(),
+        GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 },
+        GlyphTrim { crop_left: 2, crop_right: 1, advance_adjust: 5 },
+        GlyphTrim { crop_left: 0, crop_right: 2, advance_adjust: -2 },
+    ];
+    for glyph_trim in trims {
+        let font = trimmed_font(glyph_trim);
+        for (text, options) in [
+            ("HELLO WORLD", RenderOptions::default()),
+            ("T-SHIRT .?", RenderOptions::default().kerning(true)),
+            ("A", RenderOptions::default()),
+            ("PLAY 123", RenderOptions::default().letter_spacing(Some(2))),
+        ] {
+            let measured = measure_text(&font, text, &options).unwrap().width;
+            assert_eq!(measured, render_text_with(&font, text, &options).unwrap().width(), "{:?} {}", glyph_trim, text);
+            assert_eq!(measured, render_layers_with(&font, text, &options).unwrap().text.width(), "{:?} {}", glyph_trim, text);
+        }
+    }
+}
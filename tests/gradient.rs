@@ -0,0 +1,58 @@
+// The gradient recolors the text from the first color at its leftmost ink to the second at its
+// rightmost, keeping the font's alpha, and the shadow follows the blend
+use image::{Rgba, RgbaImage};
+use minecraft_titles::{RenderOptions, render_layers_with};
+mod common;
+use common::embedded_font;
+
+const FROM: Rgba<u8> = Rgba([255, 170, 0, 255]);
+const TO: Rgba<u8> = Rgba([0, 64, 255, 255]);
+
+fn text_layer(text: &str, options: &RenderOptions) -> RgbaImage {
+    render_layers_with(&embedded_font(), text, options).unwrap().text
+}
+
+// The first lit pixel found scanning the columns in order, top to bottom in each
+fn first_lit(layer: &RgbaImage, columns: impl Iterator<Item = u32>) -> Rgba<u8> {
+    let height = layer.height();
+    columns.flat_map(|x| (0..height).map(move |y| (x, y)))
+        .map(|(x, y)| *layer.get_pixel(x, y))
+        .find(|pixel| pixel[3] > 0)
+        .unwrap()
+}
+
+#[test]
+fn ends_of_the_text_get_the_two_colors() {
+    let layer = text_layer("HELLO", &RenderOptions::default().gradient(Some((FROM, TO))));
+    let width = layer.width();
+
+    let rgb = |pixel: Rgba<u8>| [pixel[0], pixel[1], pixel[2]];
+    assert_eq!(rgb(first_lit(&layer, 0..width)), rgb(FROM));
+    assert_eq!(rgb(first_lit(&layer, (0..width).rev())), rgb(TO));
+}
+
+#[test]
+fn alpha_is_kept() {
+    let plain = text_layer("HELLO", &RenderOptions::default());
+    let blended = text_layer("HELLO", &RenderOptions::default().gradient(Some((FROM, TO))));
+
+    assert_eq!(plain.dimensions(), blended.dimensions());
+    assert!(plain.pixels().zip(blended.pixels()).all(|(plain, blended)| plain[3] == blended[3]));
+}
+
+#[test]
+fn shadow_darkens_the_blend_at_its_column() {
+    let options = RenderOptions::default().scale(1.0).gradient(Some((FROM, TO)));
+    let plain = text_layer("I", &options);
+    let shadowed = text_layer("I", &options.clone().shadow(true));
+
+    let (x, y) = plain.enumerate_pixels()
+        .find(|(x, y, pixel)| pixel[3] > 0 && *x + 1 < plain.width() && plain.get_pixel(x + 1, y + 1)[3] == 0)
+        .map(|(x, y, _)| (x, y))
+        .unwrap();
+    let (ink, shadow) = (plain.get_pixel(x, y), shadowed.get_pixel(x + 1, y + 1));
+    assert!(shadow[3] > 0);
+    for channel in 0..3 {
+        assert_eq!(shadow[channel], (ink[channel] & 0xFC) >> 2);
+    }
+}
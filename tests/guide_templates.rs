@@ -0,0 +1,110 @@
+// The UV helper templates. CurrentBands with its default colors and the legacy guide scale must keep
+// producing the exact image the tool always has, baseline included; run with UPDATE_GOLDEN=1 to rewrite tests/golden after an intended change
+use image::{RgbaImage, Rgba};
+use minecraft_titles::{GuideTemplate, Outline, RenderOptions, layout_with, render_layers_with, render_text_with};
+mod common;
+use common::{embedded_font, assert_golden};
+
+#[test]
+fn current_bands_match_the_golden_image() {
+    let font = embedded_font();
+    assert_golden("current_bands.png", &render_text_with(&font, "Hello World", &RenderOptions::default().legacy_guide_scale(true).kerning(true).draw_baseline(true)).unwrap());
+    assert_golden("current_bands_mirrored.png", &render_text_with(&font, "AVATAR", &RenderOptions::default().mirrored_guides(true).scale(1.0).draw_baseline(true)).unwrap());
+}
+
+#[test]
+fn explicit_current_bands_are_the_default() {
+    let font = embedded_font();
+    let options = RenderOptions::default().legacy_guide_scale(true).kerning(true).draw_baseline(true).guides(GuideTemplate::current_bands());
+    assert_golden("current_bands.png", &render_text_with(&font, "Hello World", &options).unwrap());
+}
+
+// The guide layer of every template is the same size, so switching templates keeps the texture's size
+#[test]
+fn every_template_keeps_the_guide_size() {
+    let font = embedded_font();
+    let templates = [GuideTemplate::current_bands(), GuideTemplate::per_glyph_boxes(), GuideTemplate::border_frame(), GuideTemplate::None];
+    let sizes: Vec<_> = templates.iter()
+        .map(|template| render_layers_with(&font, "Hi there", &RenderOptions::default().guides(*template)).unwrap().guides.dimensions())
+        .collect();
+    assert!(sizes.iter().all(|size| *size == sizes[0]), "{:?}", sizes);
+}
+
+#[test]
+fn border_frame_outlines_the_layer_edge() {
+    let color = Rgba([255, 0, 0, 255]);
+    let guides = render_layers_with(&embedded_font(), "Hi", &RenderOptions::default().guides(GuideTemplate::BorderFrame { color })).unwrap().guides;
+    let (width, height) = guides.dimensions();
+
+    for (x, y, pixel) in guides.enumerate_pixels() {
+        let on_edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+        assert_eq!(*pixel == color, on_edge, "pixel {},{}", x, y);
+    }
+}
+
+#[test]
+fn glyph_boxes_follow_the_placements() {
+    let font = embedded_font();
+    let color = Rgba([0, 0, 255, 255]);
+    for scale in [1, 3] {
+        let options = RenderOptions::default().scale(scale as f32).guides(GuideTemplate::PerGlyphBoxes { color });
+        let guides = render_layers_with(&font, "A B", &options).unwrap().guides;
+        let layout = layout_with(&font, "A B", &options).unwrap();
+
+        // Both outlines span the full height, 1px wide at any scale, and the space between the letters gets none
+        for glyph in layout.glyphs.iter().filter(|glyph| glyph.crop_width > 0) {
+            let left = glyph.x as u32 * scale;
+            let right = ((glyph.x as u32 + glyph.crop_width) * scale - 1).min(guides.width() - 1);
+            assert!((0..guides.height()).all(|y| *guides.get_pixel(left, y) == color && *guides.get_pixel(right, y) == color), "{}x", scale);
+        }
+        let boxed_columns = (0..guides.width()).filter(|x| guides.get_pixel(*x, guides.height() / 2)[3] > 0).count();
+        assert_eq!(boxed_columns, 4, "{}x", scale);
+    }
+}
+
+#[test]
+fn no_guides_leave_the_layer_empty() {
+    let guides = render_layers_with(&embedded_font(), "Hi", &RenderOptions::default().guides(GuideTemplate::None)).unwrap().guides;
+    assert!(guides.width() > 0 && guides.pixels().all(|pixel| pixel[3] == 0));
+}
+
+// The highlight is worked out from the glyph placements, with its columns scaled the same way as the
+// text, so they are exactly the text layer's at any scale, whole or not, and with every effect that
+// widens the ink
+#[test]
+fn highlight_edges_match_the_ink_at_any_scale() {
+    let font = embedded_font();
+    let ink_columns = |image: &RgbaImage| -> Vec<u32> {
+        (0..image.width()).filter(|x| (0..image.height()).any(|y| image.get_pixel(*x, y)[3] > 0)).collect()
+    };
+    let effects = [
+        RenderOptions::default(),
+        RenderOptions::default().bold(true),
+        RenderOptions::default().shadow(true),
+        RenderOptions::default().outline(Some(Outline::new(Rgba([0, 0, 0, 255]), 2))),
+        RenderOptions::default().bold(true).shadow(true).outline(Some(Outline::new(Rgba([0, 0, 0, 255]), 1))),
+        RenderOptions::default().gradient(Some((Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 255])))),
+        RenderOptions::default().kerning(true).letter_spacing(Some(-1)),
+    ];
+
+    for (index, options) in effects.into_iter().enumerate() {
+        for scale in [1.5, 2.0, 2.5, 3.0] {
+            let layers = render_layers_with(&font, "AVATAR Hello", &options.clone().scale(scale)).unwrap();
+            let (highlighted, inked) = (ink_columns(&layers.guides), ink_columns(&layers.text));
+            let case = format!("effects {} at {}x", index, scale);
+            assert_eq!(layers.guides.width(), layers.text.width(), "{}", case);
+            assert_eq!((highlighted.first(), highlighted.last()), (inked.first(), inked.last()), "{}", case);
+            assert_eq!(highlighted, inked, "{}", case);
+        }
+    }
+}
+
+// Invisible ink highlights nothing, and neither does a transparent outline around it
+#[test]
+fn transparent_ink_is_not_highlighted() {
+    let font = embedded_font();
+    let hidden = RenderOptions::default().tint(Rgba([255, 255, 255, 0])).outline(Some(Outline::new(Rgba([0, 0, 0, 0]), 2)));
+    let layers = render_layers_with(&font, "HI", &hidden).unwrap();
+    assert!(layers.text.pixels().all(|pixel| pixel[3] == 0));
+    assert!(layers.guides.pixels().all(|pixel| pixel[3] == 0));
+}
@@ -0,0 +1,52 @@
+// Comparing a render with its reference: matching pixels come out dimmed to a third, changed ones
+// red, and pixels only one image has count as changed
+use image::{Rgba, RgbaImage};
+use minecraft_titles::diff_images;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+fn reference() -> RgbaImage {
+    RgbaImage::from_fn(4, 3, |x, y| Rgba([x as u8 * 60, y as u8 * 90, 255, 255]))
+}
+
+fn dimmed(pixel: Rgba<u8>) -> Rgba<u8> {
+    let [r, g, b, a] = pixel.0;
+    let dim = |channel: u8| (channel as u32 * a as u32 / (255 * 3)) as u8;
+    Rgba([dim(r), dim(g), dim(b), 255])
+}
+
+#[test]
+fn identical_images_have_no_changes() {
+    let diff = diff_images(&reference(), &reference());
+    assert_eq!(diff.changed_pixels, 0);
+    assert_eq!(diff.image.dimensions(), (4, 3));
+    assert!(diff.image.enumerate_pixels().all(|(x, y, pixel)| *pixel == dimmed(*reference().get_pixel(x, y))));
+    assert_eq!(*diff.image.get_pixel(3, 2), Rgba([60, 60, 85, 255]));
+}
+
+#[test]
+fn one_changed_pixel_is_marked_red() {
+    let mut actual = reference();
+    actual.put_pixel(2, 1, Rgba([0, 0, 0, 0]));
+
+    let diff = diff_images(&reference(), &actual);
+    assert_eq!(diff.changed_pixels, 1);
+    for (x, y, pixel) in diff.image.enumerate_pixels() {
+        let expected = if (x, y) == (2, 1) { RED } else { dimmed(*reference().get_pixel(x, y)) };
+        assert_eq!(*pixel, expected, "({}, {})", x, y);
+    }
+}
+
+#[test]
+fn pixels_outside_either_image_count_as_changed() {
+    // 2 columns more and a row less than the reference
+    let actual = RgbaImage::from_fn(6, 2, |x, y| reference().get_pixel_checked(x, y).copied().unwrap_or(Rgba([0, 0, 0, 255])));
+
+    let diff = diff_images(&reference(), &actual);
+    assert_eq!(diff.image.dimensions(), (6, 3));
+    assert_eq!(diff.changed_pixels, 6 * 3 - 4 * 2);
+    for (x, y, pixel) in diff.image.enumerate_pixels() {
+        let expected = if x < 4 && y < 2 { dimmed(*reference().get_pixel(x, y)) } else { RED };
+        assert_eq!(*pixel, expected, "({}, {})", x, y);
+    }
+}
@@ -0,0 +1,49 @@
+// kern_across_spaces applies a fraction of a pair's kerning when a single space separates the pair.
+// The embedded font kerns "T-" by -1, so in "T -" it moves the '-' and everything after it
+use minecraft_titles::{RenderOptions, layout_with};
+mod common;
+use common::embedded_font;
+
+// Pen position and kerning of every glyph of `text`
+fn placements(text: &str, options: &RenderOptions) -> Vec<(i64, i64)> {
+    layout_with(&embedded_font(), text, options).unwrap().glyphs.iter().map(|glyph| (glyph.pen_x, glyph.kerning)).collect()
+}
+
+#[test]
+fn off_by_default() {
+    let kerned = RenderOptions::default().kerning(true);
+    assert_eq!(kerned.kern_across_spaces, None);
+    assert!(placements("T -", &kerned).iter().all(|(_, kerning)| *kerning == 0));
+}
+
+#[test]
+fn the_pair_across_the_space_moves_the_cursor() {
+    let plain = placements("T -H", &RenderOptions::default().kerning(true));
+    let across = placements("T -H", &RenderOptions::default().kerning(true).kern_across_spaces(Some(1.0)));
+
+    // Only the '-' is kerned, and the 'H' after it moves along with it
+    assert_eq!(across.iter().map(|(_, kerning)| *kerning).collect::<Vec<_>>(), [0, 0, -1, 0]);
+    assert_eq!(across[..2], plain[..2]);
+    assert_eq!(across[2].0, plain[2].0 - 1);
+    assert_eq!(across[3].0, plain[3].0 - 1);
+}
+
+#[test]
+fn the_fraction_scales_and_rounds_the_pair() {
+    // ".?" kerns by -3
+    let kerning = |fraction: f32| placements(". ?", &RenderOptions::default().kerning(true).kern_across_spaces(Some(fraction)))[2].1;
+    assert_eq!(kerning(1.0), -3);
+    assert_eq!(kerning(0.5), -2);
+    assert_eq!(kerning(0.1), 0);
+    assert_eq!(kerning(0.0), 0);
+}
+
+#[test]
+fn only_a_single_space_is_kerned_across() {
+    let options = RenderOptions::default().kerning(true).kern_across_spaces(Some(1.0));
+    assert!(placements("T  -", &options).iter().all(|(_, kerning)| *kerning == 0));
+    // Without kerning there is nothing to apply
+    assert!(placements("T -", &RenderOptions::default().kern_across_spaces(Some(1.0))).iter().all(|(_, kerning)| *kerning == 0));
+    // The pair itself keeps its full kerning
+    assert_eq!(placements("T-", &options)[1].1, -1);
+}
@@ -0,0 +1,71 @@
+// Pin the widths each layout profile gives the embedded font. Vanilla widths are the sums of the
+// glyphs' xadvance values, plus one pixel per inked glyph when bold, which is how the game measures
+// text at GUI scale 1
+use minecraft_titles::{LayoutProfile, RenderOptions, layout_with, render_layers_with};
+mod common;
+use common::embedded_font;
+
+fn width(text: &str, options: &RenderOptions) -> u32 {
+    layout_with(&embedded_font(), text, options).unwrap().width
+}
+
+fn vanilla() -> RenderOptions {
+    RenderOptions::default().layout_profile(LayoutProfile::Vanilla)
+}
+
+#[test]
+fn vanilla_advances_by_xadvance() {
+    // H 11, e 11, l 7, l 7, o 11
+    assert_eq!(width("Hello", &vanilla()), 47);
+    // H 11, i 7, ! 7
+    assert_eq!(width("Hi!", &vanilla()), 25);
+}
+
+#[test]
+fn vanilla_space_is_its_own_glyph() {
+    // A 11, space 5, B 11
+    assert_eq!(width("A B", &vanilla()), 27);
+}
+
+#[test]
+fn bold_adds_a_pixel_per_inked_glyph() {
+    assert_eq!(width("Hi", &vanilla().bold(true)), 20);
+    assert_eq!(width("A B", &vanilla().bold(true)), 29);
+}
+
+#[test]
+fn shadow_adds_a_column() {
+    assert_eq!(width("Hi", &vanilla().shadow(true)), 19);
+}
+
+#[test]
+fn legacy_keeps_the_trimmed_spacing() {
+    assert_eq!(width("Hello", &RenderOptions::default()), 33);
+}
+
+#[test]
+fn shadow_is_drawn_below_and_right() {
+    let font = embedded_font();
+    let plain = render_layers_with(&font, "I", &vanilla().scale(1.0)).unwrap().text;
+    let shadowed = render_layers_with(&font, "I", &vanilla().scale(1.0).shadow(true)).unwrap().text;
+
+    // Below and right of an inked pixel whose diagonal neighbor is empty, the shadow shows a darker pixel
+    let (x, y) = plain.enumerate_pixels()
+        .find(|(x, y, pixel)| pixel[3] > 0 && *x + 1 < plain.width() && plain.get_pixel(x + 1, y + 1)[3] == 0)
+        .map(|(x, y, _)| (x, y))
+        .unwrap();
+    let shadow = shadowed.get_pixel(x + 1, y + 1);
+    assert!(shadow[3] > 0 && shadow[0] < plain.get_pixel(x, y)[0]);
+}
+
+#[test]
+fn shadow_is_not_clipped_without_padding() {
+    let font = embedded_font();
+    let options = vanilla().vertical_padding(0);
+    let plain = layout_with(&font, "Hg", &options).unwrap();
+    let shadowed = layout_with(&font, "Hg", &options.clone().shadow(true)).unwrap();
+    assert_eq!(shadowed.height, plain.height + 1);
+
+    // With the usual padding the shadow fits in it and the height stays
+    assert_eq!(layout_with(&font, "Hg", &vanilla().shadow(true)).unwrap().height, layout_with(&font, "Hg", &vanilla()).unwrap().height);
+}
@@ -0,0 +1,88 @@
+// A .fnt file cut off partway through loads leniently: the unreadable tail is skipped, the warning
+// says how much was recovered of what the count headers promised, and the binary renders as long as
+// the text only needs characters that survived
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use minecraft_titles::{Font, FontLoadOptions};
+
+const FNT: &str = include_str!("../src/assets/MinecraftDebugger-bitmap.fnt");
+const ATLAS: &[u8] = include_bytes!("../src/assets/MinecraftDebugger-bitmap.png");
+
+// The font file cut off inside the char line for 'S', so it ends on a line that doesn't parse and
+// has lost the rest of the glyphs and the kernings section, count header included
+fn truncated() -> &'static str {
+    let cut = FNT.find("char id=83 ").unwrap() + "char id=83      x=24   y=".len();
+    &FNT[..cut]
+}
+
+fn load(fnt: &str, lenient: bool) -> Result<Font, String> {
+    let options = FontLoadOptions { lenient, ..FontLoadOptions::default() };
+    Font::from_fnt_with(fnt.as_bytes(), vec![image::load_from_memory(ATLAS).unwrap()], &options).map_err(|e| e.to_string())
+}
+
+fn run(dir: &std::path::Path, text: &str, lenient: bool) -> Output {
+    let font = dir.join("damaged.fnt");
+    let out = dir.join("title.png");
+    let mut args = vec!["--text", text, "--font", font.to_str().unwrap(), "--out", out.to_str().unwrap()];
+    if lenient {
+        args.push("--lenient");
+    }
+    Command::new(env!("CARGO_BIN_EXE_rust_bitmap_renderer")).args(args).output().unwrap()
+}
+
+fn damaged_font_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("damaged.fnt"), truncated()).unwrap();
+    fs::write(dir.join("MinecraftDebugger-bitmap.png"), ATLAS).unwrap();
+    dir
+}
+
+#[test]
+fn a_strict_load_fails() {
+    assert!(load(truncated(), false).is_err());
+    assert!(!load(FNT, false).unwrap().is_partial());
+}
+
+#[test]
+fn a_lenient_load_reports_what_was_recovered() {
+    let font = load(truncated(), true).unwrap();
+    let recovered = truncated().lines().filter(|line| line.starts_with("char ")).count() - 1;
+    assert!(font.is_partial());
+    assert_eq!(font.chars().count(), recovered);
+    assert!(font.has('R') && !font.has('S') && !font.has('a'));
+    assert_eq!(font.kerning('L', 'T'), 0);
+
+    let warning = font.warnings().iter().find(|warning| warning.starts_with("The font file looks damaged")).unwrap();
+    let expected = format!("The font file looks damaged; recovered {} of 250 chars and 0 kerning pairs, skipping 1 unreadable line (first at line {}: ", recovered, recovered + 5);
+    assert!(warning.starts_with(&expected), "{}", warning);
+
+    // A complete file loaded leniently has nothing to report
+    let complete = load(FNT, true).unwrap();
+    assert!(!complete.is_partial());
+    assert!(complete.warnings().iter().all(|warning| !warning.contains("damaged")));
+}
+
+#[test]
+fn the_binary_renders_text_the_recovered_glyphs_cover() {
+    let dir = damaged_font_dir("the_binary_renders_text_the_recovered_glyphs_cover");
+    let output = run(&dir, "HELLO 123", true);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("The font file looks damaged"));
+    assert!(dir.join("title.png").exists());
+
+    // Without --lenient the same file is an error
+    assert!(!run(&dir, "HELLO 123", false).status.success());
+}
+
+#[test]
+fn the_binary_refuses_text_that_needs_lost_glyphs() {
+    let dir = damaged_font_dir("the_binary_refuses_text_that_needs_lost_glyphs");
+    let output = run(&dir, "BEST WISHES", true);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("The font file is damaged and lacks 'S' (U+0053), 'T' (U+0054), 'W' (U+0057) needed for this text"), "{}", stderr);
+    assert!(!dir.join("title.png").exists());
+}
@@ -0,0 +1,34 @@
+// Letter spacing moves every character after the first, and the canvas grows or shrinks with it
+use minecraft_titles::{RenderOptions, measure_text, render_text, render_text_with};
+mod common;
+use common::embedded_font;
+
+#[test]
+fn zero_spacing_is_unchanged() {
+    let font = embedded_font();
+    let spaced = render_text_with(&font, "Hello World", &RenderOptions::default().kerning(true).letter_spacing(Some(0))).unwrap();
+    let plain = render_text(&font, "Hello World", true, None, 1.5, false, &Default::default()).unwrap();
+    assert_eq!(spaced, plain);
+}
+
+#[test]
+fn spacing_is_added_between_characters() {
+    let font = embedded_font();
+    let unscaled = RenderOptions::default().scale(1.0);
+    let plain = measure_text(&font, "HELLO", &unscaled).unwrap();
+    let loose = measure_text(&font, "HELLO", &unscaled.clone().letter_spacing(Some(3))).unwrap();
+    let tight = measure_text(&font, "HELLO", &unscaled.clone().letter_spacing(Some(-1))).unwrap();
+
+    assert_eq!(loose.width, plain.width + 4 * 3);
+    assert_eq!(tight.width, plain.width - 4);
+    assert_eq!(render_text_with(&font, "HELLO", &unscaled.letter_spacing(Some(3))).unwrap().width(), loose.width);
+}
+
+#[test]
+fn tight_spacing_still_moves_the_pen() {
+    let metrics = measure_text(&embedded_font(), "HELLO WORLD", &RenderOptions::default().letter_spacing(Some(-100))).unwrap();
+
+    for pair in metrics.positions.windows(2) {
+        assert_eq!(pair[1].x, pair[0].x + 1);
+    }
+}
@@ -0,0 +1,34 @@
+// measure_text has to agree with what render_text_with actually draws
+use minecraft_titles::{LayoutProfile, RenderOptions, measure_text, render_text_with};
+mod common;
+use common::embedded_font;
+
+#[test]
+fn measured_size_matches_the_render() {
+    let font = embedded_font();
+    let options = [
+        RenderOptions::default(),
+        RenderOptions::default().kerning(true).scale(1.0),
+        RenderOptions::default().letter_spacing(Some(2)).vertical_padding(1),
+        RenderOptions::default().layout_profile(LayoutProfile::Vanilla).bold(true).shadow(true),
+    ];
+
+    for options in &options {
+        for text in ["HELLO", "Hello World", "WAVY AV", "i"] {
+            let metrics = measure_text(&font, text, options).unwrap();
+            let image = render_text_with(&font, text, options).unwrap();
+            assert_eq!((metrics.width, metrics.height), image.dimensions(), "{:?} with {:?}", text, options);
+        }
+    }
+}
+
+#[test]
+fn positions_follow_the_pen() {
+    let metrics = measure_text(&embedded_font(), "AB C", &RenderOptions::default()).unwrap();
+    let chars: String = metrics.positions.iter().map(|position| position.ch).collect();
+    assert_eq!(chars, "AB C");
+
+    for pair in metrics.positions.windows(2) {
+        assert_eq!(pair[1].x, pair[0].x + pair[0].advance as i64);
+    }
+}
@@ -0,0 +1,76 @@
+// A character the font lacks is drawn with glyph 0, else with '?', else skipped with the average
+// advance so words keep their spacing. Either way it is listed in the layout's missing characters
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderError, RenderOptions, layout_with, render_layers_with};
+
+// 'A' and 'B' advance 6 and 8 px, so the average advance of the glyphs with ink is 7
+const LETTERS: &str = "\
+info face=\"Fallbacks\" size=5 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=24 scaleH=5 pages=1
+page id=0 file=\"fallbacks.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=66 x=6 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=8 page=0
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+";
+const GLYPH_ZERO: &str = "char id=0 x=12 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=9 page=0\n";
+const QUESTION_MARK: &str = "char id=63 x=18 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=10 page=0\n";
+
+fn load(extra_glyphs: &[&str]) -> Font {
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_pixel(24, 5, Rgba([255, 255, 255, 255])));
+    let options = FontLoadOptions { glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }, ..FontLoadOptions::default() };
+    Font::from_fnt_with(format!("{}{}", LETTERS, extra_glyphs.concat()).as_bytes(), vec![atlas], &options).unwrap()
+}
+
+// Source column, pen position and advance of every placed glyph, and the pen after the last one
+fn placements(font: &Font, text: &str) -> (Vec<(u32, i64, u32)>, u32) {
+    let text_layout = layout_with(font, text, &RenderOptions::default()).unwrap();
+    (text_layout.glyphs.iter().map(|glyph| (glyph.crop_x, glyph.pen_x, glyph.advance)).collect(), text_layout.width)
+}
+
+#[test]
+fn glyph_zero_comes_first() {
+    let font = load(&[GLYPH_ZERO, QUESTION_MARK]);
+    assert_eq!(placements(&font, "AéB"), (vec![(0, 0, 6), (12, 6, 9), (6, 15, 8)], 23));
+
+    let text_layout = layout_with(&font, "AéB", &RenderOptions::default()).unwrap();
+    let missing: Vec<bool> = text_layout.glyphs.iter().map(|glyph| glyph.source.missing_glyph).collect();
+    assert_eq!(missing, [false, true, false]);
+    // The placement keeps the character the text asked for
+    assert_eq!(text_layout.glyphs[1].char_id, 'é' as u32);
+}
+
+#[test]
+fn then_the_question_mark() {
+    let font = load(&[QUESTION_MARK]);
+    assert_eq!(placements(&font, "AéB"), (vec![(0, 0, 6), (18, 6, 10), (6, 16, 8)], 24));
+}
+
+#[test]
+fn then_the_average_advance() {
+    let font = load(&[]);
+    // Nothing is drawn for the 'é', but the 'B' still starts 7 px after it
+    assert_eq!(placements(&font, "AéB"), (vec![(0, 0, 6), (6, 13, 8)], 21));
+    let text_layer = render_layers_with(&font, "AéB", &RenderOptions::default().scale(1.0)).unwrap().text;
+    assert!((6..13).all(|x| (0..text_layer.height()).all(|y| text_layer.get_pixel(x, y)[3] == 0)));
+}
+
+#[test]
+fn missing_characters_are_listed_once_in_order() {
+    for font in [load(&[GLYPH_ZERO]), load(&[QUESTION_MARK]), load(&[])] {
+        let text_layout = layout_with(&font, "éAüé😀B", &RenderOptions::default()).unwrap();
+        assert_eq!(text_layout.missing, ['é', 'ü', '😀']);
+    }
+    assert!(layout_with(&load(&[]), "AB A", &RenderOptions::default()).unwrap().missing.is_empty());
+}
+
+#[test]
+fn text_that_is_all_missing_does_not_panic() {
+    // A fallback glyph gets drawn
+    let drawn = render_layers_with(&load(&[QUESTION_MARK]), "éü", &RenderOptions::default()).unwrap();
+    assert!(drawn.text.width() > 0);
+
+    // Skipping leaves nothing to draw, which is an error rather than a zero-width image
+    let error = render_layers_with(&load(&[]), "éü", &RenderOptions::default()).map(|_| ()).unwrap_err();
+    assert!(matches!(error.downcast_ref::<RenderError>(), Some(RenderError::NothingToRender)));
+    assert_eq!(layout_with(&load(&[]), "éü", &RenderOptions::default()).unwrap().missing, ['é', 'ü']);
+}
@@ -0,0 +1,52 @@
+// Glyphs split across several atlas pages are cropped from the page their char line names, and the
+// page files come back in page id order
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderOptions, page_files, render_layers_with};
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+// 'A' and 'B' use the same rect on different pages, and the page lines are out of order
+const TWO_PAGE_FNT: &str = "\
+info face=\"Two pages\" size=5 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=8 scaleH=5 pages=2
+page id=1 file=\"two_pages_1.png\"
+page id=0 file=\"two_pages_0.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=66 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=1
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+";
+
+fn page(color: Rgba<u8>) -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 5, color))
+}
+
+fn load(pages: Vec<DynamicImage>) -> Result<Font, String> {
+    let options = FontLoadOptions { glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }, ..FontLoadOptions::default() };
+    Font::from_fnt_with(TWO_PAGE_FNT.as_bytes(), pages, &options).map_err(|e| e.to_string())
+}
+
+#[test]
+fn page_files_are_in_id_order() {
+    assert_eq!(page_files(TWO_PAGE_FNT.as_bytes()).unwrap(), ["two_pages_0.png", "two_pages_1.png"]);
+    let font = load(vec![page(RED), page(BLUE)]).unwrap();
+    assert_eq!(font.page_files(), ["two_pages_0.png", "two_pages_1.png"]);
+    assert_eq!((font.glyph('A').unwrap().page(), font.glyph('B').unwrap().page()), (0, 1));
+}
+
+#[test]
+fn each_glyph_is_cropped_from_its_own_page() {
+    let font = load(vec![page(RED), page(BLUE)]).unwrap();
+    let text_layer = render_layers_with(&font, "AB A", &RenderOptions::default().scale(1.0)).unwrap().text;
+
+    let color_at = |x: u32| (0..text_layer.height()).map(|y| *text_layer.get_pixel(x, y)).find(|pixel| pixel[3] > 0);
+    for (columns, color) in [(0..5, RED), (6..11, BLUE), (15..20, RED)] {
+        assert!(columns.clone().all(|x| color_at(x) == Some(color)), "{:?}", columns);
+    }
+    assert!([5, 11, 12, 13, 14].iter().all(|x| color_at(*x).is_none()));
+}
+
+#[test]
+fn every_page_needs_an_image() {
+    assert_eq!(load(vec![page(RED)]).err().unwrap(), "Error: glyph U+0042 is on page 1 but only 1 page image was given");
+}
@@ -0,0 +1,56 @@
+// Text that would come out as a blank texture is a NothingToRender error instead of a zero-width
+// image, and characters the font lacks are listed either way
+use minecraft_titles::{Font, FontLoadOptions, RenderError, RenderOptions, layout_with, render_layers_with, render_text_with};
+mod common;
+use common::embedded_font;
+
+fn without_fallback() -> Font {
+    let atlas = image::load_from_memory(include_bytes!("../src/assets/MinecraftDebugger-bitmap.png")).unwrap();
+    let options = FontLoadOptions { missing_glyph_fallback: false, ..FontLoadOptions::default() };
+    Font::from_fnt_with(include_bytes!("../src/assets/MinecraftDebugger-bitmap.fnt"), vec![atlas], &options).unwrap()
+}
+
+fn nothing_to_render(font: &Font, text: &str) -> bool {
+    let render_error = render_text_with(font, text, &RenderOptions::default()).unwrap_err();
+    let layers_error = render_layers_with(font, text, &RenderOptions::default()).map(|_| ()).unwrap_err();
+    [render_error, layers_error].iter().all(|error| matches!(error.downcast_ref::<RenderError>(), Some(RenderError::NothingToRender)))
+}
+
+#[test]
+fn empty_text() {
+    let font = embedded_font();
+    let text_layout = layout_with(&font, "", &RenderOptions::default()).unwrap();
+    assert!(text_layout.glyphs.is_empty());
+    assert_eq!(text_layout.width, 0);
+    assert!(text_layout.missing.is_empty());
+    assert!(nothing_to_render(&font, ""));
+}
+
+#[test]
+fn whitespace_only_text() {
+    let font = embedded_font();
+    for text in [" ", "   ", "\t \t"] {
+        let text_layout = layout_with(&font, text, &RenderOptions::default()).unwrap();
+        assert!(text_layout.glyphs.iter().all(|glyph| glyph.crop_width == 0 || glyph.crop_height == 0), "{:?}", text);
+        assert!(text_layout.missing.is_empty());
+        assert!(nothing_to_render(&font, text), "{:?}", text);
+    }
+}
+
+#[test]
+fn text_the_font_has_no_glyphs_for() {
+    // With the fallback the missing characters are drawn as the font's missing glyph
+    let font = embedded_font();
+    let text_layout = layout_with(&font, "日本日", &RenderOptions::default()).unwrap();
+    assert_eq!(text_layout.missing, ['日', '本']);
+    assert!(text_layout.glyphs.iter().all(|glyph| glyph.source.missing_glyph));
+    assert!(render_text_with(&font, "日本日", &RenderOptions::default()).unwrap().width() > 0);
+
+    // Without it there is nothing to draw, and the missing list still says why
+    let font = without_fallback();
+    let text_layout = layout_with(&font, "日本日", &RenderOptions::default()).unwrap();
+    assert_eq!(text_layout.missing, ['日', '本']);
+    assert!(text_layout.glyphs.is_empty());
+    assert!(nothing_to_render(&font, "日本日"));
+    assert_eq!(RenderError::NothingToRender.to_string(), "the text has no characters the font can draw");
+}
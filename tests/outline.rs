@@ -0,0 +1,53 @@
+// The outline: a ring of its color around the ink, beneath the text, with the canvas grown to fit
+use image::Rgba;
+use minecraft_titles::{Outline, RenderOptions, layout_with, render_layers_with};
+mod common;
+use common::embedded_font;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+#[test]
+fn canvas_grows_by_the_thickness_on_every_side() {
+    let font = embedded_font();
+    let plain = layout_with(&font, "Hello", &RenderOptions::default()).unwrap();
+    for thickness in 1..=3 {
+        let outlined = layout_with(&font, "Hello", &RenderOptions::default().outline(Some(Outline::new(RED, thickness)))).unwrap();
+        assert_eq!(outlined.width, plain.width + 2 * thickness);
+        assert_eq!(outlined.height, plain.height + 2 * thickness);
+        assert_eq!(outlined.baseline, plain.baseline + thickness as i32);
+        assert_eq!(outlined.glyphs[0].x, plain.glyphs[0].x + thickness as i64);
+    }
+}
+
+#[test]
+fn outline_surrounds_the_ink_beneath_the_text() {
+    let font = embedded_font();
+    let plain = render_layers_with(&font, "I", &RenderOptions::default().scale(1.0)).unwrap().text;
+    let outlined = render_layers_with(&font, "I", &RenderOptions::default().scale(1.0).outline(Some(Outline::new(RED, 2)))).unwrap().text;
+
+    for (x, y, pixel) in plain.enumerate_pixels() {
+        let moved = outlined.get_pixel(x + 2, y + 2);
+        if pixel[3] == 255 {
+            assert_eq!(moved, pixel, "the text covers its own outline at {},{}", x, y);
+        }
+    }
+    // Two columns left of the leftmost ink, in an inked row, the outline shows
+    let (x, y) = plain.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] == 255).map(|(x, y, _)| (x, y)).min().unwrap();
+    assert_eq!(*outlined.get_pixel(x, y + 2), RED);
+    assert_eq!(outlined.get_pixel(0, 0)[3], 0);
+}
+
+#[test]
+fn highlight_covers_the_outline() {
+    let layers = render_layers_with(&embedded_font(), "Hi", &RenderOptions::default().outline(Some(Outline::new(RED, 1)))).unwrap();
+    let columns = |image: &image::RgbaImage| -> Vec<u32> {
+        (0..image.width()).filter(|x| (0..image.height()).any(|y| image.get_pixel(*x, y)[3] > 0)).collect()
+    };
+    assert_eq!(columns(&layers.guides), columns(&layers.text));
+}
+
+#[test]
+fn thickness_is_clamped() {
+    assert_eq!(Outline::new(RED, 0).thickness(), 1);
+    assert_eq!(Outline::new(RED, 9).thickness(), 3);
+}
@@ -0,0 +1,86 @@
+// Dimensions are accumulated in 64 bits and only converted back once they are known to fit, so
+// a canvas past u32 or past what an RGBA buffer can hold is an OutputTooLarge error with the size
+// it would have had, rather than a wrapped size or a panic in the image crate. Each case is just
+// under and just over one limit, with a font whose advances and line height are that large
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, RenderError, RenderLimits, RenderOptions, layout_with, measure_text, render_layers_with};
+
+// 'A' and 'B' are single pixels that move the pen as far as asked
+fn huge_font(line_height: u64, a_advance: u64, b_advance: u64) -> Font {
+    let fnt = format!("\
+info face=\"Huge\" size=1 padding=0,0,0,0
+common lineHeight={} base=1 scaleW=8 scaleH=8 pages=1
+page id=0 file=\"huge.png\"
+char id=65 x=1 y=1 width=1 height=1 xoffset=0 yoffset=0 xadvance={} page=0
+char id=66 x=3 y=1 width=1 height=1 xoffset=0 yoffset=0 xadvance={} page=0", line_height, a_advance, b_advance);
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255])));
+    let options = FontLoadOptions { standard_vertical_metrics: true, ..FontLoadOptions::default() };
+    Font::from_fnt_with(fnt.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+fn unpadded() -> RenderOptions {
+    RenderOptions::default().vertical_padding(0)
+}
+
+// The advance for 'A' that makes "AB" `width` wide next to a 'B' advance of 10, whatever the layout
+// takes off each advance
+fn a_advance(width: u64) -> u64 {
+    let beyond_advances = layout_with(&huge_font(6, 10, 10), "AB", &unpadded()).unwrap().width as i64 - 20;
+    (width as i64 - 10 - beyond_advances) as u64
+}
+
+fn too_large(result: Result<(u32, u32), RenderError>) -> Option<(u64, u64)> {
+    match result {
+        Err(RenderError::OutputTooLarge { width, height }) => Some((width, height)),
+        _ => None,
+    }
+}
+
+fn layout_size(font: &Font, text: &str, options: &RenderOptions) -> Result<(u32, u32), RenderError> {
+    layout_with(font, text, options).map(|text_layout| (text_layout.width, text_layout.height))
+}
+
+#[test]
+fn width_past_u32() {
+    let font = huge_font(6, a_advance(u32::MAX as u64), 10);
+    assert_eq!(layout_size(&font, "AB", &unpadded()).unwrap(), (u32::MAX, 6));
+    let font = huge_font(6, a_advance(1 << 32), 10);
+    assert_eq!(too_large(layout_size(&font, "AB", &unpadded())), Some((1 << 32, 6)));
+}
+
+#[test]
+fn height_past_u32() {
+    // Padding adds a row above and below the line
+    let padded = RenderOptions::default().vertical_padding(1);
+    assert_eq!(layout_size(&huge_font(u32::MAX as u64 - 2, 1, 1), "A", &padded).unwrap().1, u32::MAX);
+    assert_eq!(too_large(layout_size(&huge_font(u32::MAX as u64 - 1, 1, 1), "A", &padded)), Some((0, 1 << 32)));
+}
+
+#[test]
+fn buffer_length_past_isize() {
+    // 2^31 rows of 2^30 four-byte pixels is one byte more than isize::MAX
+    let font = huge_font(1 << 31, a_advance((1 << 30) - 1), 10);
+    assert_eq!(layout_size(&font, "AB", &unpadded()).unwrap(), ((1 << 30) - 1, 1 << 31));
+    let font = huge_font(1 << 31, a_advance(1 << 30), 10);
+    assert_eq!(too_large(layout_size(&font, "AB", &unpadded())), Some((1 << 30, 1 << 31)));
+
+    // Both sides at u32::MAX overflow the byte count in 64 bits as well
+    let font = huge_font(u32::MAX as u64, a_advance(u32::MAX as u64), 10);
+    assert_eq!(too_large(layout_size(&font, "AB", &unpadded())), Some((u32::MAX as u64, u32::MAX as u64)));
+}
+
+#[test]
+fn scaling_the_text_past_u32() {
+    let unlimited = RenderLimits { max_chars: usize::MAX, max_width: u32::MAX, max_height: u32::MAX, max_pixels: u64::MAX };
+    let scaled = unpadded().scale(4.0).limits(unlimited);
+
+    let font = huge_font(6, a_advance((1 << 30) - 1), 10);
+    let metrics = measure_text(&font, "AB", &scaled).unwrap();
+    assert_eq!((metrics.width, metrics.height), (u32::MAX - 3, 24));
+
+    // The scaled size is refused before any layer is drawn
+    let font = huge_font(6, a_advance(1 << 30), 10);
+    assert_eq!(too_large(measure_text(&font, "AB", &scaled).map(|metrics| (metrics.width, metrics.height))), Some((1 << 32, 24)));
+    let error = render_layers_with(&font, "AB", &scaled).map(|_| ()).unwrap_err();
+    assert_eq!(error.to_string(), "output would be 4,294,967,296 x 24 px, which is too large to allocate");
+}
@@ -0,0 +1,84 @@
+// Narrow glyphs keep the columns their rects hold. The fixture atlas is red everywhere outside the
+// glyph rects, so a crop that slips past a rect or keeps the wrong column shows up as red or as a
+// missing column. Inside the rects, '#' is ink and '.' is transparent padding
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, RenderOptions, layout_with, rasterize_glyphs};
+
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+// Each glyph's char id, x, rect rows and how many columns it should come out as. '.' and ':' have
+// a padding column on each side; ''' is a single column and '|' two, with no room for padding
+const GLYPHS: [(char, u32, [&str; 5], u32); 4] = [
+    ('.', 1, ["...", "...", "...", "...", ".#."], 1),
+    (':', 6, ["....", ".##.", "....", "....", ".##."], 2),
+    ('\'', 12, ["#", "#", ".", ".", "."], 1),
+    ('|', 15, ["#.", "#.", "#.", "#.", "#."], 2),
+];
+
+fn atlas() -> DynamicImage {
+    let mut atlas = RgbaImage::from_pixel(20, 7, RED);
+    for (_, x, rows, _) in GLYPHS {
+        for (row, line) in rows.iter().enumerate() {
+            for (column, cell) in line.chars().enumerate() {
+                let pixel = if cell == '#' { WHITE } else { Rgba([0, 0, 0, 0]) };
+                atlas.put_pixel(x + column as u32, 1 + row as u32, pixel);
+            }
+        }
+    }
+    DynamicImage::ImageRgba8(atlas)
+}
+
+// `padding` is the info line's padding field, if any. Every advance is 3 more than the columns the
+// glyph should come out as, which the default trim takes off again
+fn fixture_font(padding: Option<&str>) -> Font {
+    let mut fnt = format!("info face=\"Punctuation\" size=5{}\n", padding.map(|padding| format!(" padding={}", padding)).unwrap_or_default());
+    fnt.push_str("common lineHeight=5 base=5 scaleW=20 scaleH=7 pages=1\npage id=0 file=\"punctuation.png\"\n");
+    for (ch, x, rows, columns) in GLYPHS {
+        fnt.push_str(&format!("char id={} x={} y=1 width={} height=5 xoffset=0 yoffset=0 xadvance={} page=0\n", ch as u32, x, rows[0].len(), columns + 3));
+    }
+    let options = FontLoadOptions { standard_vertical_metrics: true, ..FontLoadOptions::default() };
+    Font::from_fnt_with(fnt.as_bytes(), vec![atlas()], &options).unwrap()
+}
+
+// The text layer as rows of '#', '.' and 'R' for anything read from outside a rect
+fn rendered(font: &Font, text: &str) -> Vec<String> {
+    let text_layout = layout_with(font, text, &RenderOptions::default().vertical_padding(0)).unwrap();
+    let layer = rasterize_glyphs(&text_layout, font);
+    (0..layer.height())
+        .map(|y| (0..layer.width()).map(|x| match *layer.get_pixel(x, y) {
+            WHITE => '#',
+            RED => 'R',
+            _ => '.',
+        }).collect())
+        .collect()
+}
+
+#[test]
+fn padded_glyphs_keep_their_ink_columns() {
+    let font = fixture_font(Some("0,1,0,1"));
+    assert_eq!(rendered(&font, "."), [".", ".", ".", ".", "#"]);
+    assert_eq!(rendered(&font, ":"), ["..", "##", "..", "..", "##"]);
+}
+
+#[test]
+fn one_and_two_column_rects_are_kept_whole() {
+    for font in [fixture_font(Some("0,1,0,1")), fixture_font(None)] {
+        assert_eq!(rendered(&font, "'"), ["#", "#", ".", ".", "."]);
+        assert_eq!(rendered(&font, "|"), ["#.", "#.", "#.", "#.", "#."]);
+    }
+}
+
+#[test]
+fn no_declared_padding_is_never_trimmed() {
+    let font = fixture_font(Some("0,0,0,0"));
+    // The advances leave room for only part of the rect, so the padding overhangs the pen
+    assert_eq!(rendered(&font, "."), ["...", "...", "...", "...", ".#."]);
+    assert_eq!(rendered(&font, "'"), ["#", "#", ".", ".", "."]);
+}
+
+#[test]
+fn punctuation_in_a_row() {
+    let font = fixture_font(Some("0,1,0,1"));
+    assert_eq!(rendered(&font, ".:'|"), ["...##.", ".####.", "....#.", "....#.", "###.#."]);
+}
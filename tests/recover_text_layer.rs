@@ -0,0 +1,79 @@
+// extract_text_layer recovers what the pipeline drew over the background from a texture the binary
+// saved: taking away a freshly tiled UV checker leaves exactly the rendered layers, and putting them
+// back over it gives the texture again
+use std::path::PathBuf;
+use std::process::Command;
+use image::{Rgba, RgbaImage, imageops};
+use minecraft_titles::{BackgroundOptions, BackgroundSource, RenderOptions, extract_text_layer, generate_background, render_text_with};
+mod common;
+use common::embedded_font;
+
+const TOLERANCE: u8 = 8;
+
+// A texture of `text` saved by the binary with its default settings
+fn saved_texture(text: &str, name: &str) -> RgbaImage {
+    let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.png", name));
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_bitmap_renderer")).args(["--text", text, "--out", out.to_str().unwrap()]).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    image::open(&out).unwrap().to_rgba8()
+}
+
+fn checker_under(texture: &RgbaImage) -> RgbaImage {
+    let checker = image::load_from_memory(include_bytes!("../src/assets/uv_checker.png")).unwrap();
+    generate_background(&BackgroundSource::Image(&checker), texture.width(), texture.height(), &BackgroundOptions::default()).unwrap()
+}
+
+fn alpha_mask(image: &RgbaImage) -> Vec<(u32, u32)> {
+    image.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] > 0).map(|(x, y, _)| (x, y)).collect()
+}
+
+#[test]
+fn recovers_the_rendered_layers() {
+    let texture = saved_texture("HELLO", "recovers_the_rendered_layers");
+    let recovered = extract_text_layer(&texture, &checker_under(&texture), TOLERANCE);
+
+    // The binary renders at 1.5x guides and places the layers a column left of the background's edge
+    let rendered = render_text_with(&embedded_font(), "HELLO", &RenderOptions::default().scale(1.5)).unwrap();
+    let mut placed = RgbaImage::new(texture.width(), texture.height());
+    imageops::overlay(&mut placed, &rendered, -1, 0);
+    assert_eq!(alpha_mask(&recovered), alpha_mask(&placed));
+
+    // Opaque ink comes back as it was drawn
+    for (x, y, pixel) in placed.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] == 255) {
+        assert_eq!(recovered.get_pixel(x, y), pixel);
+    }
+}
+
+#[test]
+fn the_recovered_layer_recomposes_the_texture() {
+    let texture = saved_texture("PLAY GAME 123!?", "the_recovered_layer_recomposes_the_texture");
+    let background = checker_under(&texture);
+
+    let mut recomposed = background.clone();
+    imageops::overlay(&mut recomposed, &extract_text_layer(&texture, &background, 0), 0, 0);
+    // The guides were blended over the checker once already, and blending rounds, so the recovered
+    // pixels come back over it at most a level off
+    for (x, y, pixel) in recomposed.enumerate_pixels() {
+        assert!(pixel.0.iter().zip(texture.get_pixel(x, y).0).all(|(a, b)| a.abs_diff(b) <= 1), "({}, {})", x, y);
+    }
+}
+
+#[test]
+fn tolerance_absorbs_noise_in_the_background() {
+    let texture = saved_texture("HELLO", "tolerance_absorbs_noise_in_the_background");
+    let background = checker_under(&texture);
+    let clean = extract_text_layer(&texture, &background, TOLERANCE);
+
+    // Shift every background pixel by a few levels, the way lossy re-encoding would
+    let mut noisy = texture.clone();
+    for (x, y, pixel) in noisy.enumerate_pixels_mut() {
+        if pixel == background.get_pixel(x, y) {
+            let shift = |channel: u8| if (x + y) % 2 == 0 { channel.saturating_add(3) } else { channel.saturating_sub(3) };
+            *pixel = Rgba([shift(pixel[0]), shift(pixel[1]), shift(pixel[2]), pixel[3]]);
+        }
+    }
+
+    assert!(extract_text_layer(&noisy, &background, TOLERANCE) == clean);
+    assert!(alpha_mask(&extract_text_layer(&noisy, &background, 2)).len() > alpha_mask(&clean).len());
+}
+
@@ -0,0 +1,82 @@
+// Text over the safety limits fails with the limit it broke, and fails before the canvas is
+// allocated. The allocator below remembers the largest block each thread asked for, so a test can
+// see what a failed render allocated on its way to the error
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use minecraft_titles::{RenderLimits, RenderOptions, layout_with, render_layers_with};
+mod common;
+use common::embedded_font;
+
+struct LargestAllocation;
+
+thread_local! {
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for LargestAllocation {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(layout.size())));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAllocation = LargestAllocation;
+
+// The error `render` returns, and the largest block allocated while it ran
+fn largest_allocation<T>(render: impl FnOnce() -> T) -> (T, usize) {
+    LARGEST.with(|largest| largest.set(0));
+    let result = render();
+    (result, LARGEST.with(|largest| largest.get()))
+}
+
+#[test]
+fn pasted_text_over_the_character_limit_fails_before_it_is_copied() {
+    let font = embedded_font();
+    let text = "W".repeat(100_000);
+
+    let (result, largest) = largest_allocation(|| render_layers_with(&font, &text, &RenderOptions::default()).map(|_| ()));
+    assert_eq!(result.unwrap_err().to_string(), "text has 100,000 characters; limit is 4,096");
+    assert!(largest < text.len(), "allocated {} bytes", largest);
+
+    // The limit itself is allowed, so that much text goes on to the canvas check
+    let (result, _) = largest_allocation(|| render_layers_with(&font, &text[..4096], &RenderOptions::default()).map(|_| ()));
+    assert!(result.unwrap_err().to_string().contains("px wide; limit is 16,384"));
+}
+
+#[test]
+fn a_canvas_over_the_limits_fails_before_it_is_allocated() {
+    let font = embedded_font();
+    let text = "W".repeat(4096);
+    let text_layout = layout_with(&font, &text, &RenderOptions::default()).unwrap();
+    let canvas_bytes = text_layout.width as usize * text_layout.height as usize * 4;
+
+    let (result, largest) = largest_allocation(|| render_layers_with(&font, &text, &RenderOptions::default()).map(|_| ()));
+    let expected = format!("output would be {},{:03} px wide; limit is 16,384", text_layout.width / 1000, text_layout.width % 1000);
+    assert_eq!(result.unwrap_err().to_string(), expected);
+    // Measuring the text allocates its layout, but nothing the size of the canvas
+    assert!(largest < canvas_bytes / 4, "allocated {} bytes for a {} byte canvas", largest, canvas_bytes);
+
+    // Raising the limit lets the same text through
+    let raised = RenderLimits { max_width: text_layout.width, ..RenderLimits::default() };
+    let layers = render_layers_with(&font, &text, &RenderOptions::default().scale(1.0).limits(raised)).unwrap();
+    assert_eq!(layers.text.width(), text_layout.width);
+}
+
+#[test]
+fn checks_allow_exactly_the_limit() {
+    let limits = RenderLimits::default();
+    assert!(limits.check_text(&"A".repeat(4096)).is_ok());
+    assert_eq!(limits.check_text(&"A".repeat(4097)).unwrap_err().to_string(), "text has 4,097 characters; limit is 4,096");
+
+    assert!(limits.check_canvas(16_384, 1).is_ok());
+    assert_eq!(limits.check_canvas(16_385, 1).unwrap_err().to_string(), "output would be 16,385 px wide; limit is 16,384");
+    assert!(limits.check_canvas(1, 16_384).is_ok());
+    assert_eq!(limits.check_canvas(1, 16_385).unwrap_err().to_string(), "output would be 16,385 px high; limit is 16,384");
+    assert!(limits.check_canvas(4096, 4096).is_ok());
+    assert_eq!(limits.check_canvas(4096, 4097).unwrap_err().to_string(), "output would have 16,781,312 pixels; limit is 16,777,216");
+}
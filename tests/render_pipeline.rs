@@ -0,0 +1,151 @@
+// Each step of render_text_with on its own, and the whole pipeline against images the baseline
+// commit's render_text made. That render_text measured every character with xadvance - 2, without
+// kerning, but drew it with xadvance - 3 and kerning, so its images ended in empty columns that only
+// the red baseline reached. The golden images are its output with those columns cropped; the
+// pipeline must match every other pixel, which is why the baseline is drawn here. It scaled the guides
+// alone, so these render with the legacy guide scale
+use image::{Rgba, RgbaImage, imageops};
+use minecraft_titles::{GuideTemplate, RenderOptions, baseline_guide, compose, layout_with, measure_text, rasterize_glyphs, render_guides, scale_nearest, render_layers_with, render_layout_layers, render_text_with};
+mod common;
+use common::{embedded_font, assert_golden};
+
+fn inked_columns(layer: &RgbaImage) -> Vec<u32> {
+    (0..layer.width()).filter(|x| (0..layer.height()).any(|y| layer.get_pixel(*x, y)[3] > 0)).collect()
+}
+
+#[test]
+fn legacy_output_matches_the_baseline_commit() {
+    let font = embedded_font();
+    let options = RenderOptions::default().legacy_guide_scale(true).draw_baseline(true);
+    assert_golden("baseline_commit_hello_world.png", &render_text_with(&font, "HELLO WORLD", &options).unwrap());
+    assert_golden("baseline_commit_kerned.png", &render_text_with(&font, "T-SHIRT .? AV", &options.kerning(true).scale(1.0)).unwrap());
+}
+
+#[test]
+fn layout_moves_the_pen_by_each_advance_and_kerning() {
+    let text_layout = layout_with(&embedded_font(), "T-SHIRT .?", &RenderOptions::default().kerning(true)).unwrap();
+
+    assert_eq!(text_layout.glyphs.len(), 10);
+    assert_eq!(text_layout.glyphs[0].pen_x, 0);
+    for pair in text_layout.glyphs.windows(2) {
+        assert_eq!(pair[1].pen_x, pair[0].pen_x + pair[0].advance as i64 + pair[1].kerning);
+    }
+    assert!(text_layout.glyphs.iter().any(|glyph| glyph.kerning < 0));
+    let last = text_layout.glyphs.last().unwrap();
+    assert_eq!(text_layout.width as i64, (last.pen_x + last.advance as i64).max(last.x + last.crop_width as i64));
+}
+
+#[test]
+fn rasterizing_copies_each_glyph_from_the_atlas() {
+    let font = embedded_font();
+    let text_layout = layout_with(&font, "HELLO", &RenderOptions::default()).unwrap();
+    let text_layer = rasterize_glyphs(&text_layout, &font);
+    assert_eq!(text_layer.dimensions(), (text_layout.width, text_layout.height));
+
+    // The glyphs of HELLO don't overlap, so every pixel of every crop is where its placement says
+    let atlas = font.pages()[0].to_rgba8();
+    for glyph in &text_layout.glyphs {
+        let crop = imageops::crop_imm(&atlas, glyph.crop_x, glyph.crop_y, glyph.crop_width, glyph.crop_height).to_image();
+        for (x, y, pixel) in crop.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] == 255) {
+            assert_eq!(text_layer.get_pixel((glyph.x + x as i64) as u32, (glyph.y + y as i64) as u32), pixel);
+        }
+    }
+}
+
+#[test]
+fn baseline_guide_is_one_red_row() {
+    let text_layout = layout_with(&embedded_font(), "HELLO", &RenderOptions::default()).unwrap();
+    let baseline = baseline_guide(&text_layout);
+
+    assert_eq!(baseline.dimensions(), (text_layout.width, text_layout.height));
+    for (_, y, pixel) in baseline.enumerate_pixels() {
+        let expected = if y as i32 == text_layout.baseline { Rgba([255, 0, 0, 255]) } else { Rgba([0, 0, 0, 0]) };
+        assert_eq!(*pixel, expected);
+    }
+}
+
+#[test]
+fn guides_highlight_the_inked_columns_at_the_given_size() {
+    let font = embedded_font();
+    let text_layout = layout_with(&font, "HI THERE", &RenderOptions::default()).unwrap();
+    let text_layer = rasterize_glyphs(&text_layout, &font);
+    let guides = render_guides(&GuideTemplate::current_bands(), &text_layout, &font, text_layout.width, 32, false);
+
+    assert_eq!(guides.dimensions(), (text_layout.width, 32));
+    assert_eq!(inked_columns(&guides), inked_columns(&text_layer));
+    for x in inked_columns(&guides) {
+        assert!((0..32).all(|y| guides.get_pixel(x, y)[3] == 128), "column {}", x);
+    }
+
+    // Wider than the line, the columns scale the way scale_nearest scales the text
+    let (width, height) = (text_layout.width * 3, text_layout.height * 3);
+    let guides = render_guides(&GuideTemplate::current_bands(), &text_layout, &font, width, height, false);
+    assert_eq!(inked_columns(&guides), inked_columns(&scale_nearest(&text_layer, width, height)));
+}
+
+// The baseline is a layer of its own now, so it can't light up the guides. The baseline commit
+// found the text's columns by skipping pure red pixels, which also skipped pure red ink
+#[test]
+fn guides_ignore_the_baseline_but_not_red_ink() {
+    let font = embedded_font();
+    let options = RenderOptions::default().draw_baseline(true);
+    let layers = render_layers_with(&font, "HI", &options).unwrap();
+    assert_eq!(inked_columns(&layers.guides), inked_columns(&layers.text));
+    assert!(inked_columns(&layers.guides).len() < layers.guides.width() as usize);
+
+    let red = render_layers_with(&font, "HI", &options.tint(Rgba([255, 0, 0, 255]))).unwrap();
+    assert_eq!(inked_columns(&red.guides), inked_columns(&layers.text));
+}
+
+#[test]
+fn compose_stacks_the_layers_bottom_to_top() {
+    let mut bottom = RgbaImage::from_pixel(3, 2, Rgba([0, 0, 255, 255]));
+    bottom.put_pixel(2, 1, Rgba([0, 0, 0, 0]));
+    let mut top = RgbaImage::new(2, 2);
+    top.put_pixel(1, 0, Rgba([255, 0, 0, 255]));
+
+    let composed = compose(4, 2, &[&bottom, &top]);
+    assert_eq!(composed.dimensions(), (4, 2));
+    assert_eq!(*composed.get_pixel(0, 0), Rgba([0, 0, 255, 255]));
+    assert_eq!(*composed.get_pixel(1, 0), Rgba([255, 0, 0, 255]));
+    assert_eq!(*composed.get_pixel(2, 1), Rgba([0, 0, 0, 0]));
+    assert_eq!(*composed.get_pixel(3, 0), Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn render_text_with_composes_the_layers() {
+    let font = embedded_font();
+    let options = RenderOptions::default().draw_baseline(true).kerning(true);
+    let layers = render_layers_with(&font, "AV WA TO", &options).unwrap();
+    let (width, height) = layers.dimensions();
+
+    assert_eq!(render_text_with(&font, "AV WA TO", &options).unwrap(), compose(width, height, &[&layers.guides, &layers.baseline, &layers.text]));
+}
+
+#[test]
+fn layers_from_an_existing_layout_match_render_layers_with() {
+    let font = embedded_font();
+    for options in [RenderOptions::default().draw_baseline(true).kerning(true), RenderOptions::default().scale(2.0).shadow(true), RenderOptions::default().legacy_guide_scale(true)] {
+        let from_text = render_layers_with(&font, "AV WA TO", &options).unwrap();
+        let from_layout = render_layout_layers(&font, &layout_with(&font, "AV WA TO", &options).unwrap(), &options).unwrap();
+        assert!(from_layout.guides == from_text.guides && from_layout.baseline == from_text.baseline && from_layout.text == from_text.text);
+    }
+}
+
+#[test]
+fn below_1x_legacy_guides_leave_the_image_as_tall_as_the_text() {
+    let font = embedded_font();
+    let options = RenderOptions::default().scale(0.5).legacy_guide_scale(true);
+    let layers = render_layers_with(&font, "HELLO", &options).unwrap();
+    assert!(layers.guides.height() < layers.text.height());
+    assert_eq!(layers.dimensions(), layers.text.dimensions());
+
+    // Every row of ink makes it into the image, and the guides still cover only their own rows
+    let rendered = render_text_with(&font, "HELLO", &options).unwrap();
+    assert_eq!(rendered.dimensions(), layers.text.dimensions());
+    for (x, y, pixel) in layers.text.enumerate_pixels().filter(|(_, _, pixel)| pixel[3] == 255) {
+        assert_eq!(rendered.get_pixel(x, y), pixel);
+    }
+    assert!((layers.guides.height()..rendered.height()).all(|y| (0..rendered.width()).all(|x| rendered.get_pixel(x, y) == layers.text.get_pixel(x, y))));
+    assert_eq!(measure_text(&font, "HELLO", &options).unwrap().height, rendered.height());
+}
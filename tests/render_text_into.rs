@@ -0,0 +1,57 @@
+// render_text_into draws the same pixels render_text_with returns, just onto an existing image,
+// and reports what fell outside it
+use image::{Rgba, RgbaImage, imageops};
+use minecraft_titles::{Outline, RenderOptions, render_text_into, render_text_with};
+mod common;
+use common::embedded_font;
+
+fn options() -> [RenderOptions; 4] {
+    [
+        RenderOptions::default(),
+        RenderOptions::default().kerning(true).draw_baseline(true),
+        RenderOptions::default().legacy_guide_scale(true).shadow(true),
+        RenderOptions::default().scale(2.0).outline(Some(Outline::new(Rgba([0, 0, 0, 255]), 1))),
+    ]
+}
+
+#[test]
+fn a_fresh_buffer_gets_exactly_the_rendered_image() {
+    let font = embedded_font();
+    for options in options() {
+        let expected = render_text_with(&font, "T-SHIRT .? HELLO", &options).unwrap();
+        let mut target = RgbaImage::new(expected.width(), expected.height());
+
+        let info = render_text_into(&font, "T-SHIRT .? HELLO", &options, &mut target, 0, 0).unwrap();
+        assert!(target == expected);
+        assert_eq!((info.width, info.height), expected.dimensions());
+        assert_eq!((info.clipped_left, info.clipped_right, info.clipped_top, info.clipped_bottom), (0, 0, 0, 0));
+    }
+}
+
+#[test]
+fn an_offset_leaves_the_rest_of_the_target_alone() {
+    let font = embedded_font();
+    let expected = render_text_with(&font, "HELLO", &RenderOptions::default()).unwrap();
+    let backdrop = Rgba([10, 20, 30, 255]);
+    let mut target = RgbaImage::from_pixel(expected.width() + 10, expected.height() + 6, backdrop);
+
+    render_text_into(&font, "HELLO", &RenderOptions::default(), &mut target, 4, 3).unwrap();
+    let mut on_backdrop = RgbaImage::from_pixel(expected.width(), expected.height(), backdrop);
+    imageops::overlay(&mut on_backdrop, &expected, 0, 0);
+    assert!(imageops::crop_imm(&target, 4, 3, expected.width(), expected.height()).to_image() == on_backdrop);
+    assert!(target.enumerate_pixels()
+        .filter(|(x, y, _)| !(4..4 + expected.width()).contains(x) || !(3..3 + expected.height()).contains(y))
+        .all(|(_, _, pixel)| *pixel == backdrop));
+}
+
+#[test]
+fn what_falls_outside_the_target_is_reported() {
+    let font = embedded_font();
+    let expected = render_text_with(&font, "HELLO", &RenderOptions::default()).unwrap();
+    let mut target = RgbaImage::new(expected.width(), expected.height());
+
+    let info = render_text_into(&font, "HELLO", &RenderOptions::default(), &mut target, -2, 5).unwrap();
+    assert_eq!((info.clipped_left, info.clipped_right, info.clipped_top, info.clipped_bottom), (2, 0, 0, 5));
+    let info = render_text_into(&font, "HELLO", &RenderOptions::default(), &mut target, 3, -1).unwrap();
+    assert_eq!((info.clipped_left, info.clipped_right, info.clipped_top, info.clipped_bottom), (0, 3, 1, 0));
+}
@@ -0,0 +1,19 @@
+// sanitize_text strips control, zero-width and bidi format characters and counts each kind it
+// removed. Newlines and tabs stay, and so does everything the font might have a glyph for
+use minecraft_titles::sanitize_text;
+
+#[test]
+fn invisible_characters_are_counted_and_removed() {
+    let (sanitized, removed) = sanitize_text("HEL\u{200B}LO\u{200B} \u{202E}WORLD\u{7}\u{FEFF}");
+    assert_eq!(sanitized, "HELLO WORLD");
+    assert_eq!(removed, ["Removed 2 x U+200B", "Removed 1 x U+202E", "Removed 1 x U+0007", "Removed 1 x U+FEFF"]);
+}
+
+#[test]
+fn newlines_tabs_and_printable_text_are_kept() {
+    for text in ["HELLO\nWORLD", "A\tB", "Ünïcödé ½ §6", ""] {
+        assert_eq!(sanitize_text(text), (text.to_string(), Vec::new()));
+    }
+    // C1 controls go as well as C0 ones
+    assert_eq!(sanitize_text("A\u{85}\u{0}B").0, "AB");
+}
@@ -0,0 +1,66 @@
+// The scale applies to the whole render: the glyphs are scaled along with the guides, nearest
+// neighbor, so the highlighted columns still cover exactly the ink and the output is as large as the
+// scaled text. The legacy guide scale scales the guides alone, the way older versions did
+use image::RgbaImage;
+use minecraft_titles::{RenderOptions, layout_with, measure_text, render_layers_with, render_text_with};
+mod common;
+use common::embedded_font;
+
+// Leftmost, topmost, rightmost and bottommost non-transparent pixels
+fn bounding_box(image: &RgbaImage) -> (u32, u32, u32, u32) {
+    image.enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[3] > 0)
+        .fold((u32::MAX, u32::MAX, 0, 0), |(left, top, right, bottom), (x, y, _)| (left.min(x), top.min(y), right.max(x), bottom.max(y)))
+}
+
+#[test]
+fn highlight_covers_the_ink_at_scale_2() {
+    let layers = render_layers_with(&embedded_font(), "Hello World", &RenderOptions::default().scale(2.0)).unwrap();
+
+    let (text_left, _, text_right, _) = bounding_box(&layers.text);
+    let (guide_left, _, guide_right, _) = bounding_box(&layers.guides);
+    assert_eq!((text_left, text_right), (guide_left, guide_right));
+    for x in 0..layers.text.width() {
+        let inked = (0..layers.text.height()).any(|y| layers.text.get_pixel(x, y)[3] > 0);
+        assert_eq!(inked, layers.guides.get_pixel(x, 0)[3] > 0, "column {}", x);
+    }
+}
+
+#[test]
+fn output_is_the_scaled_text() {
+    let font = embedded_font();
+    let layout = layout_with(&font, "Hello", &RenderOptions::default()).unwrap();
+    let options = RenderOptions::default().scale(2.0);
+
+    let image = render_text_with(&font, "Hello", &options).unwrap();
+    assert_eq!(image.dimensions(), (layout.width * 2, layout.height * 2));
+    let metrics = measure_text(&font, "Hello", &options).unwrap();
+    assert_eq!((metrics.width, metrics.height), image.dimensions());
+
+    // Every texel becomes a crisp 2 x 2 block
+    let plain = render_layers_with(&font, "Hello", &RenderOptions::default().scale(1.0)).unwrap().text;
+    let scaled = render_layers_with(&font, "Hello", &options).unwrap().text;
+    assert!(scaled.enumerate_pixels().all(|(x, y, pixel)| pixel == plain.get_pixel(x / 2, y / 2)));
+}
+
+#[test]
+fn text_and_highlight_share_a_bounding_box_by_default() {
+    let font = embedded_font();
+    let layers = render_layers_with(&font, "Hello", &RenderOptions::default().scale(2.0)).unwrap();
+    let layout = layout_with(&font, "Hello", &RenderOptions::default()).unwrap();
+    assert_eq!(layers.text.dimensions(), (layout.width * 2, layout.height * 2));
+    assert_eq!(layers.guides.dimensions(), layers.text.dimensions());
+
+    let (text_left, _, text_right, _) = bounding_box(&layers.text);
+    let (guide_left, _, guide_right, _) = bounding_box(&layers.guides);
+    assert_eq!((guide_left, guide_right), (text_left, text_right));
+}
+
+#[test]
+fn the_legacy_guide_scale_scales_the_guides_alone() {
+    let font = embedded_font();
+    let layers = render_layers_with(&font, "Hello", &RenderOptions::default().scale(2.0).legacy_guide_scale(true)).unwrap();
+    let layout = layout_with(&font, "Hello", &RenderOptions::default()).unwrap();
+    assert_eq!(layers.text.dimensions(), (layout.width, layout.height));
+    assert_eq!(layers.guides.height(), 32);
+}
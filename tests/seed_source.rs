@@ -0,0 +1,39 @@
+// Seeds for randomized effects. FromText must stay the documented FNV-1a hash, so a title keeps its
+// look across versions, machines and runs
+use minecraft_titles::SeedSource;
+
+fn sequence(seed: SeedSource, text: &str) -> Vec<u64> {
+    let mut rng = seed.rng(text);
+    (0..8).map(|_| rng.next_u64()).collect()
+}
+
+#[test]
+fn from_text_is_the_fnv_1a_hash() {
+    assert_eq!(SeedSource::FromText.seed(""), 0xcbf29ce484222325);
+    assert_eq!(SeedSource::FromText.seed("a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(SeedSource::FromText.seed("foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn from_text_repeats_for_the_same_text_only() {
+    assert_eq!(sequence(SeedSource::FromText, "MY TITLE"), sequence(SeedSource::FromText, "MY TITLE"));
+    assert_ne!(sequence(SeedSource::FromText, "MY TITLE"), sequence(SeedSource::FromText, "MY TITLE!"));
+}
+
+#[test]
+fn fixed_ignores_the_text() {
+    assert_eq!(sequence(SeedSource::Fixed(7), "MY TITLE"), sequence(SeedSource::Fixed(7), "OTHER"));
+    assert_ne!(sequence(SeedSource::Fixed(7), "MY TITLE"), sequence(SeedSource::Fixed(8), "MY TITLE"));
+}
+
+#[test]
+fn random_gives_every_render_its_own_seed() {
+    assert_ne!(sequence(SeedSource::Random, "MY TITLE"), sequence(SeedSource::Random, "MY TITLE"));
+}
+
+#[test]
+fn below_stays_in_range() {
+    let mut rng = SeedSource::FromText.rng("MY TITLE");
+    assert!((0..1000).all(|_| rng.below(6) < 6));
+    assert_eq!(rng.below(0), 0);
+}
@@ -0,0 +1,54 @@
+// The pen is signed, so kerning can pull a glyph left of the one before it, even past the start of
+// the text. The line then shifts right until nothing is left of the canvas, and no ink is cut off
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderOptions, layout_with, render_layers_with};
+
+// 'A' has ink in the two left columns of its 5 px rect, so kerning "AA" by -3 slots each one into the
+// empty columns of the one before. ".A" kerns by more than the '.' advances
+const KERNED_FNT: &str = "\
+info face=\"Kerned\" size=5 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=16 scaleH=5 pages=1
+page id=0 file=\"kerned.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=46 x=6 y=0 width=2 height=5 xoffset=0 yoffset=0 xadvance=3 page=0
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0
+kerning first=65 second=65 amount=-3
+kerning first=46 second=65 amount=-8
+";
+
+fn kerned_font() -> Font {
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_fn(16, 5, |x, _| if matches!(x, 0 | 1 | 6 | 7) { Rgba([255, 255, 255, 255]) } else { Rgba([0, 0, 0, 0]) }));
+    let options = FontLoadOptions { glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }, ..FontLoadOptions::default() };
+    Font::from_fnt_with(KERNED_FNT.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+fn inked_columns(text: &str) -> Vec<u32> {
+    let text_layer = render_layers_with(&kerned_font(), text, &RenderOptions::default().kerning(true).scale(1.0)).unwrap().text;
+    (0..text_layer.width()).filter(|x| (0..text_layer.height()).any(|y| text_layer.get_pixel(*x, y)[3] > 0)).collect()
+}
+
+#[test]
+fn kerning_interleaves_without_collisions() {
+    let text_layout = layout_with(&kerned_font(), "AAAA", &RenderOptions::default().kerning(true)).unwrap();
+    assert_eq!(text_layout.glyphs.iter().map(|glyph| glyph.x).collect::<Vec<_>>(), [0, 3, 6, 9]);
+    // The last 'A' still advances its full 6 px
+    assert_eq!(text_layout.width, 15);
+    assert_eq!(inked_columns("AAAA"), [0, 1, 3, 4, 6, 7, 9, 10]);
+}
+
+#[test]
+fn kerning_past_the_start_shifts_the_line() {
+    // The 'A' lands 5 px left of the '.', so both move right by 5
+    let text_layout = layout_with(&kerned_font(), ".A", &RenderOptions::default().kerning(true)).unwrap();
+    let placed: Vec<(i64, i64, i64)> = text_layout.glyphs.iter().map(|glyph| (glyph.x, glyph.pen_x, glyph.kerning)).collect();
+    assert_eq!(placed, [(5, 5, 0), (0, 0, -8)]);
+    assert_eq!(text_layout.width, 7);
+    assert_eq!(inked_columns(".A"), [0, 1, 5, 6]);
+}
+
+#[test]
+fn without_kerning_nothing_moves() {
+    let text_layout = layout_with(&kerned_font(), ".AA", &RenderOptions::default()).unwrap();
+    assert_eq!(text_layout.glyphs.iter().map(|glyph| glyph.x).collect::<Vec<_>>(), [0, 3, 9]);
+    assert!(text_layout.glyphs.iter().all(|glyph| glyph.kerning == 0));
+}
@@ -0,0 +1,67 @@
+// A font without a space glyph gets one synthesized from half its median letter advance, so words
+// keep their gaps in layout, measurement, rendering and wrapping
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderOptions, layout_with, measure_text, render_text_with, wrap_lines};
+
+// Solid 5x5 letters that advance 6 px, and 8 px for 'C', so the median advance is 6. The rects have
+// no padding and the advances no negative spacing, so nothing is trimmed
+const SPACELESS_FNT: &str = "\
+info face=\"Spaceless\" size=5 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=24 scaleH=5 pages=1
+page id=0 file=\"spaceless.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=66 x=6 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=67 x=12 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=8 page=0
+";
+const SPACE_GLYPH: &str = "char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=3 page=0\n";
+
+fn load(fnt: &str) -> Font {
+    let options = FontLoadOptions {
+        standard_vertical_metrics: true,
+        glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 },
+        ..FontLoadOptions::default()
+    };
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_pixel(24, 5, Rgba([255, 255, 255, 255])));
+    Font::from_fnt_with(fnt.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+fn spaceless_font() -> Font {
+    load(SPACELESS_FNT)
+}
+
+// The same font with the 3 px space glyph the synthesized one should match
+fn spaced_font() -> Font {
+    load(&format!("{}{}", SPACELESS_FNT, SPACE_GLYPH))
+}
+
+#[test]
+fn the_space_advance_is_synthesized_and_reported() {
+    let font = spaceless_font();
+    assert!(!font.has(' '));
+    assert_eq!(font.advance(' '), Some(3));
+    assert_eq!(font.warnings(), ["The font has no space glyph; using a synthesized 3 px advance"]);
+    assert!(spaced_font().warnings().is_empty());
+}
+
+#[test]
+fn words_are_placed_a_space_apart() {
+    let text_layout = layout_with(&spaceless_font(), "AB CA", &RenderOptions::default()).unwrap();
+    let pens: Vec<(u32, i64)> = text_layout.glyphs.iter().filter(|glyph| glyph.crop_width > 0).map(|glyph| (glyph.char_id, glyph.pen_x)).collect();
+    assert_eq!(pens, [(65, 0), (66, 6), (67, 15), (65, 23)]);
+    assert_eq!(text_layout.width, 29);
+}
+
+#[test]
+fn the_synthesized_space_matches_a_real_one() {
+    let (spaceless, spaced) = (spaceless_font(), spaced_font());
+    let options = RenderOptions::default().vertical_padding(0);
+
+    for text in ["A B", "AB  CA", "C A B C"] {
+        assert_eq!(measure_text(&spaceless, text, &options).unwrap().width, measure_text(&spaced, text, &options).unwrap().width, "{}", text);
+        assert!(render_text_with(&spaceless, text, &options).unwrap() == render_text_with(&spaced, text, &options).unwrap(), "{}", text);
+    }
+    // "AB CA" is 29 px wide, so at 28 px it wraps at the space
+    assert_eq!(wrap_lines(&spaceless, "AB CA", 28, &options).unwrap(), ["AB", "CA"]);
+    assert_eq!(wrap_lines(&spaceless, "AB CA", 29, &options).unwrap(), wrap_lines(&spaced, "AB CA", 29, &options).unwrap());
+    assert_eq!(wrap_lines(&spaceless, "AB CA", 29, &options).unwrap(), ["AB CA"]);
+}
@@ -0,0 +1,65 @@
+// The generated fixture font behind the test-support feature: its .fnt and atlas load like any other
+// font, and the glyphs come out as the 3x5 bitmaps they were drawn from
+use image::RgbaImage;
+use minecraft_titles::test_support::{fixture_atlas, fixture_fnt, fixture_font, render_fixture};
+use minecraft_titles::{Font, RenderOptions, render_layers_with};
+
+fn loaded() -> Font {
+    Font::from_fnt(fixture_fnt().as_bytes(), vec![image::DynamicImage::ImageRgba8(fixture_atlas())]).unwrap()
+}
+
+// The ink inside a rect as rows of '#' and '.', joined with '/'
+fn bitmap(image: &RgbaImage, x: u32, y: u32, width: u32, height: u32) -> String {
+    (y..y + height)
+        .map(|row| (x..x + width).map(|column| if image.get_pixel(column, row)[3] > 0 { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// The glyph's cell in the atlas with the padding column on each side left off
+fn atlas_bitmap(font: &Font, ch: char) -> String {
+    let glyph = font.glyph(ch).unwrap();
+    bitmap(&fixture_atlas(), glyph.x() + 1, glyph.y(), glyph.width() - 2, glyph.height())
+}
+
+#[test]
+fn the_generated_files_load_as_a_font() {
+    let font = loaded();
+    assert!(font.warnings().is_empty(), "{:?}", font.warnings());
+    // 59 drawn glyphs, their 26 lowercase copies and the space
+    assert_eq!(font.chars().count(), 86);
+    assert_eq!(font.info().face, "Fixture 3x5");
+    let metrics = font.metrics().unwrap();
+    assert_eq!((metrics.line_height, metrics.base, metrics.scale_w, metrics.scale_h), (7, 5, 80, 30));
+    assert_eq!(font.page_files(), ["fixture.png"]);
+    assert!(fixture_font().chars().eq(font.chars()));
+}
+
+#[test]
+fn glyph_metrics() {
+    let font = loaded();
+    let fields = |ch: char| font.glyph(ch).map(|glyph| (glyph.id(), glyph.x(), glyph.y(), glyph.width(), glyph.height(), glyph.xoffset(), glyph.yoffset(), glyph.xadvance()));
+    assert_eq!(fields('A'), Some((65, 0, 0, 5, 5, 0, 0, 7)));
+    assert_eq!(fields('?'), Some((63, 35, 10, 5, 5, 0, 0, 7)));
+    assert_eq!(fields(' '), Some((32, 0, 0, 0, 0, 0, 0, 5)));
+    assert_eq!((font.kerning('A', 'V'), font.kerning('V', 'A'), font.kerning('L', 'T'), font.kerning('T', 'L')), (-1, -1, -1, 0));
+}
+
+#[test]
+fn glyph_bitmaps() {
+    let font = loaded();
+    assert_eq!(atlas_bitmap(&font, 'A'), ".#./#.#/###/#.#/#.#");
+    assert_eq!(atlas_bitmap(&font, '?'), "##./..#/.#./.../.#.");
+    assert_eq!(atlas_bitmap(&font, '4'), "#.#/#.#/###/..#/..#");
+    // Lowercase letters are copies of the capitals in cells of their own
+    assert_eq!(atlas_bitmap(&font, 'a'), atlas_bitmap(&font, 'A'));
+    assert_ne!(font.glyph('a').unwrap().x(), font.glyph('A').unwrap().x());
+}
+
+#[test]
+fn rendering_draws_the_bitmap() {
+    let text_layer = render_layers_with(&fixture_font(), "A", &RenderOptions::default().scale(1.0)).unwrap().text;
+    let top = (0..text_layer.height()).find(|y| (0..text_layer.width()).any(|x| text_layer.get_pixel(x, *y)[3] > 0)).unwrap();
+    assert_eq!(bitmap(&text_layer, 0, top, 3, 5), ".#./#.#/###/#.#/#.#");
+    assert!(render_fixture("A").is_ok());
+}
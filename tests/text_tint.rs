@@ -0,0 +1,29 @@
+// The tint multiplies into the white font: white changes nothing, other colors keep the glyph alpha
+use image::Rgba;
+use minecraft_titles::{RenderOptions, render_layers_with};
+mod common;
+use common::embedded_font;
+
+#[test]
+fn white_tint_changes_nothing() {
+    let font = embedded_font();
+    let plain = render_layers_with(&font, "Hello", &RenderOptions::default()).unwrap().text;
+    let white = render_layers_with(&font, "Hello", &RenderOptions::default().tint(Rgba([255, 255, 255, 255]))).unwrap().text;
+    assert!(plain == white);
+}
+
+#[test]
+fn tint_multiplies_every_glyph_pixel() {
+    let font = embedded_font();
+    let orange = Rgba([255, 170, 0, 255]);
+    let plain = render_layers_with(&font, "Hello", &RenderOptions::default()).unwrap().text;
+    let tinted = render_layers_with(&font, "Hello", &RenderOptions::default().tint(orange)).unwrap().text;
+
+    for (plain, tinted) in plain.pixels().zip(tinted.pixels()) {
+        assert_eq!(tinted[3], plain[3]);
+        for channel in 0..3 {
+            assert_eq!(tinted[channel] as u32, plain[channel] as u32 * orange[channel] as u32 / 255);
+        }
+    }
+    assert!(tinted.pixels().any(|pixel| pixel[3] > 0 && pixel[0] == 255 && pixel[1] == 170 && pixel[2] == 0));
+}
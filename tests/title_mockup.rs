@@ -0,0 +1,42 @@
+// The title screen preview: the title blown up by the GUI scale and placed where the game draws its
+// logo, centered and 30 GUI pixels down
+use image::{Rgba, RgbaImage};
+use minecraft_titles::{MOCKUP_SCREEN_SIZE, title_screen_mockup, title_screen_position};
+mod common;
+use common::embedded_font;
+
+#[test]
+fn position_is_centered_in_gui_pixels() {
+    // 1920 px at scale 2 is 960 GUI px wide; a 274 px logo is 137 GUI px each side of the center
+    assert_eq!(title_screen_position(548, 1920, 2), (480 * 2 - 137 * 2, 60));
+    // 1920 / 7 rounds up to 275 GUI px, as the game rounds it
+    assert_eq!(title_screen_position(70, 1920, 7), ((137 - 5) * 7, 210));
+    // Titles wider than the screen start at its left edge
+    assert_eq!(title_screen_position(4000, 1920, 2).0, 0);
+}
+
+#[test]
+fn every_texel_becomes_a_block_of_screen_pixels() {
+    let mut title = RgbaImage::new(3, 2);
+    title.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+    title.put_pixel(2, 1, Rgba([0, 0, 255, 255]));
+    let mockup = title_screen_mockup(&embedded_font(), &title, 3).unwrap();
+
+    assert_eq!(mockup.dimensions(), MOCKUP_SCREEN_SIZE);
+    let (x, y) = title_screen_position(9, MOCKUP_SCREEN_SIZE.0, 3);
+    for (dx, dy) in (0..3).flat_map(|dx| (0..3).map(move |dy| (dx, dy))) {
+        assert_eq!(*mockup.get_pixel(x + dx, y + dy), Rgba([255, 0, 0, 255]));
+        assert_eq!(*mockup.get_pixel(x + 6 + dx, y + 3 + dy), Rgba([0, 0, 255, 255]));
+    }
+    // Transparent texels show the backdrop
+    assert_eq!(mockup.get_pixel(x + 3, y)[3], 255);
+    assert_ne!(*mockup.get_pixel(x + 3, y), Rgba([255, 0, 0, 255]));
+}
+
+#[test]
+fn screen_grows_for_a_wide_title() {
+    let title = RgbaImage::from_pixel(1000, 10, Rgba([255, 255, 255, 255]));
+    let mockup = title_screen_mockup(&embedded_font(), &title, 4).unwrap();
+    assert_eq!(mockup.width(), 4000);
+    assert_eq!(*mockup.get_pixel(0, 120), Rgba([255, 255, 255, 255]));
+}
@@ -0,0 +1,58 @@
+// A trimmed font written to disk loads back like any other font and lays out and renders its
+// characters exactly as the full font does. Only where the glyphs sit in the atlas changes
+use std::fs;
+use std::path::PathBuf;
+use minecraft_titles::{Font, RenderOptions, layout_with, page_files, render_text_with, trim_font};
+mod common;
+use common::embedded_font;
+
+const BATCH: [&str; 3] = ["HELLO WORLD", "T-SHIRT .?", "Wavy Title"];
+
+fn write_and_reload(font: &Font, chars: &str, name: &str) -> Font {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let (fnt, atlas) = trim_font(font, chars, "trimmed.png");
+    fs::write(dir.join("trimmed.fnt"), fnt).unwrap();
+    atlas.save(dir.join("trimmed.png")).unwrap();
+
+    let fnt = fs::read(dir.join("trimmed.fnt")).unwrap();
+    let pages = page_files(&fnt).unwrap().iter().map(|file| image::open(dir.join(file)).unwrap()).collect();
+    Font::from_fnt(&fnt, pages).unwrap()
+}
+
+#[test]
+fn trimmed_font_renders_the_batch_identically() {
+    let font = embedded_font();
+    let trimmed = write_and_reload(&font, &BATCH.concat(), "trimmed_font_renders_the_batch_identically");
+
+    for options in [RenderOptions::default(), RenderOptions::default().kerning(true).draw_baseline(true)] {
+        for text in BATCH {
+            let original = layout_with(&font, text, &options).unwrap();
+            let reloaded = layout_with(&trimmed, text, &options).unwrap();
+            assert_eq!((reloaded.width, reloaded.height, reloaded.baseline), (original.width, original.height, original.baseline), "{}", text);
+            assert_eq!(reloaded.glyphs.len(), original.glyphs.len());
+            for (reloaded, original) in reloaded.glyphs.iter().zip(&original.glyphs) {
+                assert_eq!(
+                    (reloaded.char_id, reloaded.x, reloaded.y, reloaded.pen_x, reloaded.advance, reloaded.kerning, reloaded.crop_width, reloaded.crop_height),
+                    (original.char_id, original.x, original.y, original.pen_x, original.advance, original.kerning, original.crop_width, original.crop_height),
+                );
+            }
+
+            assert!(render_text_with(&trimmed, text, &options).unwrap() == render_text_with(&font, text, &options).unwrap(), "{}", text);
+        }
+    }
+}
+
+#[test]
+fn only_the_used_characters_and_their_kerning_are_kept() {
+    let font = embedded_font();
+    let trimmed = write_and_reload(&font, "T-.", "only_the_used_characters_and_their_kerning_are_kept");
+
+    assert!(trimmed.has('T') && trimmed.has('-') && trimmed.has('.'));
+    assert!(!trimmed.has('?') && !trimmed.has('H'));
+    assert_eq!(trimmed.kerning('T', '-'), font.kerning('T', '-'));
+    assert_ne!(trimmed.kerning('T', '-'), 0);
+    assert!(trimmed.pages()[0].width() * trimmed.pages()[0].height() < font.pages()[0].width() * font.pages()[0].height());
+}
@@ -0,0 +1,103 @@
+// wrap_lines keeps every line within the width, measured the way the renderer lays it out, § codes
+// carried over from the lines before it included
+use minecraft_titles::{RenderOptions, formatting_codes_at_end, layout_with, strip_formatting_codes, wrap_lines};
+mod common;
+use common::embedded_font;
+
+const PARAGRAPH: &str = "Minecraft splash texts can run long, like this one, which would otherwise be squashed into a single very wide texture by the game";
+
+#[test]
+fn every_line_fits_the_width() {
+    let font = embedded_font();
+    for options in [RenderOptions::default(), RenderOptions::default().kerning(true)] {
+        let lines = wrap_lines(&font, PARAGRAPH, 128, &options).unwrap();
+
+        assert!(lines.len() > 1);
+        for line in &lines {
+            let width = layout_with(&font, line, &options).unwrap().width;
+            assert!(width <= 128, "{:?} is {} px wide", line, width);
+        }
+        // Only the spaces between lines are dropped
+        assert_eq!(lines.join(" "), PARAGRAPH);
+    }
+}
+
+#[test]
+fn lines_are_filled_greedily() {
+    let font = embedded_font();
+    let options = RenderOptions::default();
+    let lines = wrap_lines(&font, PARAGRAPH, 128, &options).unwrap();
+
+    // No line could have taken the next line's first word as well
+    for pair in lines.windows(2) {
+        let next_word = pair[1].split(' ').next().unwrap();
+        let longer = format!("{} {}", pair[0], next_word);
+        assert!(layout_with(&font, &longer, &options).unwrap().width > 128);
+    }
+}
+
+#[test]
+fn over_long_words_are_broken() {
+    let font = embedded_font();
+    let options = RenderOptions::default();
+    let lines = wrap_lines(&font, "A SUPERCALIFRAGILISTIC WORD", 48, &options).unwrap();
+
+    assert!(lines.iter().all(|line| layout_with(&font, line, &options).unwrap().width <= 48), "{:?}", lines);
+    assert_eq!(lines.concat().replace(' ', ""), "ASUPERCALIFRAGILISTICWORD");
+}
+
+#[test]
+fn characters_wider_than_the_width_still_render() {
+    let lines = wrap_lines(&embedded_font(), "WW", 1, &RenderOptions::default()).unwrap();
+    assert_eq!(lines, vec!["W", "W"]);
+}
+
+#[test]
+fn text_that_fits_stays_on_one_line() {
+    assert_eq!(wrap_lines(&embedded_font(), "HELLO  WORLD", 256, &RenderOptions::default()).unwrap(), vec!["HELLO WORLD"]);
+}
+
+// Each line is measured in the style the lines before it carried over, so a §l still in effect
+// widens it. Without that, a line that only fits unbolded would be drawn bold and too wide
+#[test]
+fn a_carried_bold_counts_toward_the_width() {
+    let font = embedded_font();
+    let options = RenderOptions::default();
+    let max_width = layout_with(&font, "CD EF", &options).unwrap().width;
+    let lines = wrap_lines(&font, "§lAB CD EF", max_width, &options).unwrap();
+
+    assert_eq!(lines, ["§lAB", "§lCD", "§lEF"]);
+    for line in &lines {
+        assert!(layout_with(&font, line, &options).unwrap().width <= max_width, "{:?}", line);
+    }
+}
+
+// A word broken between characters never splits a § from its code, and codes go with the character
+// after them, so no line is left with nothing to draw
+#[test]
+fn formatted_words_break_between_drawn_characters() {
+    let font = embedded_font();
+    let options = RenderOptions::default();
+    let lines = wrap_lines(&font, "§6SUPER§lCALI§r§aFRAGILISTIC", 48, &options).unwrap();
+
+    assert!(lines.len() > 2, "{:?}", lines);
+    for line in &lines {
+        assert!(!line.ends_with('§') && !strip_formatting_codes(line).is_empty(), "{:?}", line);
+        assert!(layout_with(&font, line, &options).unwrap().width <= 48, "{:?}", line);
+    }
+    assert_eq!(lines.iter().map(|line| strip_formatting_codes(line)).collect::<String>(), "SUPERCALIFRAGILISTIC");
+    // Each line picks up the style the one before it ended in
+    for pair in lines.windows(2) {
+        assert!(pair[1].starts_with(&formatting_codes_at_end(&pair[0])), "{:?}", pair);
+    }
+}
+
+#[test]
+fn codes_on_their_own_join_the_next_line() {
+    let font = embedded_font();
+    let options = RenderOptions::default();
+    let max_width = layout_with(&font, "HELLO", &options).unwrap().width.max(layout_with(&font, "WORLD", &options).unwrap().width);
+    // With nothing drawn before them there is no space to keep either
+    assert_eq!(wrap_lines(&font, "§6 WORLD", max_width, &options).unwrap(), ["§6WORLD"]);
+    assert_eq!(wrap_lines(&font, "HELLO WORLD §6", max_width, &options).unwrap(), ["HELLO", "WORLD§6"]);
+}
@@ -0,0 +1,84 @@
+// XML .fnt files load into the same font as the text format, whatever order the attributes come in,
+// with xoffset and the other optional attributes defaulting when left out
+#![allow(deprecated)]
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{CharData, Font, RenderOptions, load_font_data, page_files, render_text_with};
+
+const TEXT_FNT: &str = "\
+info face=\"Tiny & Co\" size=5 padding=0,1,0,1 spacing=1,1
+common lineHeight=7 base=6 scaleW=16 scaleH=8 pages=1
+page id=0 file=\"tiny.png\"
+chars count=3
+char id=65 x=0 y=0 width=5 height=6 xoffset=0 yoffset=1 xadvance=7 page=0 chnl=15
+char id=66 x=5 y=0 width=5 height=6 xoffset=-1 yoffset=1 xadvance=6 page=0 chnl=15
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=4 page=0 chnl=15
+kernings count=1
+kerning first=65 second=66 amount=-2
+";
+
+// The same font with every element's attributes in a different order and the zero xoffsets left out
+const XML_FNT: &str = r#"<?xml version="1.0"?>
+<font>
+  <info padding="0,1,0,1" spacing="1,1" size="5" face="Tiny &amp; Co"/>
+  <common pages="1" scaleH="8" scaleW="16" base="6" lineHeight="7"/>
+  <pages>
+    <page file="tiny.png" id="0"/>
+  </pages>
+  <chars count="3">
+    <char chnl="15" page="0" xadvance="7" yoffset="1" height="6" width="5" y="0" x="0" id="65"/>
+    <char id="66" xoffset="-1" xadvance="6" x="5" y="0" width="5" height="6" yoffset="1" page="0" chnl="15"/>
+    <char xadvance="4" id="32" x="0" y="0" width="0" height="0" yoffset="0" page="0"/>
+  </chars>
+  <kernings count="1">
+    <kerning amount="-2" second="66" first="65"/>
+  </kernings>
+</font>
+"#;
+
+fn atlas() -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(16, 8, |x, _| if x % 5 == 4 { Rgba([0, 0, 0, 0]) } else { Rgba([255, 255, 255, 255]) }))
+}
+
+fn fields(char_data: &CharData) -> (u32, u32, u32, u32, u32, i32, i32, u32, u32) {
+    (
+        char_data.id(), char_data.x(), char_data.y(), char_data.width(), char_data.height(),
+        char_data.xoffset(), char_data.yoffset(), char_data.xadvance(), char_data.page(),
+    )
+}
+
+#[test]
+fn attribute_order_does_not_matter() {
+    let (text_glyphs, text_kerning) = load_font_data(TEXT_FNT.as_bytes()).unwrap();
+    let (xml_glyphs, xml_kerning) = load_font_data(XML_FNT.as_bytes()).unwrap();
+
+    assert_eq!(xml_glyphs.len(), 3);
+    for (id, char_data) in &text_glyphs {
+        assert_eq!(fields(&xml_glyphs[id]), fields(char_data), "glyph {}", id);
+    }
+    assert_eq!(xml_kerning, text_kerning);
+    assert_eq!(xml_kerning[&(65, 66)], -2);
+}
+
+#[test]
+fn a_missing_xoffset_is_zero() {
+    let (xml_glyphs, _) = load_font_data(XML_FNT.as_bytes()).unwrap();
+    assert_eq!(xml_glyphs[&65].xoffset(), 0);
+    assert_eq!(xml_glyphs[&32].xoffset(), 0);
+    assert_eq!(xml_glyphs[&66].xoffset(), -1);
+}
+
+#[test]
+fn loads_the_same_font_as_the_text_format() {
+    let text = Font::from_fnt(TEXT_FNT.as_bytes(), vec![atlas()]).unwrap();
+    let xml = Font::from_fnt(XML_FNT.as_bytes(), vec![atlas()]).unwrap();
+
+    assert_eq!(xml.info().face, "Tiny & Co");
+    assert_eq!((xml.info().size, xml.info().spacing, xml.info().padding), (text.info().size, text.info().spacing, text.info().padding));
+    let metrics = |font: &Font| font.metrics().map(|metrics| (metrics.line_height, metrics.base, metrics.scale_w, metrics.scale_h));
+    assert_eq!(metrics(&xml), Some((7, 6, 16, 8)));
+    assert_eq!(page_files(XML_FNT.as_bytes()).unwrap(), ["tiny.png"]);
+
+    for options in [RenderOptions::default(), RenderOptions::default().kerning(true)] {
+        assert!(render_text_with(&xml, "AB BA", &options).unwrap() == render_text_with(&text, "AB BA", &options).unwrap());
+    }
+}
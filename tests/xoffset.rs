@@ -0,0 +1,70 @@
+// A glyph's xoffset shifts its rect from the pen, counted from the offset most of the font's glyphs
+// share, and a negative one at the start of the text moves the rest right instead of underflowing
+use image::{DynamicImage, Rgba, RgbaImage};
+use minecraft_titles::{Font, FontLoadOptions, GlyphTrim, RenderOptions, layout_with, render_layers_with};
+
+// Solid 5x5 letters that advance 6 px. 'j' reaches 2 px back under the letter before it, ',' sits a
+// column forward, and the space leaves xoffset out
+const XOFFSET_FNT: &str = "\
+info face=\"Offsets\" size=5 padding=0,0,0,0
+common lineHeight=5 base=5 scaleW=24 scaleH=5 pages=1
+page id=0 file=\"offsets.png\"
+char id=65 x=0 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=66 x=6 y=0 width=5 height=5 xoffset=0 yoffset=0 xadvance=6 page=0
+char id=106 x=12 y=0 width=5 height=5 xoffset=-2 yoffset=0 xadvance=6 page=0
+char id=44 x=18 y=0 width=5 height=5 xoffset=1 yoffset=0 xadvance=6 page=0
+char id=32 x=0 y=0 width=0 height=0 yoffset=0 xadvance=3 page=0
+";
+
+fn offsets_font() -> Font {
+    let atlas = DynamicImage::ImageRgba8(RgbaImage::from_fn(24, 5, |x, _| if x % 6 == 5 { Rgba([0, 0, 0, 0]) } else { Rgba([255, 255, 255, 255]) }));
+    let options = FontLoadOptions { glyph_trim: GlyphTrim { crop_left: 0, crop_right: 0, advance_adjust: 0 }, ..FontLoadOptions::default() };
+    Font::from_fnt_with(XOFFSET_FNT.as_bytes(), vec![atlas], &options).unwrap()
+}
+
+// Rect and pen column of every glyph with ink
+fn columns(text: &str) -> Vec<(i64, i64)> {
+    let text_layout = layout_with(&offsets_font(), text, &RenderOptions::default()).unwrap();
+    text_layout.glyphs.iter().filter(|glyph| glyph.crop_width > 0).map(|glyph| (glyph.x, glyph.pen_x)).collect()
+}
+
+// Columns of the text layer with any ink in them
+fn inked_columns(text: &str) -> Vec<u32> {
+    let text_layer = render_layers_with(&offsets_font(), text, &RenderOptions::default().scale(1.0)).unwrap().text;
+    (0..text_layer.width()).filter(|x| (0..text_layer.height()).any(|y| text_layer.get_pixel(*x, y)[3] > 0)).collect()
+}
+
+#[test]
+fn xoffset_is_parsed_and_defaults_to_zero() {
+    let font = offsets_font();
+    assert_eq!(font.glyph('j').unwrap().xoffset(), -2);
+    assert_eq!(font.glyph(',').unwrap().xoffset(), 1);
+    assert_eq!(font.glyph(' ').unwrap().xoffset(), 0);
+}
+
+#[test]
+fn zero_offsets_place_glyphs_at_the_pen() {
+    assert_eq!(columns("AB A"), [(0, 0), (6, 6), (15, 15)]);
+    let expected: Vec<u32> = [0..5, 6..11, 15..20].into_iter().flatten().collect();
+    assert_eq!(inked_columns("AB A"), expected);
+}
+
+#[test]
+fn offsets_move_the_rect_but_not_the_pen() {
+    assert_eq!(columns("AjB"), [(0, 0), (4, 6), (12, 12)]);
+    assert_eq!(columns("A,B"), [(0, 0), (7, 6), (12, 12)]);
+    // 'j' overlaps the end of the 'A' and leaves a gap before the 'B'
+    let expected: Vec<u32> = [0..9, 12..17].into_iter().flatten().collect();
+    assert_eq!(inked_columns("AjB"), expected);
+}
+
+#[test]
+fn a_negative_offset_at_the_start_shifts_the_line() {
+    // 'j' would start 2 columns left of the canvas, so everything moves right by 2
+    assert_eq!(columns("jA"), [(0, 2), (8, 8)]);
+    let text_layout = layout_with(&offsets_font(), "jA", &RenderOptions::default()).unwrap();
+    assert_eq!(text_layout.width, 14);
+    let expected: Vec<u32> = [0..5, 8..13].into_iter().flatten().collect();
+    assert_eq!(inked_columns("jA"), expected);
+    assert_eq!(inked_columns("j"), [0, 1, 2, 3, 4]);
+}